@@ -68,6 +68,18 @@ app.post("/proof-user", async (req, res) => {
           type: "regex",
           value: '"id"\\s*:\\s*(?<id>\\d+)',
         },
+        {
+          type: "regex",
+          value: '"created_at"\\s*:\\s*"(?<created_at>[^"]+)"',
+        },
+        {
+          type: "regex",
+          value: '"public_repos"\\s*:\\s*(?<public_repos>\\d+)',
+        },
+        {
+          type: "regex",
+          value: '"followers"\\s*:\\s*(?<followers>\\d+)',
+        },
       ],
     };
     const client = clients[reclaimApplicationIndex];
@@ -0,0 +1,639 @@
+use cosmwasm_std::{
+    coin, coins, ensure, entry_point, to_json_binary, Addr, BankMsg, Binary, Decimal, Deps,
+    DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult, Timestamp, Uint128,
+};
+use lazydev_reward_interface::{
+    contract::STORAGE_ACCESS_INFALLIBLE_MSG,
+    event::RewardFeeEvent,
+    msg::{RewardMsg, RewardScope, ValidateRewardConfigMsg},
+    oracle::{OracleQueryMsg, PriceResponse},
+    reward::PrReward,
+};
+
+use crate::{
+    error::Error,
+    msg::{Config, ExecuteMsg, FiatRewardConfig, InstantiateMsg, MigrateMsg, QueryMsg},
+    state::{ACCUMULATED_FEES, ADMIN, CONFIG, REWARDS},
+};
+
+#[entry_point]
+#[allow(clippy::needless_pass_by_value)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    ADMIN
+        .save(deps.storage, &info.sender)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    REWARDS.init(deps.storage, &msg.lazydev_address);
+
+    CONFIG
+        .save(deps.storage, &msg.config)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    Ok(Response::default())
+}
+
+/// Whether this contract rewards PRs in `scope`.
+fn is_allowed(config: &Config, scope: &RewardScope) -> bool {
+    // org allowlists only apply to github, repos on other forges must be allowed individually
+    match scope {
+        RewardScope::Repo(repo) => {
+            (repo.forge.is_github() && config.valid_orgs.contains(&repo.org))
+                || config.valid_repos.contains(repo)
+        }
+        RewardScope::Org { forge, org } => forge.is_github() && config.valid_orgs.contains(org),
+    }
+}
+
+/// A reward in [`Config::denom`], priced at the oracle price of [`Config::quote`].
+#[derive(Debug, PartialEq)]
+struct PricedReward {
+    amount: Uint128,
+    quote_amount: Decimal,
+    price: Decimal,
+    price_updated_at: Timestamp,
+}
+
+impl PricedReward {
+    fn into_pr_reward(self, config: &Config) -> PrReward {
+        PrReward::PricedToken {
+            denom: config.denom.clone(),
+            amount: self.amount,
+            quote: config.quote.clone(),
+            quote_amount: self.quote_amount,
+            price: self.price,
+            price_updated_at: self.price_updated_at,
+        }
+    }
+}
+
+/// The reward for `msg` at the current oracle price, before the protocol fee is deducted.
+fn priced_reward(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    msg: &RewardMsg,
+) -> Result<PricedReward, Error> {
+    let reward_config =
+        FiatRewardConfig::parse(&msg.reward_config).map_err(Error::InvalidConfig)?;
+
+    let PriceResponse { price, updated_at } = deps
+        .querier
+        .query_wasm_smart(
+            &config.oracle,
+            &OracleQueryMsg::Price {
+                base: config.base.clone(),
+                quote: config.quote.clone(),
+            },
+        )
+        .map_err(Error::Oracle)?;
+
+    ensure!(
+        updated_at.plus_seconds(config.max_staleness) >= env.block.time,
+        Error::StalePrice {
+            updated_at,
+            max_staleness: config.max_staleness,
+        }
+    );
+
+    ensure!(
+        reward_config.in_bounds(price),
+        Error::PriceOutOfBounds {
+            price,
+            min_price: reward_config.min_price,
+            max_price: reward_config.max_price,
+        }
+    );
+
+    let amount = reward_config
+        .token_amount(price, config.decimals)
+        .ok_or(Error::Conversion {
+            quote_amount: reward_config.amount,
+            price,
+        })?;
+
+    Ok(PricedReward {
+        amount,
+        quote_amount: reward_config.amount,
+        price,
+        price_updated_at: updated_at,
+    })
+}
+
+/// Deduct the protocol fee of `msg` from `reward`, returning the fee amount.
+fn deduct_fee(reward: &mut PricedReward, msg: &RewardMsg) -> Uint128 {
+    let fee_amount = msg
+        .protocol_fee
+        .as_ref()
+        .map_or(Uint128::zero(), |fee| fee.fee_amount(reward.amount));
+
+    reward.amount -= fee_amount;
+
+    fee_amount
+}
+
+#[entry_point]
+#[allow(clippy::needless_pass_by_value)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Rewards(msg) => Ok(to_json_binary(&REWARDS.query(
+            deps.storage,
+            &msg,
+            || {
+                let config = CONFIG.load(deps.storage)?;
+
+                let mut reward = priced_reward(deps, &env, &config, &msg)
+                    .map_err(|err| StdError::generic_err(err.to_string()))?;
+                deduct_fee(&mut reward, &msg);
+
+                Ok(reward.into_pr_reward(&config))
+            },
+        )?)?),
+        QueryMsg::ValidateRewardConfig(ValidateRewardConfigMsg {
+            scope,
+            reward_config,
+        }) => {
+            FiatRewardConfig::parse(&reward_config)
+                .map_err(|err| StdError::generic_err(Error::InvalidConfig(err).to_string()))?;
+
+            ensure!(
+                is_allowed(&CONFIG.load(deps.storage)?, &scope),
+                StdError::generic_err(Error::InvalidScope(scope).to_string())
+            );
+
+            Ok(to_json_binary(&Empty {})?)
+        }
+        QueryMsg::Config {} => Ok(to_json_binary(&CONFIG.load(deps.storage)?)?),
+        QueryMsg::Admin {} => Ok(to_json_binary(&ADMIN.load(deps.storage)?)?),
+        QueryMsg::AccumulatedFees {} => Ok(to_json_binary(
+            &ACCUMULATED_FEES.may_load(deps.storage)?.unwrap_or_default(),
+        )?),
+    }
+}
+
+#[entry_point]
+#[allow(clippy::needless_pass_by_value)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, Error> {
+    match msg {
+        ExecuteMsg::Reward(msg) => {
+            REWARDS.ensure_lazydev(deps.storage, &info.sender)?;
+
+            reward(deps, &env, msg)
+        }
+        ExecuteMsg::Revoke(_) => {
+            REWARDS.ensure_lazydev(deps.storage, &info.sender)?;
+
+            // NOTE: sent funds can not be clawed back
+            Err(Error::Irrevocable)
+        }
+        ExecuteMsg::UpdateConfig(config) => {
+            ensure_admin(deps.as_ref(), &info.sender)?;
+
+            CONFIG
+                .save(deps.storage, &config)
+                .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+            Ok(Response::default())
+        }
+        ExecuteMsg::Withdraw { amount, recipient } => {
+            ensure_admin(deps.as_ref(), &info.sender)?;
+
+            Ok(Response::new().add_message(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: vec![amount],
+            }))
+        }
+    }
+}
+
+fn ensure_admin(deps: Deps, sender: &Addr) -> Result<(), Error> {
+    ensure!(
+        *sender
+            == ADMIN
+                .load(deps.storage)
+                .expect(STORAGE_ACCESS_INFALLIBLE_MSG),
+        Error::OnlyAdmin
+    );
+
+    Ok(())
+}
+
+/// Pay out the reward for `msg` at the current oracle price, sending the protocol fee to the
+/// treasury.
+fn reward(deps: DepsMut, env: &Env, msg: RewardMsg) -> Result<Response, Error> {
+    let config = CONFIG
+        .load(deps.storage)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    ensure!(
+        is_allowed(&config, &RewardScope::Repo(msg.repo.clone())),
+        Error::InvalidRepo(msg.repo)
+    );
+
+    let mut reward = priced_reward(deps.as_ref(), env, &config, &msg)?;
+
+    // fail with a clear error instead of the bank send failing, since the balance is funded
+    // separately by the sponsor
+    let balance = deps
+        .querier
+        .query_balance(&env.contract.address, &config.denom)?;
+    ensure!(
+        balance.amount >= reward.amount,
+        Error::InsufficientBalance {
+            balance,
+            amount: coin(reward.amount.u128(), &config.denom),
+        }
+    );
+
+    let fee_amount = deduct_fee(&mut reward, &msg);
+    let amount = reward.amount;
+
+    let reward_event = REWARDS.claim(deps.storage, &msg, &reward.into_pr_reward(&config));
+
+    let mut response = Response::new();
+
+    if let Some(protocol_fee) = msg.protocol_fee.filter(|_| !fee_amount.is_zero()) {
+        let accumulated_fees = ACCUMULATED_FEES
+            .may_load(deps.storage)
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+            .unwrap_or_default();
+
+        ACCUMULATED_FEES
+            .save(deps.storage, &(accumulated_fees + fee_amount))
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+        response = response
+            .add_message(BankMsg::Send {
+                to_address: protocol_fee.treasury.to_string(),
+                amount: coins(fee_amount.u128(), &config.denom),
+            })
+            .add_event(RewardFeeEvent {
+                fee: PrReward::Token {
+                    denom: config.denom.clone(),
+                    amount: fee_amount,
+                },
+                repo: msg.repo,
+                pr_id: msg.pr_id,
+                treasury: protocol_fee.treasury,
+            });
+    }
+
+    Ok(response
+        .add_message(BankMsg::Send {
+            to_address: msg.recipient_address.to_string(),
+            amount: coins(amount.u128(), config.denom),
+        })
+        .add_event(reward_event))
+}
+
+#[entry_point]
+pub fn migrate(_: DepsMut, _: Env, _: MigrateMsg) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        from_json,
+        testing::{message_info, mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage},
+        to_json_binary, Addr, ContractResult, OwnedDeps, SystemResult, WasmQuery,
+    };
+    use lazydev_reward_interface::{
+        msg::{ProtocolFee, RevokeMsg},
+        state::Repo,
+    };
+
+    use super::*;
+
+    #[test]
+    fn price() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let updated_at = env.block.time.minus_seconds(60);
+        deps.querier.update_wasm(move |query| {
+            let WasmQuery::Smart { msg, .. } = query else {
+                panic!("unexpected query {query:?}")
+            };
+            assert_eq!(
+                cosmwasm_std::from_json::<OracleQueryMsg>(msg).unwrap(),
+                OracleQueryMsg::Price {
+                    base: "ATOM".to_owned(),
+                    quote: "USD".to_owned(),
+                }
+            );
+
+            SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&PriceResponse {
+                    price: Decimal::percent(400),
+                    updated_at,
+                })
+                .unwrap(),
+            ))
+        });
+
+        let mut config = Config {
+            denom: "uatom".to_owned(),
+            decimals: 6,
+            base: "ATOM".to_owned(),
+            quote: "USD".to_owned(),
+            oracle: Addr::unchecked("oracle"),
+            max_staleness: 60,
+            valid_repos: vec![],
+            valid_orgs: vec!["unionlabs".to_owned()],
+        };
+        let mut msg = RewardMsg {
+            repo: Repo::from(("unionlabs", "union")),
+            pr_id: 1,
+            user_id: 2,
+            recipient_address: Addr::unchecked("recipient"),
+            reward_config: r#"{"amount":"50","max_price":"5"}"#.to_owned(),
+            protocol_fee: None,
+            ordinal: 1,
+        };
+
+        // $50 at $4 per ATOM
+        assert_eq!(
+            priced_reward(deps.as_ref(), &env, &config, &msg).unwrap(),
+            PricedReward {
+                amount: Uint128::new(12_500_000),
+                quote_amount: Decimal::percent(5000),
+                price: Decimal::percent(400),
+                price_updated_at: updated_at,
+            }
+        );
+
+        msg.reward_config = r#"{"amount":"50","min_price":"4.5"}"#.to_owned();
+        assert!(matches!(
+            priced_reward(deps.as_ref(), &env, &config, &msg),
+            Err(Error::PriceOutOfBounds { .. })
+        ));
+
+        config.max_staleness = 59;
+        assert!(matches!(
+            priced_reward(deps.as_ref(), &env, &config, &msg),
+            Err(Error::StalePrice { .. })
+        ));
+    }
+
+    fn set_price(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        price: Decimal,
+        updated_at: Timestamp,
+    ) {
+        deps.querier.update_wasm(move |_| {
+            SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&PriceResponse { price, updated_at }).unwrap(),
+            ))
+        });
+    }
+
+    #[test]
+    fn execute_reward() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let admin = deps.api.addr_make("admin");
+        let lazydev = deps.api.addr_make("lazydev");
+        let recipient = deps.api.addr_make("recipient");
+        let treasury = deps.api.addr_make("treasury");
+        let oracle = deps.api.addr_make("oracle");
+
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&admin, &[]),
+            InstantiateMsg {
+                config: Config {
+                    denom: "uatom".to_owned(),
+                    decimals: 6,
+                    base: "ATOM".to_owned(),
+                    quote: "USD".to_owned(),
+                    oracle,
+                    max_staleness: 60,
+                    valid_repos: vec![],
+                    valid_orgs: vec!["unionlabs".to_owned()],
+                },
+                lazydev_address: lazydev.clone(),
+            },
+        )
+        .unwrap();
+
+        deps.querier
+            .bank
+            .update_balance(&env.contract.address, coins(20_000_000, "uatom"));
+        set_price(
+            &mut deps,
+            Decimal::percent(400),
+            env.block.time.minus_seconds(60),
+        );
+
+        let msg = |pr_id| RewardMsg {
+            repo: Repo::from(("unionlabs", "union")),
+            pr_id,
+            user_id: 2,
+            recipient_address: recipient.clone(),
+            reward_config: r#"{"amount":"50","max_price":"5"}"#.to_owned(),
+            protocol_fee: Some(ProtocolFee {
+                bps: 100,
+                treasury: treasury.clone(),
+            }),
+            ordinal: 1,
+        };
+
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&recipient, &[]),
+                ExecuteMsg::Reward(msg(1)),
+            ),
+            Err(Error::OnlyLazydev(_))
+        ));
+
+        // $50 at $4 per ATOM, minus a 1% fee
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&lazydev, &[]),
+            ExecuteMsg::Reward(msg(1)),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages
+                .into_iter()
+                .map(|msg| msg.msg)
+                .collect::<Vec<_>>(),
+            [
+                BankMsg::Send {
+                    to_address: treasury.to_string(),
+                    amount: coins(125_000, "uatom"),
+                }
+                .into(),
+                BankMsg::Send {
+                    to_address: recipient.to_string(),
+                    amount: coins(12_375_000, "uatom"),
+                }
+                .into(),
+            ]
+        );
+        assert_eq!(
+            from_json::<Uint128>(
+                query(deps.as_ref(), env.clone(), QueryMsg::AccumulatedFees {}).unwrap()
+            )
+            .unwrap(),
+            Uint128::new(125_000)
+        );
+
+        set_price(
+            &mut deps,
+            Decimal::percent(600),
+            env.block.time.minus_seconds(60),
+        );
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&lazydev, &[]),
+                ExecuteMsg::Reward(msg(2)),
+            ),
+            Err(Error::PriceOutOfBounds { .. })
+        ));
+
+        set_price(
+            &mut deps,
+            Decimal::percent(400),
+            env.block.time.minus_seconds(61),
+        );
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&lazydev, &[]),
+                ExecuteMsg::Reward(msg(2)),
+            ),
+            Err(Error::StalePrice { .. })
+        ));
+
+        // the remaining balance of 7.5 ATOM does not cover another reward of 12.5 ATOM
+        set_price(&mut deps, Decimal::percent(400), env.block.time);
+        deps.querier
+            .bank
+            .update_balance(&env.contract.address, coins(7_500_000, "uatom"));
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&lazydev, &[]),
+                ExecuteMsg::Reward(msg(2)),
+            ),
+            Err(Error::InsufficientBalance { .. })
+        ));
+
+        // rejected, such that the revert of the PR reports the reward as not revoked
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                env,
+                message_info(&lazydev, &[]),
+                ExecuteMsg::Revoke(RevokeMsg {
+                    reward: msg(1),
+                    revert_pr_id: None,
+                }),
+            ),
+            Err(Error::Irrevocable)
+        ));
+    }
+
+    #[test]
+    fn admin() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let admin = deps.api.addr_make("admin");
+        let lazydev = deps.api.addr_make("lazydev");
+        let sponsor = deps.api.addr_make("sponsor");
+        let oracle = deps.api.addr_make("oracle");
+
+        let mut config = Config {
+            denom: "uatom".to_owned(),
+            decimals: 6,
+            base: "ATOM".to_owned(),
+            quote: "USD".to_owned(),
+            oracle,
+            max_staleness: 60,
+            valid_repos: vec![],
+            valid_orgs: vec!["unionlabs".to_owned()],
+        };
+
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&admin, &[]),
+            InstantiateMsg {
+                config: config.clone(),
+                lazydev_address: lazydev,
+            },
+        )
+        .unwrap();
+
+        config.max_staleness = 600;
+
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&sponsor, &[]),
+                ExecuteMsg::UpdateConfig(config.clone()),
+            ),
+            Err(Error::OnlyAdmin)
+        ));
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&admin, &[]),
+            ExecuteMsg::UpdateConfig(config.clone()),
+        )
+        .unwrap();
+        assert_eq!(
+            from_json::<Config>(query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap())
+                .unwrap(),
+            config
+        );
+
+        let withdraw = ExecuteMsg::Withdraw {
+            amount: coin(100, "uatom"),
+            recipient: sponsor.clone(),
+        };
+
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&sponsor, &[]),
+                withdraw.clone(),
+            ),
+            Err(Error::OnlyAdmin)
+        ));
+
+        let res = execute(deps.as_mut(), env, message_info(&admin, &[]), withdraw).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            BankMsg::Send {
+                to_address: sponsor.to_string(),
+                amount: coins(100, "uatom"),
+            }
+            .into()
+        );
+    }
+}
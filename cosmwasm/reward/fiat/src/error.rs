@@ -0,0 +1,44 @@
+use cosmwasm_std::{Coin, Decimal, StdError, Timestamp};
+use lazydev_reward_interface::{contract::OnlyLazydev, msg::RewardScope, state::Repo};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Std(#[from] StdError),
+    #[error(transparent)]
+    OnlyLazydev(#[from] OnlyLazydev),
+    #[error("only admin")]
+    OnlyAdmin,
+    #[error("repo {}/{} not allowed", .0.org, .0.repo)]
+    InvalidRepo(Repo),
+    #[error("{0:?} not allowed")]
+    InvalidScope(RewardScope),
+    #[error("invalid config")]
+    InvalidConfig(StdError),
+    #[error("sent funds can not be revoked")]
+    Irrevocable,
+    #[error("unable to query the price from the oracle: {0}")]
+    Oracle(StdError),
+    #[error("price last updated at {updated_at}, which is older than the max staleness of {max_staleness} seconds")]
+    StalePrice {
+        updated_at: Timestamp,
+        max_staleness: u64,
+    },
+    #[error("price {price} is outside of the bounds [{}, {}]", display_bound(.min_price), display_bound(.max_price))]
+    PriceOutOfBounds {
+        price: Decimal,
+        min_price: Option<Decimal>,
+        max_price: Option<Decimal>,
+    },
+    #[error("unable to convert {quote_amount} at a price of {price}")]
+    Conversion {
+        quote_amount: Decimal,
+        price: Decimal,
+    },
+    #[error("insufficient balance of {balance} to pay out {amount}")]
+    InsufficientBalance { balance: Coin, amount: Coin },
+}
+
+fn display_bound(bound: &Option<Decimal>) -> String {
+    bound.map_or_else(|| "-".to_owned(), |bound| bound.to_string())
+}
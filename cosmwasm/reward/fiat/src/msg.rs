@@ -0,0 +1,121 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{from_json, Addr, Coin, Decimal, Empty, StdResult, Uint128};
+use lazydev_reward_interface::{
+    msg::{QueryRewardsResponse, RevokeMsg, RewardMsg, ValidateRewardConfigMsg},
+    state::Repo,
+};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub config: Config,
+    pub lazydev_address: Addr,
+}
+
+#[cw_serde]
+pub struct Config {
+    /// The native denom that rewards are paid out in. Rewards are sent from the balance of this
+    /// contract, which must be funded by the sponsor.
+    pub denom: String,
+    /// The decimals of `denom`, i.e. the number of base units in one whole token as a power of 10.
+    pub decimals: u8,
+    /// The symbol of the token as known by the oracle, e.g. `ATOM`.
+    pub base: String,
+    /// The quote currency that rewards are denominated in, e.g. `USD`.
+    pub quote: String,
+    /// The price oracle contract, implementing
+    /// [`OracleQueryMsg`][lazydev_reward_interface::oracle::OracleQueryMsg].
+    pub oracle: Addr,
+    /// The maximum age of the oracle price in seconds. Rewards fail if the price is older than
+    /// this.
+    pub max_staleness: u64,
+
+    /// The repos this contract will provide rewards for.
+    pub valid_repos: Vec<Repo>,
+    /// The github orgs this contract will provide rewards for. Repos on other forges must be listed
+    /// in `valid_repos`.
+    pub valid_orgs: Vec<String>,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    Reward(RewardMsg),
+    /// Revoking a reward always fails, since sent funds can not be clawed back. The revert of the
+    /// PR reports the reward as not revoked.
+    Revoke(RevokeMsg),
+    /// Replace the config of this contract. Only callable by the admin.
+    UpdateConfig(Config),
+    /// Send `amount` from the balance of this contract to `recipient`, for example to recover the
+    /// funds of a sponsor that stops rewarding. Only callable by the admin.
+    Withdraw {
+        amount: Coin,
+        recipient: Addr,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Returns the reward at the current oracle price, or the claimed reward with the price it was
+    /// converted with.
+    #[returns(QueryRewardsResponse)]
+    Rewards(RewardMsg),
+    #[returns(Empty)]
+    ValidateRewardConfig(ValidateRewardConfigMsg),
+    #[returns(Config)]
+    Config {},
+    /// Returns the address that can update the config and withdraw funds.
+    #[returns(Addr)]
+    Admin {},
+    /// Returns the total amount of tokens sent to the treasury as protocol fees.
+    #[returns(Uint128)]
+    AccumulatedFees {},
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
+
+/// The `reward_config` of this contract, as json.
+///
+/// ```json
+/// { "amount": "50", "min_price": "4.5", "max_price": "5.5" }
+/// ```
+#[cw_serde]
+pub struct FiatRewardConfig {
+    /// The value of the reward in the quote currency of the contract.
+    pub amount: Decimal,
+    /// The lowest price of one whole token that the reward may be converted with. This bounds the
+    /// amount of tokens paid out if the price drops or the oracle misreports.
+    #[serde(default)]
+    pub min_price: Option<Decimal>,
+    /// The highest price of one whole token that the reward may be converted with.
+    #[serde(default)]
+    pub max_price: Option<Decimal>,
+}
+
+impl FiatRewardConfig {
+    pub fn parse(reward_config: &str) -> StdResult<Self> {
+        from_json(reward_config)
+    }
+
+    /// Whether `price` is within the slippage bounds of this config.
+    #[must_use]
+    pub fn in_bounds(&self, price: Decimal) -> bool {
+        self.min_price.is_none_or(|min_price| price >= min_price)
+            && self.max_price.is_none_or(|max_price| price <= max_price)
+    }
+
+    /// The amount of base units of a token with `decimals` that `amount` is worth at `price`,
+    /// rounded down. Returns [`None`] if the price is zero or the amount overflows.
+    #[must_use]
+    pub fn token_amount(&self, price: Decimal, decimals: u8) -> Option<Uint128> {
+        if price.is_zero() {
+            return None;
+        }
+
+        Uint128::new(10)
+            .checked_pow(u32::from(decimals))
+            .ok()?
+            .checked_multiply_ratio(self.amount.atomics(), price.atomics())
+            .ok()
+    }
+}
@@ -0,0 +1,18 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::Item;
+use lazydev_reward_interface::contract::Rewards;
+
+use crate::msg::Config;
+
+/// The address of the lazydev contract, which is the only address that can call the `reward`
+/// entrypoint, and the already claimed rewards.
+pub const REWARDS: Rewards = Rewards::new("lazydev", "claimed_rewards");
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// The address that instantiated this contract, which is the only address that can update the
+/// config and withdraw funds.
+pub const ADMIN: Item<Addr> = Item::new("admin");
+
+/// The total amount of tokens sent to the treasury as protocol fees.
+pub const ACCUMULATED_FEES: Item<Uint128> = Item::new("accumulated_fees");
@@ -0,0 +1,268 @@
+use cosmwasm_std::{Addr, Event, Order, StdResult, Storage};
+use cw_storage_plus::{Item, Map};
+
+use crate::{
+    msg::{QueryRewardsResponse, RewardMsg},
+    reward::PrReward,
+    reward_event,
+    state::Repo,
+};
+
+pub const SERIALIZATION_INFALLIBLE_MSG: &str = "serialization is infallible";
+pub const STORAGE_ACCESS_INFALLIBLE_MSG: &str = "storage access is infallible";
+
+#[derive(Debug, PartialEq, thiserror::Error)]
+#[error("only lazydev")]
+pub struct OnlyLazydev;
+
+/// The version of the layout of [`Rewards`]. This is unset for contracts instantiated before the
+/// claimed rewards were keyed by forge, see [`Rewards::migrate`].
+const REWARDS_VERSION: Item<u16> = Item::new("rewards_version");
+
+const CURRENT_REWARDS_VERSION: u16 = 1;
+
+/// The address of the lazydev contract, which is the only address allowed to send rewards.
+///
+/// This is part of [`Rewards`], and only needs to be used on its own by reward contracts that do
+/// not pay out rewards themselves.
+pub struct Lazydev(Item<Addr>);
+
+impl Lazydev {
+    #[must_use]
+    pub const fn new(namespace: &'static str) -> Self {
+        Self(Item::new(namespace))
+    }
+
+    /// Store the address of the lazydev contract. This is expected to be called on instantiation.
+    pub fn init(&self, storage: &mut dyn Storage, lazydev: &Addr) {
+        self.0
+            .save(storage, lazydev)
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+    }
+
+    /// Ensure that `sender` is the lazydev contract.
+    pub fn ensure_lazydev(&self, storage: &dyn Storage, sender: &Addr) -> Result<(), OnlyLazydev> {
+        let lazydev = self.0.load(storage).expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+        if *sender == lazydev {
+            Ok(())
+        } else {
+            Err(OnlyLazydev)
+        }
+    }
+}
+
+/// The state that every reward contract keeps: the address of the lazydev contract, which is the
+/// only address allowed to send rewards, and the rewards that have already been paid out.
+///
+/// ```ignore
+/// pub const REWARDS: Rewards = Rewards::new("lazydev", "claimed_rewards");
+/// ```
+pub struct Rewards {
+    lazydev: Lazydev,
+    /// Already claimed rewards, keyed by `(pr_id, repo)`.
+    claimed: Map<(u64, Repo), PrReward>,
+    claimed_namespace: &'static str,
+}
+
+impl Rewards {
+    #[must_use]
+    pub const fn new(lazydev_namespace: &'static str, claimed_namespace: &'static str) -> Self {
+        Self {
+            lazydev: Lazydev::new(lazydev_namespace),
+            claimed: Map::new(claimed_namespace),
+            claimed_namespace,
+        }
+    }
+
+    /// See [`Lazydev::init`].
+    pub fn init(&self, storage: &mut dyn Storage, lazydev: &Addr) {
+        self.lazydev.init(storage, lazydev);
+
+        REWARDS_VERSION
+            .save(storage, &CURRENT_REWARDS_VERSION)
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+    }
+
+    /// Migrate the claimed rewards of a contract instantiated before forge support, which are keyed
+    /// by `(pr_id, org, repo)`, to be keyed by the github repo. This is a no-op for contracts that
+    /// are already up to date, and is expected to be called on migration.
+    pub fn migrate(&self, storage: &mut dyn Storage) {
+        if REWARDS_VERSION
+            .may_load(storage)
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+            .is_some()
+        {
+            return;
+        }
+
+        let legacy_claimed: Map<(u64, String, String), PrReward> = Map::new(self.claimed_namespace);
+
+        let claimed = legacy_claimed
+            .range(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+        for ((pr_id, org, repo), reward) in claimed {
+            legacy_claimed.remove(storage, (pr_id, org.clone(), repo.clone()));
+
+            self.claimed
+                .save(storage, (pr_id, Repo::from((org, repo))), &reward)
+                .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+        }
+
+        REWARDS_VERSION
+            .save(storage, &CURRENT_REWARDS_VERSION)
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+    }
+
+    /// See [`Lazydev::ensure_lazydev`].
+    pub fn ensure_lazydev(&self, storage: &dyn Storage, sender: &Addr) -> Result<(), OnlyLazydev> {
+        self.lazydev.ensure_lazydev(storage, sender)
+    }
+
+    /// The reward that was paid out for a PR, if it has already been rewarded.
+    pub fn claimed(
+        &self,
+        storage: &dyn Storage,
+        repo: &Repo,
+        pr_id: u64,
+    ) -> StdResult<Option<PrReward>> {
+        self.claimed.may_load(storage, (pr_id, repo.clone()))
+    }
+
+    /// Respond to a [`Rewards`][crate::msg::RewardQueryMsg::Rewards] query: the claimed reward if
+    /// the PR has already been rewarded, otherwise the reward returned by `preview`.
+    pub fn query(
+        &self,
+        storage: &dyn Storage,
+        msg: &RewardMsg,
+        preview: impl FnOnce() -> StdResult<PrReward>,
+    ) -> StdResult<QueryRewardsResponse> {
+        Ok(match self.claimed(storage, &msg.repo, msg.pr_id)? {
+            Some(claimed_rewards) => QueryRewardsResponse {
+                claimed: true,
+                rewards: vec![claimed_rewards],
+            },
+            None => QueryRewardsResponse {
+                claimed: false,
+                rewards: vec![preview()?],
+            },
+        })
+    }
+
+    /// Record that `reward` was paid out for the PR in `msg`, returning the event for the reward.
+    pub fn claim(&self, storage: &mut dyn Storage, msg: &RewardMsg, reward: &PrReward) -> Event {
+        self.claimed
+            .save(storage, (msg.pr_id, msg.repo.clone()), reward)
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+        reward_event(
+            reward,
+            msg.repo.clone(),
+            msg.pr_id,
+            msg.user_id,
+            msg.recipient_address.clone(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{testing::MockStorage, Uint128};
+
+    use super::*;
+
+    #[test]
+    fn rewards() {
+        const REWARDS: Rewards = Rewards::new("lazydev", "claimed_rewards");
+
+        let mut storage = MockStorage::new();
+
+        let lazydev = Addr::unchecked("lazydev");
+
+        REWARDS.init(&mut storage, &lazydev);
+
+        assert_eq!(REWARDS.ensure_lazydev(&storage, &lazydev), Ok(()));
+        assert_eq!(
+            REWARDS.ensure_lazydev(&storage, &Addr::unchecked("other")),
+            Err(OnlyLazydev)
+        );
+
+        let msg = RewardMsg {
+            repo: Repo::from(("unionlabs", "union")),
+            pr_id: 1,
+            user_id: 2,
+            recipient_address: Addr::unchecked("recipient"),
+            reward_config: String::new(),
+            protocol_fee: None,
+            ordinal: 1,
+        };
+        // unset fields are omitted for reward contracts built against older versions
+        assert!(!cosmwasm_std::to_json_string(&msg)
+            .unwrap()
+            .contains("protocol_fee"));
+        let reward = PrReward::Token {
+            denom: "lazy".to_owned(),
+            amount: Uint128::new(100),
+        };
+        let preview = || {
+            Ok(PrReward::Token {
+                denom: "lazy".to_owned(),
+                amount: Uint128::new(200),
+            })
+        };
+
+        assert!(!REWARDS.query(&storage, &msg, preview).unwrap().claimed);
+
+        REWARDS.claim(&mut storage, &msg, &reward);
+
+        assert_eq!(
+            REWARDS.query(&storage, &msg, preview).unwrap(),
+            QueryRewardsResponse {
+                claimed: true,
+                rewards: vec![reward],
+            }
+        );
+    }
+
+    #[test]
+    fn migrate() {
+        const REWARDS: Rewards = Rewards::new("lazydev", "claimed_rewards");
+        const LEGACY_CLAIMED: Map<(u64, String, String), PrReward> = Map::new("claimed_rewards");
+
+        let mut storage = MockStorage::new();
+
+        // a contract instantiated before forge support, without a rewards version
+        Lazydev::new("lazydev").init(&mut storage, &Addr::unchecked("lazydev"));
+
+        let reward = PrReward::Token {
+            denom: "lazy".to_owned(),
+            amount: Uint128::new(100),
+        };
+
+        LEGACY_CLAIMED
+            .save(
+                &mut storage,
+                (1, "unionlabs".to_owned(), "union".to_owned()),
+                &reward,
+            )
+            .unwrap();
+
+        REWARDS.migrate(&mut storage);
+
+        let repo = Repo::from(("unionlabs", "union"));
+
+        assert_eq!(
+            REWARDS.claimed(&storage, &repo, 1),
+            Ok(Some(reward.clone()))
+        );
+        assert!(!LEGACY_CLAIMED.has(&storage, (1, "unionlabs".to_owned(), "union".to_owned())));
+        assert_eq!(REWARDS_VERSION.load(&storage), Ok(CURRENT_REWARDS_VERSION));
+
+        // migrating again leaves the claimed rewards untouched
+        REWARDS.migrate(&mut storage);
+
+        assert_eq!(REWARDS.claimed(&storage, &repo, 1), Ok(Some(reward)));
+    }
+}
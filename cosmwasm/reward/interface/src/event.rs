@@ -0,0 +1,321 @@
+//! The event schema shared by lazydev and the reward contracts.
+//!
+//! Every event carries a [`attribute::VERSION`] attribute set to [`VERSION`], and every attribute
+//! key is unique within an event. Each event has a typed representation implementing
+//! [`TypedEvent`], which is used both to construct the event and to decode it back from a
+//! [`cosmwasm_std::Event`], for example in off-chain indexers or in tests.
+//!
+//! Events emitted by contracts are prefixed with `wasm-` by the chain; both the prefixed and the
+//! unprefixed event types are accepted when decoding.
+
+use cosmwasm_std::{Addr, Binary, Coin, Event, Uint128};
+
+use crate::{forge::Forge, reward::PrReward, state::Repo};
+
+/// The current version of the event schema. This is bumped whenever an event changes in a
+/// backwards incompatible way.
+pub const VERSION: &str = "1";
+
+pub const REWARD: &str = "reward";
+pub const REWARD_FEE: &str = "reward_fee";
+
+pub mod attribute {
+    pub const VERSION: &str = "version";
+    pub const REWARD: &str = "reward";
+    pub const FORGE: &str = "forge";
+    pub const ORG: &str = "org";
+    pub const REPO: &str = "repo";
+    pub const PR: &str = "pr";
+    pub const USER: &str = "user";
+    pub const RECIPIENT: &str = "recipient";
+    pub const FEE: &str = "fee";
+    pub const TREASURY: &str = "treasury";
+}
+
+/// An event with a typed representation.
+pub trait TypedEvent: Sized {
+    /// The type of the event, without the `wasm-` prefix.
+    const TYPE: &'static str;
+
+    fn into_event(self) -> Event;
+
+    fn try_from_event(event: &Event) -> Result<Self, DecodeError>;
+}
+
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum DecodeError {
+    #[error("expected event type {expected}, found {found}")]
+    InvalidType {
+        expected: &'static str,
+        found: String,
+    },
+    #[error("unsupported event version {0}")]
+    UnsupportedVersion(String),
+    #[error("missing attribute {0}")]
+    MissingAttribute(&'static str),
+    #[error("duplicate attribute {0}")]
+    DuplicateAttribute(&'static str),
+    #[error("invalid value for attribute {key}: {value}")]
+    InvalidAttribute { key: &'static str, value: String },
+}
+
+/// A value that is stored in a single event attribute.
+pub trait AttributeValue: Sized {
+    fn encode(&self) -> String;
+
+    fn decode(value: &str) -> Option<Self>;
+}
+
+impl AttributeValue for String {
+    fn encode(&self) -> String {
+        self.clone()
+    }
+
+    fn decode(value: &str) -> Option<Self> {
+        Some(value.to_owned())
+    }
+}
+
+impl AttributeValue for u64 {
+    fn encode(&self) -> String {
+        self.to_string()
+    }
+
+    fn decode(value: &str) -> Option<Self> {
+        value.parse().ok()
+    }
+}
+
+impl AttributeValue for Addr {
+    fn encode(&self) -> String {
+        self.to_string()
+    }
+
+    fn decode(value: &str) -> Option<Self> {
+        Some(Addr::unchecked(value))
+    }
+}
+
+impl AttributeValue for Binary {
+    fn encode(&self) -> String {
+        self.to_base64()
+    }
+
+    fn decode(value: &str) -> Option<Self> {
+        Binary::from_base64(value).ok()
+    }
+}
+
+/// Optional values are stored as `null` if they are not set. This matches the json encoding of
+/// optional json values.
+impl<T: AttributeValue> AttributeValue for Option<T> {
+    fn encode(&self) -> String {
+        self.as_ref()
+            .map_or_else(|| "null".to_owned(), AttributeValue::encode)
+    }
+
+    fn decode(value: &str) -> Option<Self> {
+        match value {
+            "null" => Some(None),
+            _ => T::decode(value).map(Some),
+        }
+    }
+}
+
+/// Implement [`AttributeValue`] for types implementing [`Display`][std::fmt::Display] and
+/// [`FromStr`][std::str::FromStr].
+#[macro_export]
+macro_rules! display_attribute_value {
+    ($($T:ty),+) => {
+        $(
+            impl $crate::event::AttributeValue for $T {
+                fn encode(&self) -> String {
+                    self.to_string()
+                }
+
+                fn decode(value: &str) -> Option<Self> {
+                    value.parse().ok()
+                }
+            }
+        )+
+    };
+}
+
+/// Implement [`AttributeValue`] for types that are stored as json.
+#[macro_export]
+macro_rules! json_attribute_value {
+    ($($T:ty),+) => {
+        $(
+            impl $crate::event::AttributeValue for $T {
+                fn encode(&self) -> String {
+                    $crate::__private::to_json_string(self)
+                        .expect($crate::contract::SERIALIZATION_INFALLIBLE_MSG)
+                }
+
+                fn decode(value: &str) -> Option<Self> {
+                    $crate::__private::from_json(value).ok()
+                }
+            }
+        )+
+    };
+}
+
+display_attribute_value!(Forge, Coin, Uint128, bool);
+json_attribute_value!(PrReward);
+
+/// A field of a typed event, stored in one or more attributes.
+pub trait EventField: Sized {
+    fn write(&self, key: &'static str, event: Event) -> Event;
+
+    fn read(key: &'static str, event: &Event) -> Result<Self, DecodeError>;
+}
+
+impl<T: AttributeValue> EventField for T {
+    fn write(&self, key: &'static str, event: Event) -> Event {
+        event.add_attribute(key, self.encode())
+    }
+
+    fn read(key: &'static str, event: &Event) -> Result<Self, DecodeError> {
+        let value = read_attribute(key, event)?;
+
+        T::decode(value).ok_or_else(|| DecodeError::InvalidAttribute {
+            key,
+            value: value.to_owned(),
+        })
+    }
+}
+
+/// Repos are stored in the [`attribute::FORGE`], [`attribute::ORG`] and [`attribute::REPO`]
+/// attributes, the key of the field is ignored.
+impl EventField for Repo {
+    fn write(&self, _: &'static str, event: Event) -> Event {
+        event.add_attributes([
+            (attribute::FORGE, self.forge.encode()),
+            (attribute::ORG, self.org.clone()),
+            (attribute::REPO, self.repo.clone()),
+        ])
+    }
+
+    fn read(_: &'static str, event: &Event) -> Result<Self, DecodeError> {
+        Ok(Repo {
+            forge: EventField::read(attribute::FORGE, event)?,
+            org: EventField::read(attribute::ORG, event)?,
+            repo: EventField::read(attribute::REPO, event)?,
+        })
+    }
+}
+
+fn read_attribute<'a>(key: &'static str, event: &'a Event) -> Result<&'a str, DecodeError> {
+    let mut values = event
+        .attributes
+        .iter()
+        .filter(|attribute| attribute.key == key)
+        .map(|attribute| attribute.value.as_str());
+
+    let value = values.next().ok_or(DecodeError::MissingAttribute(key))?;
+
+    if values.next().is_some() {
+        return Err(DecodeError::DuplicateAttribute(key));
+    }
+
+    Ok(value)
+}
+
+/// Ensure that `event` is of type `ty` and of the current [`VERSION`].
+pub fn check_header(ty: &'static str, event: &Event) -> Result<(), DecodeError> {
+    if event.ty.strip_prefix("wasm-").unwrap_or(&event.ty) != ty {
+        return Err(DecodeError::InvalidType {
+            expected: ty,
+            found: event.ty.clone(),
+        });
+    }
+
+    let version = read_attribute(attribute::VERSION, event)?;
+
+    if version != VERSION {
+        return Err(DecodeError::UnsupportedVersion(version.to_owned()));
+    }
+
+    Ok(())
+}
+
+/// Define events with a typed representation, see [`TypedEvent`].
+#[macro_export]
+macro_rules! typed_events {
+    ($(
+        $(#[$meta:meta])*
+        pub struct $Event:ident = $ty:path {
+            $(
+                $(#[$field_meta:meta])*
+                pub $field:ident: $T:ty = $key:expr,
+            )*
+        }
+    )+) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug, Clone, PartialEq)]
+            pub struct $Event {
+                $(
+                    $(#[$field_meta])*
+                    pub $field: $T,
+                )*
+            }
+
+            impl $crate::event::TypedEvent for $Event {
+                const TYPE: &'static str = $ty;
+
+                fn into_event(self) -> $crate::__private::Event {
+                    let event = $crate::__private::Event::new(Self::TYPE)
+                        .add_attribute($crate::event::attribute::VERSION, $crate::event::VERSION);
+                    $(let event = $crate::event::EventField::write(&self.$field, $key, event);)*
+                    event
+                }
+
+                fn try_from_event(
+                    event: &$crate::__private::Event,
+                ) -> Result<Self, $crate::event::DecodeError> {
+                    $crate::event::check_header(Self::TYPE, event)?;
+
+                    Ok(Self {
+                        $($field: $crate::event::EventField::read($key, event)?,)*
+                    })
+                }
+            }
+
+            impl From<$Event> for $crate::__private::Event {
+                fn from(event: $Event) -> Self {
+                    $crate::event::TypedEvent::into_event(event)
+                }
+            }
+
+            impl TryFrom<&$crate::__private::Event> for $Event {
+                type Error = $crate::event::DecodeError;
+
+                fn try_from(event: &$crate::__private::Event) -> Result<Self, Self::Error> {
+                    <Self as $crate::event::TypedEvent>::try_from_event(event)
+                }
+            }
+        )+
+    };
+}
+
+typed_events! {
+    /// A reward contract deducted the [`ProtocolFee`][crate::msg::ProtocolFee] from a reward and
+    /// sent it to the treasury.
+    pub struct RewardFeeEvent = REWARD_FEE {
+        /// The part of the reward sent to the treasury.
+        pub fee: PrReward = attribute::FEE,
+        pub repo: Repo = attribute::REPO,
+        pub pr_id: u64 = attribute::PR,
+        pub treasury: Addr = attribute::TREASURY,
+    }
+
+    /// A reward was paid out by a reward contract, see [`crate::reward_event`].
+    pub struct RewardEvent = REWARD {
+        pub reward: PrReward = attribute::REWARD,
+        pub repo: Repo = attribute::REPO,
+        pub pr_id: u64 = attribute::PR,
+        pub user_id: u64 = attribute::USER,
+        pub recipient: Addr = attribute::RECIPIENT,
+    }
+}
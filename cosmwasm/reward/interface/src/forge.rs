@@ -0,0 +1,130 @@
+use std::{fmt, str::FromStr};
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{StdError, StdResult};
+use cw_storage_plus::{Key, KeyDeserialize, Prefixer, PrimaryKey};
+
+/// The forge (git hosting service) that a repo or user account lives on.
+///
+/// Every forge other than [`Forge::Github`] must be allowlisted by the owner of the lazydev contract
+/// before it can be used.
+#[cw_serde]
+#[derive(Default, Eq, Hash, PartialOrd, Ord)]
+pub enum Forge {
+    /// <https://github.com>
+    #[default]
+    Github,
+    /// A self-hosted [GitHub Enterprise Server](https://docs.github.com/en/enterprise-server)
+    /// instance.
+    GithubEnterprise { host: String },
+    /// A [GitLab](https://docs.gitlab.com/api/rest/) instance, either gitlab.com or self-hosted.
+    Gitlab { host: String },
+    /// A self-hosted [Gitea](https://docs.gitea.com/development/api-usage) instance.
+    Gitea { host: String },
+}
+
+impl Forge {
+    const GITHUB_TAG: u8 = 0;
+    const GITHUB_ENTERPRISE_TAG: u8 = 1;
+    const GITLAB_TAG: u8 = 2;
+    const GITEA_TAG: u8 = 3;
+
+    #[must_use]
+    pub fn is_github(&self) -> bool {
+        matches!(self, Self::Github)
+    }
+
+    /// The host of this forge. This is empty for [`Forge::Github`].
+    #[must_use]
+    pub fn host(&self) -> &str {
+        match self {
+            Forge::Github => "",
+            Forge::GithubEnterprise { host } | Forge::Gitlab { host } | Forge::Gitea { host } => {
+                host
+            }
+        }
+    }
+
+    fn tag(&self) -> u8 {
+        match self {
+            Forge::Github => Self::GITHUB_TAG,
+            Forge::GithubEnterprise { .. } => Self::GITHUB_ENTERPRISE_TAG,
+            Forge::Gitlab { .. } => Self::GITLAB_TAG,
+            Forge::Gitea { .. } => Self::GITEA_TAG,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, thiserror::Error)]
+#[error("invalid forge {0}")]
+pub struct InvalidForge(pub String);
+
+impl fmt::Display for Forge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Forge::Github => f.write_str("github"),
+            Forge::GithubEnterprise { host } => write!(f, "github_enterprise:{host}"),
+            Forge::Gitlab { host } => write!(f, "gitlab:{host}"),
+            Forge::Gitea { host } => write!(f, "gitea:{host}"),
+        }
+    }
+}
+
+impl FromStr for Forge {
+    type Err = InvalidForge;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "github" => Ok(Forge::Github),
+            Some(("github_enterprise", host)) => Ok(Forge::GithubEnterprise {
+                host: host.to_owned(),
+            }),
+            Some(("gitlab", host)) => Ok(Forge::Gitlab {
+                host: host.to_owned(),
+            }),
+            Some(("gitea", host)) => Ok(Forge::Gitea {
+                host: host.to_owned(),
+            }),
+            _ => Err(InvalidForge(s.to_owned())),
+        }
+    }
+}
+
+// forges are keyed by `(tag, host)`
+impl PrimaryKey<'_> for Forge {
+    type Prefix = u8;
+
+    type SubPrefix = ();
+
+    type Suffix = String;
+
+    type SuperSuffix = (u8, String);
+
+    fn key(&self) -> Vec<Key<'_>> {
+        vec![Key::Val8([self.tag()]), Key::Ref(self.host().as_bytes())]
+    }
+}
+
+impl Prefixer<'_> for Forge {
+    fn prefix(&self) -> Vec<Key<'_>> {
+        self.key()
+    }
+}
+
+impl KeyDeserialize for Forge {
+    type Output = Forge;
+
+    const KEY_ELEMS: u16 = <(u8, String) as KeyDeserialize>::KEY_ELEMS;
+
+    fn from_vec(value: Vec<u8>) -> StdResult<Self::Output> {
+        let (tag, host) = <(u8, String) as KeyDeserialize>::from_vec(value)?;
+
+        match tag {
+            Self::GITHUB_TAG => Ok(Forge::Github),
+            Self::GITHUB_ENTERPRISE_TAG => Ok(Forge::GithubEnterprise { host }),
+            Self::GITLAB_TAG => Ok(Forge::Gitlab { host }),
+            Self::GITEA_TAG => Ok(Forge::Gitea { host }),
+            _ => Err(StdError::generic_err(format!("invalid forge tag {tag}"))),
+        }
+    }
+}
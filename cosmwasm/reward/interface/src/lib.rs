@@ -0,0 +1,44 @@
+//! The interface between lazydev and reward contracts.
+//!
+//! Reward contracts should depend on this crate rather than on `lazydev` itself, which also contains
+//! the entry points of the core contract.
+
+#![cfg_attr(not(test), warn(clippy::pedantic, clippy::unwrap_used, clippy::panic))]
+#![allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+
+use cosmwasm_std::{Addr, Event};
+
+use crate::{event::RewardEvent, reward::PrReward, state::Repo};
+
+pub mod contract;
+pub mod event;
+pub mod forge;
+pub mod msg;
+pub mod oracle;
+pub mod reward;
+pub mod state;
+
+/// Construct the event for the lazydev reward.
+#[must_use]
+pub fn reward_event(
+    reward: &PrReward,
+    repo: Repo,
+    pr_id: u64,
+    user_id: u64,
+    recipient: Addr,
+) -> Event {
+    RewardEvent {
+        reward: reward.clone(),
+        repo,
+        pr_id,
+        user_id,
+        recipient,
+    }
+    .into()
+}
+
+/// Re-exports used by the exported macros.
+#[doc(hidden)]
+pub mod __private {
+    pub use cosmwasm_std::{from_json, to_json_string, Event};
+}
@@ -0,0 +1,122 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Empty, Uint128};
+
+use crate::{forge::Forge, reward::PrReward, state::Repo};
+
+/// `ExecuteMsg` interface for reward contracts.
+#[cw_serde]
+pub enum RewardExecuteMsg {
+    Reward(RewardMsg),
+    Revoke(RevokeMsg),
+}
+
+/// The reward callback message for the reward contracts. This is expected to be
+/// in an `ExecuteMsg` under the key `"reward"`.
+///
+/// Fields that were added after the first version of this interface are omitted when they are
+/// unset, such that reward contracts built against an older version keep accepting them.
+#[cw_serde]
+pub struct RewardMsg {
+    /// The repo that the rewards are for.
+    pub repo: Repo,
+    /// The github pr id that the rewards are for.
+    pub pr_id: u64,
+    /// The user id of the user receiving the reward, on the forge of `repo`.
+    pub user_id: u64,
+    /// The recipient address to send the rewards to.
+    pub recipient_address: Addr,
+    /// Additional reward config.
+    pub reward_config: String,
+    /// The protocol fee that the reward contract should deduct from the reward and send to the
+    /// treasury, if any. Reward contracts with indivisible rewards may ignore this.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol_fee: Option<ProtocolFee>,
+    /// The position of this PR among the PRs of the user that have been rewarded in `repo`,
+    /// starting at 1 for their first rewarded PR. This is 0 if it is not known.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub ordinal: u64,
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)] // required by serde
+fn is_zero(n: &u64) -> bool {
+    *n == 0
+}
+
+/// The revoke callback message for the reward contracts, sent when a rewarded PR has been reverted.
+/// This is expected to be in an `ExecuteMsg` under the key `"revoke"`.
+///
+/// Reward contracts may cancel any part of the reward that has not been paid out yet, for example
+/// unvested amounts or badges. Reward contracts that can not revoke their rewards may reject this
+/// message, which does not prevent the PR from being marked as reverted.
+#[cw_serde]
+pub struct RevokeMsg {
+    /// The reward message that the PR was rewarded with.
+    pub reward: RewardMsg,
+    /// The id of the PR that reverted the rewarded PR, if it was proven.
+    #[serde(default)]
+    pub revert_pr_id: Option<u64>,
+}
+
+#[cw_serde]
+pub struct ProtocolFee {
+    /// The fee, in basis points of the reward.
+    pub bps: u16,
+    /// The address that the fee should be sent to.
+    pub treasury: Addr,
+}
+
+impl ProtocolFee {
+    /// The part of `amount` that is owed to the treasury, rounded down.
+    #[must_use]
+    pub fn fee_amount(&self, amount: Uint128) -> Uint128 {
+        amount.multiply_ratio(self.bps, 10_000_u128)
+    }
+}
+
+/// `QueryMsg` interface for reward contracts.
+///
+/// NOTE: This assumes that the user is eligible for these rewards. Since this query is not
+/// permissioned, it is up to the caller to check for eligibility before making any assumptions.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum RewardQueryMsg {
+    /// Query the rewards that this contract will send to `recipient_address`.
+    #[returns(QueryRewardsResponse)]
+    Rewards(RewardMsg),
+    /// Validate that this contract rewards PRs in `scope` with `reward_config`. Returns an error if
+    /// the scope is not allowed or the config is invalid.
+    ///
+    /// This is queried for every label config when a repo or org is linked or its config is
+    /// updated.
+    #[returns(Empty)]
+    ValidateRewardConfig(ValidateRewardConfigMsg),
+}
+
+#[cw_serde]
+pub struct ValidateRewardConfigMsg {
+    pub scope: RewardScope,
+    /// The reward config of the label config, see [`RewardMsg::reward_config`].
+    pub reward_config: String,
+}
+
+/// The repos that a label config applies to.
+#[cw_serde]
+pub enum RewardScope {
+    /// A single repo, linked with `LinkRepo`.
+    Repo(Repo),
+    /// Every repo in an org, linked with `LinkOrg`.
+    Org {
+        /// The forge of the org. This is omitted for github orgs.
+        #[serde(default, skip_serializing_if = "Forge::is_github")]
+        forge: Forge,
+        org: String,
+    },
+}
+
+#[cw_serde]
+pub struct QueryRewardsResponse {
+    /// Whether or not these rewards have already been claimed. If `true`, then `rewards` is the
+    /// *actual* reward that the user received when they originally claimed the rewards.
+    pub claimed: bool,
+    pub rewards: Vec<PrReward>,
+}
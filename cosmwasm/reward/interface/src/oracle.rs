@@ -0,0 +1,22 @@
+//! The price oracle interface used by reward contracts that price their rewards in a quote currency,
+//! such as `lazydev-fiat-reward`.
+
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Decimal, Timestamp};
+
+/// `QueryMsg` interface for price oracles.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum OracleQueryMsg {
+    /// The price of one whole `base` token in `quote`, e.g. `{ "base": "ATOM", "quote": "USD" }`.
+    /// Returns an error if the oracle has no price for the pair.
+    #[returns(PriceResponse)]
+    Price { base: String, quote: String },
+}
+
+#[cw_serde]
+pub struct PriceResponse {
+    pub price: Decimal,
+    /// The time the price was last updated.
+    pub updated_at: Timestamp,
+}
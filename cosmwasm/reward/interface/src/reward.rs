@@ -0,0 +1,43 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, Timestamp, Uint128};
+
+#[cw_serde]
+#[non_exhaustive]
+pub enum PrReward {
+    Token {
+        /// The denom of the token for this reward. If this is a native token, it will be the
+        /// denom, otherwise if this is a cw20 token, this will be the contract address of the
+        /// token.
+        denom: String,
+        amount: Uint128,
+    },
+    Nft {
+        collection_name: String,
+        symbol: String,
+        id: u64,
+    },
+    /// Points towards a share of the reward pool of an epoch, which is paid out in `denom` once
+    /// the epoch has closed.
+    PoolShare {
+        denom: String,
+        epoch: u64,
+        points: Uint128,
+    },
+    /// Non-transferable reputation points in a category, such as `bug` or `docs`.
+    Reputation { category: String, points: Uint128 },
+    /// A token reward that was denominated in a quote currency, and converted to an amount of the
+    /// token with a price oracle.
+    PricedToken {
+        /// The denom of the token for this reward, see [`PrReward::Token`].
+        denom: String,
+        amount: Uint128,
+        /// The quote currency of the reward, e.g. `USD`.
+        quote: String,
+        /// The value of the reward in `quote`, before any protocol fee was deducted.
+        quote_amount: Decimal,
+        /// The price of one whole token in `quote` that the reward was converted with.
+        price: Decimal,
+        /// The time the price was last updated by the oracle.
+        price_updated_at: Timestamp,
+    },
+}
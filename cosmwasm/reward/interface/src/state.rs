@@ -0,0 +1,60 @@
+use cosmwasm_schema::cw_serde;
+use cw_storage_plus::{Key, KeyDeserialize, Prefixer, PrimaryKey};
+
+use crate::forge::Forge;
+
+#[cw_serde]
+pub struct Repo {
+    /// The forge that this repo is hosted on. This is omitted for github repos.
+    #[serde(default, skip_serializing_if = "Forge::is_github")]
+    pub forge: Forge,
+    pub org: String,
+    pub repo: String,
+}
+
+impl<'a> PrimaryKey<'a> for Repo {
+    type Prefix = <(Forge, String, String) as PrimaryKey<'a>>::Prefix;
+
+    type SubPrefix = <(Forge, String, String) as PrimaryKey<'a>>::SubPrefix;
+
+    type Suffix = <(Forge, String, String) as PrimaryKey<'a>>::Suffix;
+
+    type SuperSuffix = <(Forge, String, String) as PrimaryKey<'a>>::SuperSuffix;
+
+    fn key(&self) -> Vec<Key<'_>> {
+        // copied from the tuple impl bc this trait is stupid
+        let mut keys = self.forge.key();
+        keys.extend(self.org.key());
+        keys.extend(self.repo.key());
+        keys
+    }
+}
+
+impl Prefixer<'_> for Repo {
+    fn prefix(&self) -> Vec<Key<'_>> {
+        self.key()
+    }
+}
+
+impl KeyDeserialize for Repo {
+    type Output = Repo;
+
+    const KEY_ELEMS: u16 = <(Forge, String, String) as KeyDeserialize>::KEY_ELEMS;
+
+    fn from_vec(value: Vec<u8>) -> cosmwasm_std::StdResult<Self::Output> {
+        let (forge, org, repo) = <(Forge, String, String) as KeyDeserialize>::from_vec(value)?;
+
+        Ok(Self { forge, org, repo })
+    }
+}
+
+/// Construct a github repo from `(org, repo)`.
+impl<T: Into<String>, U: Into<String>> From<(T, U)> for Repo {
+    fn from((org, repo): (T, U)) -> Self {
+        Self {
+            forge: Forge::Github,
+            org: org.into(),
+            repo: repo.into(),
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! A price oracle with admin-set prices, for testing contracts that use the
+//! [`OracleQueryMsg`][lazydev_reward_interface::oracle::OracleQueryMsg] interface. This must not be
+//! used in production.
+
+use cosmwasm_std::{
+    ensure, entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response,
+    StdError, StdResult,
+};
+use lazydev_reward_interface::{contract::STORAGE_ACCESS_INFALLIBLE_MSG, oracle::PriceResponse};
+
+use crate::{
+    error::Error,
+    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
+    state::{ADMIN, PRICES},
+};
+
+#[entry_point]
+#[allow(clippy::needless_pass_by_value)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> StdResult<Response> {
+    ADMIN
+        .save(deps.storage, &info.sender)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    Ok(Response::default())
+}
+
+#[entry_point]
+#[allow(clippy::needless_pass_by_value)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Price { base, quote } => Ok(to_json_binary(
+            &PRICES
+                .may_load(deps.storage, (&base, &quote))?
+                .ok_or_else(|| StdError::not_found(format!("price of {base} in {quote}")))?,
+        )?),
+    }
+}
+
+#[entry_point]
+#[allow(clippy::needless_pass_by_value)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, Error> {
+    match msg {
+        ExecuteMsg::SetPrice { base, quote, price } => {
+            ensure!(
+                info.sender
+                    == ADMIN
+                        .load(deps.storage)
+                        .expect(STORAGE_ACCESS_INFALLIBLE_MSG),
+                Error::OnlyAdmin
+            );
+
+            PRICES
+                .save(
+                    deps.storage,
+                    (&base, &quote),
+                    &PriceResponse {
+                        price,
+                        updated_at: env.block.time,
+                    },
+                )
+                .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+            Ok(Response::default())
+        }
+    }
+}
+
+#[entry_point]
+pub fn migrate(_: DepsMut, _: Env, _: MigrateMsg) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        from_json,
+        testing::{message_info, mock_dependencies, mock_env},
+        Decimal,
+    };
+
+    use super::*;
+    use crate::msg::QueryMsg;
+
+    #[test]
+    fn set_price() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let admin = deps.api.addr_make("admin");
+        let other = deps.api.addr_make("other");
+
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&admin, &[]),
+            InstantiateMsg {},
+        )
+        .unwrap();
+
+        let price = |deps: Deps, env: &Env| {
+            query(
+                deps,
+                env.clone(),
+                QueryMsg::Price {
+                    base: "ATOM".to_owned(),
+                    quote: "USD".to_owned(),
+                },
+            )
+            .map(|price| from_json::<PriceResponse>(price).unwrap())
+        };
+        let set_price = |price| ExecuteMsg::SetPrice {
+            base: "ATOM".to_owned(),
+            quote: "USD".to_owned(),
+            price,
+        };
+
+        assert!(price(deps.as_ref(), &env).is_err());
+
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&other, &[]),
+                set_price(Decimal::percent(400)),
+            ),
+            Err(Error::OnlyAdmin)
+        );
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&admin, &[]),
+            set_price(Decimal::percent(400)),
+        )
+        .unwrap();
+        assert_eq!(
+            price(deps.as_ref(), &env),
+            Ok(PriceResponse {
+                price: Decimal::percent(400),
+                updated_at: env.block.time,
+            })
+        );
+
+        // updating the price refreshes its timestamp
+        env.block.time = env.block.time.plus_seconds(60);
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&admin, &[]),
+            set_price(Decimal::percent(450)),
+        )
+        .unwrap();
+        assert_eq!(
+            price(deps.as_ref(), &env),
+            Ok(PriceResponse {
+                price: Decimal::percent(450),
+                updated_at: env.block.time,
+            })
+        );
+    }
+}
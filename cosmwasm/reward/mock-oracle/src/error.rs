@@ -0,0 +1,5 @@
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum Error {
+    #[error("only admin")]
+    OnlyAdmin,
+}
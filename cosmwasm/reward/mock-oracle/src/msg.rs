@@ -0,0 +1,21 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Decimal;
+
+pub use lazydev_reward_interface::oracle::OracleQueryMsg as QueryMsg;
+
+#[cw_serde]
+pub struct InstantiateMsg {}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Set the price of one whole `base` token in `quote`. The price is timestamped with the current
+    /// block time.
+    SetPrice {
+        base: String,
+        quote: String,
+        price: Decimal,
+    },
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
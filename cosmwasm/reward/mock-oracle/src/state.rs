@@ -0,0 +1,9 @@
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Item, Map};
+use lazydev_reward_interface::oracle::PriceResponse;
+
+/// The only address allowed to set prices.
+pub const ADMIN: Item<Addr> = Item::new("admin");
+
+/// The prices, keyed by `(base, quote)`.
+pub const PRICES: Map<(&str, &str), PriceResponse> = Map::new("prices");
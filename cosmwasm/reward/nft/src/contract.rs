@@ -1,12 +1,12 @@
 use cosmwasm_std::{
     ensure, instantiate2_address, to_json_binary, wasm_execute, Binary, Checksum, CodeInfoResponse,
-    Deps, DepsMut, Env, MessageInfo, QueryRequest, Response, StdResult, SubMsg, WasmMsg,
+    Deps, DepsMut, Empty, Env, MessageInfo, QueryRequest, Response, StdError, StdResult, SubMsg,
+    WasmMsg,
 };
-use lazydev::{
-    contract::STORAGE_ACCESS_INFALLIBLE_MSG,
-    models::reward::PrReward,
-    msg::{QueryRewardsResponse, RewardMsg},
-    reward_event,
+use lazydev_reward_interface::{
+    contract::{SERIALIZATION_INFALLIBLE_MSG, STORAGE_ACCESS_INFALLIBLE_MSG},
+    msg::{RewardScope, ValidateRewardConfigMsg},
+    reward::PrReward,
 };
 use sha2::{Digest, Sha256};
 
@@ -14,8 +14,8 @@ use crate::{
     error::Error,
     msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
     state::{
-        CollectionInfo, ADMIN, ALLOWED_ORGS, ALLOWED_REPOS, CLAIMED_REWARDS, COLLECTION_INFO,
-        CW721_ADDR, LAST_NFT_ID, LAZYDEV_ADDR,
+        CollectionInfo, ADMIN, ALLOWED_ORGS, ALLOWED_REPOS, COLLECTION_INFO, CW721_ADDR,
+        LAST_NFT_ID, REWARDS,
     },
 };
 
@@ -31,9 +31,7 @@ pub fn instantiate(
         .save(deps.storage, &info.sender)
         .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
 
-    LAZYDEV_ADDR
-        .save(deps.storage, &msg.lazydev_address)
-        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+    REWARDS.init(deps.storage, &msg.lazydev_address);
 
     ALLOWED_REPOS
         .save(deps.storage, &msg.config.valid_repos)
@@ -89,6 +87,25 @@ pub fn instantiate(
     }))
 }
 
+/// Whether this contract rewards PRs in `scope`.
+fn is_allowed(deps: Deps, scope: &RewardScope) -> bool {
+    let allowed_orgs = ALLOWED_ORGS
+        .load(deps.storage)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    // org allowlists only apply to github, repos on other forges must be allowed individually
+    match scope {
+        RewardScope::Repo(repo) => {
+            (repo.forge.is_github() && allowed_orgs.contains(&repo.org))
+                || ALLOWED_REPOS
+                    .load(deps.storage)
+                    .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+                    .contains(repo)
+        }
+        RewardScope::Org { forge, org } => forge.is_github() && allowed_orgs.contains(org),
+    }
+}
+
 fn get_code_hash(deps: Deps, code_id: u64) -> StdResult<Checksum> {
     Ok(deps
         .querier
@@ -102,32 +119,31 @@ fn get_code_hash(deps: Deps, code_id: u64) -> StdResult<Checksum> {
 #[allow(clippy::needless_pass_by_value)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::Rewards(RewardMsg {
-            repo,
-            pr_id,
-            user_id: _,
-            recipient_address: _,
+        // NOTE: nfts can't be split, so no protocol fee is deducted
+        QueryMsg::Rewards(msg) => Ok(to_json_binary(&REWARDS.query(
+            deps.storage,
+            &msg,
+            || {
+                let collection_info = COLLECTION_INFO.load(deps.storage)?;
+
+                Ok(PrReward::Nft {
+                    symbol: collection_info.symbol,
+                    id: LAST_NFT_ID.load(deps.storage)? + 1,
+                    collection_name: collection_info.collection_name,
+                })
+            },
+        )?)?),
+        QueryMsg::ValidateRewardConfig(ValidateRewardConfigMsg {
+            scope,
+            // NOTE: Currently unused, so any config is accepted
             reward_config: _,
         }) => {
-            let response = match CLAIMED_REWARDS.may_load(deps.storage, (pr_id, repo))? {
-                Some(claimed_rewards) => QueryRewardsResponse {
-                    claimed: true,
-                    rewards: vec![claimed_rewards],
-                },
-                None => {
-                    let collection_info = COLLECTION_INFO.load(deps.storage)?;
-                    QueryRewardsResponse {
-                        claimed: false,
-                        rewards: vec![PrReward::Nft {
-                            symbol: collection_info.symbol.to_string(),
-                            id: LAST_NFT_ID.load(deps.storage)? + 1,
-                            collection_name: collection_info.collection_name.to_string(),
-                        }],
-                    }
-                }
-            };
+            ensure!(
+                is_allowed(deps, &scope),
+                StdError::generic_err(Error::InvalidScope(scope).to_string())
+            );
 
-            Ok(to_json_binary(&response)?)
+            Ok(to_json_binary(&Empty {})?)
         }
     }
 }
@@ -141,36 +157,18 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, Error> {
     match msg {
-        ExecuteMsg::Reward(RewardMsg {
-            repo,
-            pr_id,
-            user_id,
-            recipient_address,
-            // NOTE: Currently unused
-            reward_config: _,
-        }) => {
-            ensure!(
-                LAZYDEV_ADDR
-                    .load(deps.storage)
-                    .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
-                    == info.sender,
-                Error::OnlyLazydev
-            );
+        // NOTE: reward_config is currently unused, and nfts can't be split, so no protocol fee is
+        // deducted
+        ExecuteMsg::Reward(msg) => {
+            REWARDS.ensure_lazydev(deps.storage, &info.sender)?;
 
             let cw721_token_address = CW721_ADDR
                 .load(deps.storage)
                 .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
 
             ensure!(
-                ALLOWED_ORGS
-                    .load(deps.storage)
-                    .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
-                    .contains(&repo.org)
-                    || ALLOWED_REPOS
-                        .load(deps.storage)
-                        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
-                        .contains(&repo),
-                Error::OnlyLazydev
+                is_allowed(deps.as_ref(), &RewardScope::Repo(msg.repo.clone())),
+                Error::InvalidRepo(msg.repo)
             );
 
             let nft_id = LAST_NFT_ID
@@ -189,9 +187,6 @@ pub fn execute(
                 id: nft_id,
                 collection_name: collection_info.collection_name,
             };
-            CLAIMED_REWARDS
-                .save(deps.storage, (pr_id, repo.clone()), &reward)
-                .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
 
             Ok(Response::new()
                 .add_submessage(SubMsg::new(
@@ -199,25 +194,47 @@ pub fn execute(
                         cw721_token_address,
                         &crate::msg::cw721::ExecuteMsg::Mint {
                             token_id: nft_id.to_string(),
-                            owner: recipient_address.to_string(),
+                            owner: msg.recipient_address.to_string(),
                             token_uri: None,
                         },
                         vec![],
                     )
                     .expect("works"),
                 ))
-                .add_event(reward_event(
-                    &reward,
-                    repo,
-                    pr_id,
-                    user_id,
-                    recipient_address.to_string(),
-                )))
+                .add_event(REWARDS.claim(deps.storage, &msg, &reward)))
+        }
+        ExecuteMsg::Revoke(msg) => {
+            REWARDS.ensure_lazydev(deps.storage, &info.sender)?;
+
+            // the badge stays claimed, such that the PR can not be rewarded again
+            let Some(PrReward::Nft { id, .. }) = REWARDS
+                .claimed(deps.storage, &msg.reward.repo, msg.reward.pr_id)
+                .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+            else {
+                return Ok(Response::default());
+            };
+
+            let cw721_token_address = CW721_ADDR
+                .load(deps.storage)
+                .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+            Ok(Response::new().add_submessage(SubMsg::new(
+                wasm_execute(
+                    cw721_token_address,
+                    &crate::msg::cw721::ExecuteMsg::Burn {
+                        token_id: id.to_string(),
+                    },
+                    vec![],
+                )
+                .expect(SERIALIZATION_INFALLIBLE_MSG),
+            )))
         }
     }
 }
 
 #[cosmwasm_std::entry_point]
-pub fn migrate(_: DepsMut, _: Env, _: MigrateMsg) -> StdResult<Response> {
+pub fn migrate(deps: DepsMut, _: Env, _: MigrateMsg) -> StdResult<Response> {
+    REWARDS.migrate(deps.storage);
+
     Ok(Response::default())
 }
@@ -1,12 +1,14 @@
 use cosmwasm_std::StdError;
-use lazydev::state::Repo;
+use lazydev_reward_interface::{contract::OnlyLazydev, msg::RewardScope, state::Repo};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
-    #[error("only lazydev")]
-    OnlyLazydev,
+    #[error(transparent)]
+    OnlyLazydev(#[from] OnlyLazydev),
     #[error("repo {}/{} not allowed", .0.org, .0.repo)]
     InvalidRepo(Repo),
+    #[error("{0:?} not allowed")]
+    InvalidScope(RewardScope),
     #[error("invalid config")]
     InvalidConfig(StdError),
 }
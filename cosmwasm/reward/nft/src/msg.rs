@@ -1,7 +1,7 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Addr;
-use lazydev::{
-    msg::{QueryRewardsResponse, RewardMsg},
+use cosmwasm_std::{Addr, Empty};
+use lazydev_reward_interface::{
+    msg::{QueryRewardsResponse, RevokeMsg, RewardMsg, ValidateRewardConfigMsg},
     state::Repo,
 };
 
@@ -19,7 +19,8 @@ pub struct Config {
     pub symbol: String,
     /// The repos this contract will provide rewards for.
     pub valid_repos: Vec<Repo>,
-    /// The orgs this contract will provide rewards for.
+    /// The github orgs this contract will provide rewards for. Repos on other forges must be listed
+    /// in `valid_repos`.
     pub valid_orgs: Vec<String>,
     /// The code id of the cw721 base code to instantiate the token with.
     pub cw721_base_code_id: u64,
@@ -28,6 +29,9 @@ pub struct Config {
 #[cw_serde]
 pub enum ExecuteMsg {
     Reward(RewardMsg),
+    /// Burn the badge of a reverted PR. The cw721 contract must allow the minter to burn tokens,
+    /// otherwise the revoke is rejected.
+    Revoke(RevokeMsg),
 }
 
 #[cw_serde]
@@ -35,6 +39,8 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(QueryRewardsResponse)]
     Rewards(RewardMsg),
+    #[returns(Empty)]
+    ValidateRewardConfig(ValidateRewardConfigMsg),
 }
 
 #[cw_serde]
@@ -72,5 +78,7 @@ pub mod cw721 {
             /// Metadata JSON Schema
             token_uri: Option<String>,
         },
+        /// Burn an NFT the sender has access to
+        Burn { token_id: String },
     }
 }
@@ -1,7 +1,7 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::Addr;
-use cw_storage_plus::{Item, Map};
-use lazydev::{models::reward::PrReward, state::Repo};
+use cw_storage_plus::Item;
+use lazydev_reward_interface::{contract::Rewards, state::Repo};
 
 pub const ADMIN: Item<Addr> = Item::new("admin");
 
@@ -12,9 +12,9 @@ pub struct CollectionInfo {
     pub collection_name: String,
 }
 
-/// The address of the lazydev contract. Only this contract can call the
-/// `reward` entrypoint.
-pub const LAZYDEV_ADDR: Item<Addr> = Item::new("lazydev");
+/// The address of the lazydev contract, which is the only address that can call the `reward`
+/// entrypoint, and the already claimed rewards.
+pub const REWARDS: Rewards = Rewards::new("lazydev", "claimed_rewards");
 
 /// The address of the cw721 contract to mint tokens from.
 pub const CW721_ADDR: Item<Addr> = Item::new("cw721_addr");
@@ -26,6 +26,3 @@ pub const LAST_NFT_ID: Item<u64> = Item::new("last_nft_id");
 // The allowed orgs and repos that can use this collection.
 pub const ALLOWED_ORGS: Item<Vec<String>> = Item::new("allowed_orgs");
 pub const ALLOWED_REPOS: Item<Vec<Repo>> = Item::new("allowed_repos");
-
-/// Already claimed rewards, keyed by a tuple of `(pr_id, org, repo)`.
-pub const CLAIMED_REWARDS: Map<(u64, Repo), PrReward> = Map::new("claimed_rewards");
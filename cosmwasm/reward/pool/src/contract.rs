@@ -0,0 +1,1011 @@
+use cosmwasm_std::{
+    coin, ensure, entry_point, to_json_binary, Addr, BankMsg, Binary, Deps, DepsMut, Empty, Env,
+    MessageInfo, Response, StdError, StdResult, Storage, Timestamp, Uint128,
+};
+use cw_storage_plus::Bound;
+use lazydev_reward_interface::{
+    contract::STORAGE_ACCESS_INFALLIBLE_MSG,
+    msg::{RevokeMsg, RewardScope, ValidateRewardConfigMsg},
+    reward::PrReward,
+};
+
+use crate::{
+    error::Error,
+    event::{ClaimEvent, FundEvent, RevokeEvent, RolloverEvent, SweepEvent},
+    msg::{
+        Config, EpochResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, Payout, QueryMsg, Standing,
+    },
+    state::{
+        Contribution, Epoch, ADMIN, CONFIG, CONTRIBUTIONS, EPOCHS, GENESIS, REWARDS, STANDINGS,
+    },
+};
+
+const DEFAULT_LIMIT: u32 = 30;
+
+const MAX_LIMIT: u32 = 100;
+
+#[entry_point]
+#[allow(clippy::needless_pass_by_value)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    if msg.config.epoch_duration == 0 {
+        return Err(StdError::generic_err("epoch_duration must be non-zero"));
+    }
+
+    ADMIN
+        .save(deps.storage, &info.sender)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    REWARDS.init(deps.storage, &msg.lazydev_address);
+
+    CONFIG
+        .save(deps.storage, &msg.config)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    GENESIS
+        .save(deps.storage, &env.block.time)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    Ok(Response::default())
+}
+
+/// Whether this contract rewards PRs in `scope`.
+fn is_allowed(config: &Config, scope: &RewardScope) -> bool {
+    // org allowlists only apply to github, repos on other forges must be allowed individually
+    match scope {
+        RewardScope::Repo(repo) => {
+            (repo.forge.is_github() && config.valid_orgs.contains(&repo.org))
+                || config.valid_repos.contains(repo)
+        }
+        RewardScope::Org { forge, org } => forge.is_github() && config.valid_orgs.contains(org),
+    }
+}
+
+fn current_epoch(storage: &dyn Storage, env: &Env, config: &Config) -> u64 {
+    let genesis = GENESIS.load(storage).expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    (env.block.time.seconds() - genesis.seconds()) / config.epoch_duration
+}
+
+fn load_epoch(storage: &dyn Storage, epoch: u64) -> Epoch {
+    EPOCHS
+        .may_load(storage, epoch)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        .unwrap_or_default()
+}
+
+fn pagination_limit(limit: Option<u32>) -> usize {
+    limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize
+}
+
+#[entry_point]
+#[allow(clippy::needless_pass_by_value)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+
+    match msg {
+        QueryMsg::Rewards(msg) => Ok(to_json_binary(&REWARDS.query(
+            deps.storage,
+            &msg,
+            || {
+                Ok(PrReward::PoolShare {
+                    denom: config.denom.clone(),
+                    epoch: current_epoch(deps.storage, &env, &config),
+                    points: msg.reward_config.parse()?,
+                })
+            },
+        )?)?),
+        QueryMsg::ValidateRewardConfig(ValidateRewardConfigMsg {
+            scope,
+            reward_config,
+        }) => {
+            reward_config
+                .parse::<Uint128>()
+                .map_err(|err| StdError::generic_err(Error::InvalidConfig(err).to_string()))?;
+
+            ensure!(
+                is_allowed(&config, &scope),
+                StdError::generic_err(Error::InvalidScope(scope).to_string())
+            );
+
+            Ok(to_json_binary(&Empty {})?)
+        }
+        QueryMsg::Config {} => Ok(to_json_binary(&config)?),
+        QueryMsg::Admin {} => Ok(to_json_binary(&ADMIN.load(deps.storage)?)?),
+        QueryMsg::Epoch { epoch } => {
+            let current_epoch = current_epoch(deps.storage, &env, &config);
+            let epoch = epoch.unwrap_or(current_epoch);
+            let Epoch {
+                pool,
+                total_points,
+                claimed,
+            } = load_epoch(deps.storage, epoch);
+
+            let start = GENESIS
+                .load(deps.storage)?
+                .plus_seconds(epoch * config.epoch_duration);
+
+            Ok(to_json_binary(&EpochResponse {
+                epoch,
+                start,
+                end: start.plus_seconds(config.epoch_duration),
+                closed: epoch < current_epoch,
+                claim_deadline: claim_deadline(deps.storage, &config, epoch),
+                pool,
+                total_points,
+                claimed,
+            })?)
+        }
+        QueryMsg::Standings {
+            epoch,
+            start_after,
+            limit,
+        } => {
+            let epoch = epoch.unwrap_or_else(|| current_epoch(deps.storage, &env, &config));
+            let epoch_state = load_epoch(deps.storage, epoch);
+
+            Ok(to_json_binary(
+                &STANDINGS
+                    .prefix(epoch)
+                    .range(
+                        deps.storage,
+                        start_after.as_ref().map(Bound::exclusive),
+                        None,
+                        cosmwasm_std::Order::Ascending,
+                    )
+                    .take(pagination_limit(limit))
+                    .map(|res| {
+                        res.map(|(recipient, points)| Standing {
+                            recipient,
+                            points,
+                            share: epoch_state.payout(points),
+                        })
+                    })
+                    .collect::<StdResult<Vec<_>>>()?,
+            )?)
+        }
+        QueryMsg::Payouts {
+            recipient,
+            start_after,
+            limit,
+        } => Ok(to_json_binary(
+            &CONTRIBUTIONS
+                .prefix(&recipient)
+                .range(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    cosmwasm_std::Order::Ascending,
+                )
+                .take(pagination_limit(limit))
+                .map(|res| {
+                    res.map(|(epoch, Contribution { points, claimed })| Payout {
+                        epoch,
+                        points,
+                        share: claimed
+                            .unwrap_or_else(|| load_epoch(deps.storage, epoch).payout(points)),
+                        claimed: claimed.is_some(),
+                    })
+                })
+                .collect::<StdResult<Vec<_>>>()?,
+        )?),
+    }
+}
+
+#[entry_point]
+#[allow(clippy::needless_pass_by_value)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, Error> {
+    let config = CONFIG
+        .load(deps.storage)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+    let current_epoch = current_epoch(deps.storage, &env, &config);
+
+    match msg {
+        ExecuteMsg::Reward(msg) => {
+            REWARDS.ensure_lazydev(deps.storage, &info.sender)?;
+
+            let points = msg
+                .reward_config
+                .parse::<Uint128>()
+                .map_err(Error::InvalidConfig)?;
+
+            ensure!(
+                is_allowed(&config, &RewardScope::Repo(msg.repo.clone())),
+                Error::InvalidRepo(msg.repo)
+            );
+
+            accrue_points(deps.storage, current_epoch, &msg.recipient_address, points);
+
+            // the protocol fee is not deducted from points, since the pool is funded separately
+            let reward_event = REWARDS.claim(
+                deps.storage,
+                &msg,
+                &PrReward::PoolShare {
+                    denom: config.denom,
+                    epoch: current_epoch,
+                    points,
+                },
+            );
+
+            Ok(Response::new().add_event(reward_event))
+        }
+        ExecuteMsg::Fund { epoch } => {
+            let epoch = epoch.unwrap_or(current_epoch);
+
+            ensure!(
+                epoch >= current_epoch,
+                Error::EpochClosed {
+                    epoch,
+                    current_epoch
+                }
+            );
+
+            let [funds] = &*info.funds else {
+                return Err(Error::InvalidFunds(config.denom));
+            };
+            ensure!(
+                funds.denom == config.denom && !funds.amount.is_zero(),
+                Error::InvalidFunds(config.denom)
+            );
+
+            let mut epoch_state = load_epoch(deps.storage, epoch);
+            epoch_state.pool += funds.amount;
+            EPOCHS
+                .save(deps.storage, epoch, &epoch_state)
+                .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+            Ok(Response::new().add_event(FundEvent {
+                epoch,
+                funder: info.sender,
+                amount: funds.clone(),
+            }))
+        }
+        ExecuteMsg::Claim { epoch } => {
+            claim(deps, &env, &config, info.sender, epoch, current_epoch)
+        }
+        ExecuteMsg::Rollover { epoch } => rollover(deps.storage, &config, epoch, current_epoch),
+        ExecuteMsg::Revoke(msg) => {
+            REWARDS.ensure_lazydev(deps.storage, &info.sender)?;
+
+            revoke(deps.storage, current_epoch, msg)
+        }
+        ExecuteMsg::Sweep { epoch, recipient } => {
+            ensure!(
+                info.sender
+                    == ADMIN
+                        .load(deps.storage)
+                        .expect(STORAGE_ACCESS_INFALLIBLE_MSG),
+                Error::OnlyAdmin
+            );
+
+            sweep(deps.storage, &env, &config, epoch, recipient)
+        }
+    }
+}
+
+/// The end of the claim period of `epoch`, see [`Config::claim_period`].
+fn claim_deadline(storage: &dyn Storage, config: &Config, epoch: u64) -> Timestamp {
+    GENESIS
+        .load(storage)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        .plus_seconds((epoch + 1) * config.epoch_duration + config.claim_period)
+}
+
+/// Pay out the share of `recipient` in the pool of the closed `epoch`.
+fn claim(
+    deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    recipient: Addr,
+    epoch: u64,
+    current_epoch: u64,
+) -> Result<Response, Error> {
+    ensure!(
+        epoch < current_epoch,
+        Error::EpochNotClosed {
+            epoch,
+            current_epoch
+        }
+    );
+
+    let claim_deadline = claim_deadline(deps.storage, config, epoch);
+    ensure!(
+        env.block.time < claim_deadline,
+        Error::ClaimPeriodEnded {
+            epoch,
+            claim_deadline
+        }
+    );
+
+    let mut contribution = CONTRIBUTIONS
+        .may_load(deps.storage, (&recipient, epoch))
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        .ok_or_else(|| Error::NoContribution {
+            recipient: recipient.clone(),
+            epoch,
+        })?;
+
+    ensure!(
+        contribution.claimed.is_none(),
+        Error::AlreadyClaimed { recipient, epoch }
+    );
+
+    let mut epoch_state = load_epoch(deps.storage, epoch);
+    let amount = epoch_state.payout(contribution.points);
+
+    epoch_state.claimed += amount;
+    EPOCHS
+        .save(deps.storage, epoch, &epoch_state)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    contribution.claimed = Some(amount);
+    CONTRIBUTIONS
+        .save(deps.storage, (&recipient, epoch), &contribution)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    let amount = coin(amount.u128(), &config.denom);
+
+    let mut response = Response::new().add_event(ClaimEvent {
+        epoch,
+        recipient: recipient.clone(),
+        points: contribution.points,
+        amount: amount.clone(),
+    });
+
+    if !amount.amount.is_zero() {
+        response = response.add_message(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![amount],
+        });
+    }
+
+    Ok(response)
+}
+
+/// Remove the points of the PR in `msg` from its recipient, see [`ExecuteMsg::Revoke`].
+fn revoke(
+    storage: &mut dyn Storage,
+    current_epoch: u64,
+    msg: RevokeMsg,
+) -> Result<Response, Error> {
+    let Some(PrReward::PoolShare { epoch, points, .. }) = REWARDS
+        .claimed(storage, &msg.reward.repo, msg.reward.pr_id)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+    else {
+        return Err(Error::NotRewarded {
+            repo: msg.reward.repo,
+            pr_id: msg.reward.pr_id,
+        });
+    };
+
+    let recipient = msg.reward.recipient_address;
+
+    let mut contribution = CONTRIBUTIONS
+        .load(storage, (&recipient, epoch))
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    ensure!(
+        contribution.claimed.is_none(),
+        Error::AlreadyClaimed { recipient, epoch }
+    );
+
+    contribution.points -= points;
+    CONTRIBUTIONS
+        .save(storage, (&recipient, epoch), &contribution)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+    STANDINGS
+        .save(storage, (epoch, &recipient), &contribution.points)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    // the total points of closed epochs are kept, since the other contributors may already have
+    // claimed their share
+    if epoch == current_epoch {
+        let mut epoch_state = load_epoch(storage, epoch);
+        epoch_state.total_points -= points;
+        EPOCHS
+            .save(storage, epoch, &epoch_state)
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+    }
+
+    Ok(Response::new().add_event(RevokeEvent {
+        epoch,
+        recipient,
+        points,
+    }))
+}
+
+/// Send the unclaimed part of the pool of `epoch` to `recipient`, see [`ExecuteMsg::Sweep`].
+fn sweep(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    epoch: u64,
+    recipient: Addr,
+) -> Result<Response, Error> {
+    let claim_deadline = claim_deadline(storage, config, epoch);
+    ensure!(
+        env.block.time >= claim_deadline,
+        Error::ClaimPeriodNotEnded {
+            epoch,
+            claim_deadline
+        }
+    );
+
+    let mut epoch_state = load_epoch(storage, epoch);
+
+    let amount = epoch_state.pool - epoch_state.claimed;
+    ensure!(!amount.is_zero(), Error::EmptyPool(epoch));
+
+    epoch_state.pool = epoch_state.claimed;
+    EPOCHS
+        .save(storage, epoch, &epoch_state)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    let amount = coin(amount.u128(), &config.denom);
+
+    Ok(Response::new()
+        .add_event(SweepEvent {
+            epoch,
+            recipient: recipient.clone(),
+            amount: amount.clone(),
+        })
+        .add_message(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![amount],
+        }))
+}
+
+/// Move the pool of `epoch` to the pool of `current_epoch`, if `epoch` is closed and has no points.
+/// This also covers epochs where all points were revoked.
+fn rollover(
+    storage: &mut dyn Storage,
+    config: &Config,
+    epoch: u64,
+    current_epoch: u64,
+) -> Result<Response, Error> {
+    ensure!(
+        epoch < current_epoch,
+        Error::EpochNotClosed {
+            epoch,
+            current_epoch
+        }
+    );
+
+    let mut epoch_state = load_epoch(storage, epoch);
+
+    ensure!(
+        epoch_state.total_points.is_zero(),
+        Error::EpochHasPoints(epoch)
+    );
+    ensure!(!epoch_state.pool.is_zero(), Error::EmptyPool(epoch));
+
+    let amount = epoch_state.pool;
+    epoch_state.pool = Uint128::zero();
+    EPOCHS
+        .save(storage, epoch, &epoch_state)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    let mut current_epoch_state = load_epoch(storage, current_epoch);
+    current_epoch_state.pool += amount;
+    EPOCHS
+        .save(storage, current_epoch, &current_epoch_state)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    Ok(Response::new().add_event(RolloverEvent {
+        epoch,
+        to_epoch: current_epoch,
+        amount: coin(amount.u128(), &config.denom),
+    }))
+}
+
+/// Add `points` to the points of `recipient` and the total points in `epoch`.
+fn accrue_points(storage: &mut dyn Storage, epoch: u64, recipient: &Addr, points: Uint128) {
+    let mut epoch_state = load_epoch(storage, epoch);
+    epoch_state.total_points += points;
+    EPOCHS
+        .save(storage, epoch, &epoch_state)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    let mut contribution = CONTRIBUTIONS
+        .may_load(storage, (recipient, epoch))
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        .unwrap_or_default();
+    contribution.points += points;
+    CONTRIBUTIONS
+        .save(storage, (recipient, epoch), &contribution)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    STANDINGS
+        .save(storage, (epoch, recipient), &contribution.points)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+}
+
+#[entry_point]
+pub fn migrate(_: DepsMut, _: Env, _: MigrateMsg) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        coins, from_json,
+        testing::{message_info, mock_dependencies, mock_env},
+    };
+    use lazydev_reward_interface::{msg::RewardMsg, state::Repo};
+
+    use super::*;
+
+    #[test]
+    fn epochs() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let lazydev = deps.api.addr_make("lazydev");
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        let sponsor = deps.api.addr_make("sponsor");
+
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&sponsor, &[]),
+            InstantiateMsg {
+                config: Config {
+                    denom: "ulazy".to_owned(),
+                    epoch_duration: 100,
+                    claim_period: 1000,
+                    valid_repos: vec![],
+                    valid_orgs: vec!["unionlabs".to_owned()],
+                },
+                lazydev_address: lazydev.clone(),
+            },
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&sponsor, &coins(1000, "ulazy")),
+            ExecuteMsg::Fund { epoch: None },
+        )
+        .unwrap();
+
+        for (pr_id, recipient, points) in [(1, &alice, "2"), (2, &bob, "1"), (3, &alice, "1")] {
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&lazydev, &[]),
+                ExecuteMsg::Reward(RewardMsg {
+                    repo: Repo::from(("unionlabs", "union")),
+                    pr_id,
+                    user_id: 1,
+                    recipient_address: recipient.clone(),
+                    reward_config: points.to_owned(),
+                    protocol_fee: None,
+                    ordinal: 1,
+                }),
+            )
+            .unwrap();
+        }
+
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&alice, &[]),
+                ExecuteMsg::Claim { epoch: 0 },
+            ),
+            Err(Error::EpochNotClosed { .. })
+        ));
+
+        env.block.time = env.block.time.plus_seconds(100);
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&alice, &[]),
+            ExecuteMsg::Claim { epoch: 0 },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            BankMsg::Send {
+                to_address: alice.to_string(),
+                amount: coins(750, "ulazy"),
+            }
+            .into()
+        );
+
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&alice, &[]),
+                ExecuteMsg::Claim { epoch: 0 },
+            ),
+            Err(Error::AlreadyClaimed { .. })
+        ));
+
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&sponsor, &coins(1000, "ulazy")),
+                ExecuteMsg::Fund { epoch: Some(0) },
+            ),
+            Err(Error::EpochClosed { .. })
+        ));
+
+        let payouts = cosmwasm_std::from_json::<Vec<Payout>>(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::Payouts {
+                    recipient: bob,
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            payouts,
+            [Payout {
+                epoch: 0,
+                points: Uint128::new(1),
+                share: Uint128::new(250),
+                claimed: false,
+            }]
+        );
+
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&sponsor, &[]),
+                ExecuteMsg::Rollover { epoch: 0 },
+            ),
+            Err(Error::EpochHasPoints(0))
+        ));
+
+        // nobody contributes in epoch 1, so its pool is rolled over to epoch 2
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&sponsor, &coins(500, "ulazy")),
+            ExecuteMsg::Fund { epoch: None },
+        )
+        .unwrap();
+
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&sponsor, &[]),
+                ExecuteMsg::Rollover { epoch: 1 },
+            ),
+            Err(Error::EpochNotClosed { .. })
+        ));
+
+        env.block.time = env.block.time.plus_seconds(100);
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&alice, &[]),
+            ExecuteMsg::Rollover { epoch: 1 },
+        )
+        .unwrap();
+
+        assert_eq!(load_epoch(&deps.storage, 1).pool, Uint128::zero());
+        assert_eq!(load_epoch(&deps.storage, 2).pool, Uint128::new(500));
+
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                env,
+                message_info(&alice, &[]),
+                ExecuteMsg::Rollover { epoch: 1 },
+            ),
+            Err(Error::EmptyPool(1))
+        ));
+    }
+
+    fn instantiate_pool(deps: DepsMut, env: &Env, admin: &Addr, lazydev: &Addr) {
+        instantiate(
+            deps,
+            env.clone(),
+            message_info(admin, &[]),
+            InstantiateMsg {
+                config: Config {
+                    denom: "ulazy".to_owned(),
+                    epoch_duration: 100,
+                    claim_period: 1000,
+                    valid_repos: vec![],
+                    valid_orgs: vec!["unionlabs".to_owned()],
+                },
+                lazydev_address: lazydev.clone(),
+            },
+        )
+        .unwrap();
+    }
+
+    fn reward_msg(pr_id: u64, recipient: &Addr, points: &str) -> RewardMsg {
+        RewardMsg {
+            repo: Repo::from(("unionlabs", "union")),
+            pr_id,
+            user_id: 1,
+            recipient_address: recipient.clone(),
+            reward_config: points.to_owned(),
+            protocol_fee: None,
+            ordinal: 1,
+        }
+    }
+
+    #[test]
+    fn funding() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let admin = deps.api.addr_make("admin");
+        let lazydev = deps.api.addr_make("lazydev");
+        let sponsor = deps.api.addr_make("sponsor");
+
+        instantiate_pool(deps.as_mut(), &env, &admin, &lazydev);
+
+        for funds in [vec![], coins(1000, "uatom"), coins(0, "ulazy")] {
+            assert!(matches!(
+                execute(
+                    deps.as_mut(),
+                    env.clone(),
+                    message_info(&sponsor, &funds),
+                    ExecuteMsg::Fund { epoch: None },
+                ),
+                Err(Error::InvalidFunds(_))
+            ));
+        }
+
+        // future epochs can be funded in advance
+        for epoch in [None, Some(0), Some(2)] {
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&sponsor, &coins(1000, "ulazy")),
+                ExecuteMsg::Fund { epoch },
+            )
+            .unwrap();
+        }
+
+        assert_eq!(load_epoch(&deps.storage, 0).pool, Uint128::new(2000));
+        assert_eq!(load_epoch(&deps.storage, 1).pool, Uint128::zero());
+        assert_eq!(load_epoch(&deps.storage, 2).pool, Uint128::new(1000));
+    }
+
+    #[test]
+    fn revoked_points() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let admin = deps.api.addr_make("admin");
+        let lazydev = deps.api.addr_make("lazydev");
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+
+        instantiate_pool(deps.as_mut(), &env, &admin, &lazydev);
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&admin, &coins(1000, "ulazy")),
+            ExecuteMsg::Fund { epoch: None },
+        )
+        .unwrap();
+
+        for (pr_id, recipient, points) in [(1, &alice, "2"), (2, &bob, "1"), (3, &alice, "1")] {
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&lazydev, &[]),
+                ExecuteMsg::Reward(reward_msg(pr_id, recipient, points)),
+            )
+            .unwrap();
+        }
+
+        let revoke = |deps: DepsMut, env: &Env, pr_id, recipient: &Addr| {
+            execute(
+                deps,
+                env.clone(),
+                message_info(&lazydev, &[]),
+                ExecuteMsg::Revoke(RevokeMsg {
+                    reward: reward_msg(pr_id, recipient, ""),
+                    revert_pr_id: None,
+                }),
+            )
+        };
+
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&alice, &[]),
+                ExecuteMsg::Revoke(RevokeMsg {
+                    reward: reward_msg(3, &alice, ""),
+                    revert_pr_id: None,
+                }),
+            ),
+            Err(Error::OnlyLazydev(_))
+        ));
+
+        assert!(matches!(
+            revoke(deps.as_mut(), &env, 4, &alice),
+            Err(Error::NotRewarded { pr_id: 4, .. })
+        ));
+
+        // points revoked in the current epoch are removed from the total
+        revoke(deps.as_mut(), &env, 3, &alice).unwrap();
+
+        assert_eq!(load_epoch(&deps.storage, 0).total_points, Uint128::new(3));
+        assert_eq!(
+            STANDINGS.load(&deps.storage, (0, &alice)),
+            Ok(Uint128::new(2))
+        );
+
+        env.block.time = env.block.time.plus_seconds(100);
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&bob, &[]),
+            ExecuteMsg::Claim { epoch: 0 },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            BankMsg::Send {
+                to_address: bob.to_string(),
+                amount: coins(333, "ulazy"),
+            }
+            .into()
+        );
+
+        assert!(matches!(
+            revoke(deps.as_mut(), &env, 2, &bob),
+            Err(Error::AlreadyClaimed { epoch: 0, .. })
+        ));
+
+        // points revoked in a closed epoch are kept in the total, such that the shares of the
+        // other contributors do not change
+        revoke(deps.as_mut(), &env, 1, &alice).unwrap();
+
+        assert_eq!(load_epoch(&deps.storage, 0).total_points, Uint128::new(3));
+        assert_eq!(
+            CONTRIBUTIONS
+                .load(&deps.storage, (&alice, 0))
+                .unwrap()
+                .points,
+            Uint128::zero()
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            env,
+            message_info(&alice, &[]),
+            ExecuteMsg::Claim { epoch: 0 },
+        )
+        .unwrap();
+        assert!(res.messages.is_empty());
+    }
+
+    #[test]
+    fn sweep_unclaimed() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let admin = deps.api.addr_make("admin");
+        let lazydev = deps.api.addr_make("lazydev");
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+
+        instantiate_pool(deps.as_mut(), &env, &admin, &lazydev);
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&admin, &coins(1000, "ulazy")),
+            ExecuteMsg::Fund { epoch: None },
+        )
+        .unwrap();
+
+        for (pr_id, recipient) in [(1, &alice), (2, &bob), (3, &bob)] {
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&lazydev, &[]),
+                ExecuteMsg::Reward(reward_msg(pr_id, recipient, "1")),
+            )
+            .unwrap();
+        }
+
+        let sweep = |deps: DepsMut, env: &Env, sender: &Addr| {
+            execute(
+                deps,
+                env.clone(),
+                message_info(sender, &[]),
+                ExecuteMsg::Sweep {
+                    epoch: 0,
+                    recipient: admin.clone(),
+                },
+            )
+        };
+
+        env.block.time = env.block.time.plus_seconds(100);
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&alice, &[]),
+            ExecuteMsg::Claim { epoch: 0 },
+        )
+        .unwrap();
+
+        assert!(matches!(
+            sweep(deps.as_mut(), &env, &admin),
+            Err(Error::ClaimPeriodNotEnded { epoch: 0, .. })
+        ));
+
+        env.block.time = env.block.time.plus_seconds(1000);
+
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&bob, &[]),
+                ExecuteMsg::Claim { epoch: 0 },
+            ),
+            Err(Error::ClaimPeriodEnded { epoch: 0, .. })
+        ));
+
+        assert!(matches!(
+            sweep(deps.as_mut(), &env, &bob),
+            Err(Error::OnlyAdmin)
+        ));
+
+        // the unclaimed share of bob and the rounding dust of the share of alice
+        let res = sweep(deps.as_mut(), &env, &admin).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            BankMsg::Send {
+                to_address: admin.to_string(),
+                amount: coins(667, "ulazy"),
+            }
+            .into()
+        );
+
+        let epoch = from_json::<EpochResponse>(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::Epoch { epoch: Some(0) },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(epoch.pool, Uint128::new(333));
+        assert_eq!(epoch.claimed, Uint128::new(333));
+
+        assert!(matches!(
+            sweep(deps.as_mut(), &env, &admin),
+            Err(Error::EmptyPool(0))
+        ));
+    }
+}
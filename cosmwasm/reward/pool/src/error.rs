@@ -0,0 +1,42 @@
+use cosmwasm_std::{Addr, StdError, Timestamp};
+use lazydev_reward_interface::{contract::OnlyLazydev, msg::RewardScope, state::Repo};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    OnlyLazydev(#[from] OnlyLazydev),
+    #[error("only admin")]
+    OnlyAdmin,
+    #[error("repo {}/{} not allowed", .0.org, .0.repo)]
+    InvalidRepo(Repo),
+    #[error("{0:?} not allowed")]
+    InvalidScope(RewardScope),
+    #[error("invalid config")]
+    InvalidConfig(StdError),
+    #[error("the pool must be funded with a non-zero amount of {0} only")]
+    InvalidFunds(String),
+    #[error("epoch {epoch} is closed, the current epoch is {current_epoch}")]
+    EpochClosed { epoch: u64, current_epoch: u64 },
+    #[error("epoch {epoch} is not closed yet, the current epoch is {current_epoch}")]
+    EpochNotClosed { epoch: u64, current_epoch: u64 },
+    #[error("{recipient} has no points in epoch {epoch}")]
+    NoContribution { recipient: Addr, epoch: u64 },
+    #[error("epoch {0} has points, its pool can only be claimed")]
+    EpochHasPoints(u64),
+    #[error("the pool of epoch {0} is empty")]
+    EmptyPool(u64),
+    #[error("{recipient} has already claimed their share of epoch {epoch}")]
+    AlreadyClaimed { recipient: Addr, epoch: u64 },
+    #[error("the claim period of epoch {epoch} ended at {claim_deadline}")]
+    ClaimPeriodEnded {
+        epoch: u64,
+        claim_deadline: Timestamp,
+    },
+    #[error("the claim period of epoch {epoch} ends at {claim_deadline}")]
+    ClaimPeriodNotEnded {
+        epoch: u64,
+        claim_deadline: Timestamp,
+    },
+    #[error("pr {pr_id} of {}/{} was not rewarded by this contract", .repo.org, .repo.repo)]
+    NotRewarded { repo: Repo, pr_id: u64 },
+}
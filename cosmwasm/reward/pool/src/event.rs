@@ -0,0 +1,52 @@
+use cosmwasm_std::{Addr, Coin, Uint128};
+use lazydev_reward_interface::{event::attribute, typed_events};
+
+pub const FUND: &str = "pool_fund";
+pub const CLAIM: &str = "pool_claim";
+pub const REVOKE: &str = "pool_revoke";
+pub const ROLLOVER: &str = "pool_rollover";
+pub const SWEEP: &str = "pool_sweep";
+
+pub const EPOCH: &str = "epoch";
+pub const TO_EPOCH: &str = "to_epoch";
+pub const FUNDER: &str = "funder";
+pub const AMOUNT: &str = "amount";
+pub const POINTS: &str = "points";
+
+typed_events! {
+    /// The pool of an epoch was funded.
+    pub struct FundEvent = FUND {
+        pub epoch: u64 = EPOCH,
+        pub funder: Addr = FUNDER,
+        pub amount: Coin = AMOUNT,
+    }
+
+    /// A contributor claimed their share of the pool of a closed epoch.
+    pub struct ClaimEvent = CLAIM {
+        pub epoch: u64 = EPOCH,
+        pub recipient: Addr = attribute::RECIPIENT,
+        pub points: Uint128 = POINTS,
+        pub amount: Coin = AMOUNT,
+    }
+
+    /// The pool of a closed epoch without any points was moved to the pool of `to_epoch`.
+    pub struct RolloverEvent = ROLLOVER {
+        pub epoch: u64 = EPOCH,
+        pub to_epoch: u64 = TO_EPOCH,
+        pub amount: Coin = AMOUNT,
+    }
+
+    /// The points of a reverted PR were removed from its recipient.
+    pub struct RevokeEvent = REVOKE {
+        pub epoch: u64 = EPOCH,
+        pub recipient: Addr = attribute::RECIPIENT,
+        pub points: Uint128 = POINTS,
+    }
+
+    /// The unclaimed part of the pool of an epoch was swept after its claim period.
+    pub struct SweepEvent = SWEEP {
+        pub epoch: u64 = EPOCH,
+        pub recipient: Addr = attribute::RECIPIENT,
+        pub amount: Coin = AMOUNT,
+    }
+}
@@ -0,0 +1,121 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Empty, Timestamp, Uint128};
+use lazydev_reward_interface::{
+    msg::{QueryRewardsResponse, RevokeMsg, RewardMsg, ValidateRewardConfigMsg},
+    state::Repo,
+};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub config: Config,
+    pub lazydev_address: Addr,
+}
+
+#[cw_serde]
+pub struct Config {
+    /// The native denom that the pools are funded and paid out in.
+    pub denom: String,
+    /// The duration of an epoch in seconds. Epoch 0 starts when the contract is instantiated.
+    pub epoch_duration: u64,
+    /// The time in seconds after an epoch closes during which its pool can be claimed. Afterwards,
+    /// the unclaimed part of the pool can be swept by the admin.
+    pub claim_period: u64,
+
+    /// The repos this contract will provide rewards for.
+    pub valid_repos: Vec<Repo>,
+    /// The github orgs this contract will provide rewards for. Repos on other forges must be listed
+    /// in `valid_repos`.
+    pub valid_orgs: Vec<String>,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Accrue the points in `reward_config` to the recipient for the current epoch.
+    Reward(RewardMsg),
+    /// Add the attached funds to the pool of `epoch`, or of the current epoch if not set. Closed
+    /// epochs can not be funded.
+    Fund { epoch: Option<u64> },
+    /// Claim the share of the sender in the pool of a closed epoch, `points / total_points *
+    /// pool`.
+    Claim { epoch: u64 },
+    /// Move the pool of a closed epoch without any points, which can not be claimed by anyone, to
+    /// the pool of the current epoch. Callable by anyone.
+    Rollover { epoch: u64 },
+    /// Remove the points of a reverted PR from its recipient. This fails if the recipient has
+    /// already claimed their share of the epoch. The total points of closed epochs are kept, such
+    /// that the shares of the other contributors do not change, and the revoked share is left in
+    /// the pool to be swept.
+    Revoke(RevokeMsg),
+    /// Send the unclaimed part of the pool of an epoch whose claim period has ended to `recipient`,
+    /// including rounding dust and revoked shares. The pool of the epoch is reduced to the claimed
+    /// amount, and its remaining shares can not be claimed anymore. Only callable by the admin.
+    Sweep { epoch: u64, recipient: Addr },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(QueryRewardsResponse)]
+    Rewards(RewardMsg),
+    #[returns(Empty)]
+    ValidateRewardConfig(ValidateRewardConfigMsg),
+    #[returns(Config)]
+    Config {},
+    /// Returns the address that can sweep the unclaimed pools.
+    #[returns(Addr)]
+    Admin {},
+    /// Returns the pool and total points of `epoch`, or of the current epoch if not set.
+    #[returns(EpochResponse)]
+    Epoch { epoch: Option<u64> },
+    /// Returns the points and share of the pool of every contributor in `epoch`, or in the current
+    /// epoch if not set, ordered by recipient. The share of an epoch that is not closed yet is
+    /// based on its current pool and points.
+    #[returns(Vec<Standing>)]
+    Standings {
+        epoch: Option<u64>,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+    /// Returns the points and share of `recipient` in every epoch that they have contributed to,
+    /// ordered by epoch.
+    #[returns(Vec<Payout>)]
+    Payouts {
+        recipient: Addr,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
+
+#[cw_serde]
+pub struct EpochResponse {
+    pub epoch: u64,
+    pub start: Timestamp,
+    pub end: Timestamp,
+    /// Whether the epoch has ended, after which the pool can be claimed.
+    pub closed: bool,
+    /// The end of the claim period of the epoch, after which the unclaimed part of the pool can be
+    /// swept.
+    pub claim_deadline: Timestamp,
+    pub pool: Uint128,
+    pub total_points: Uint128,
+    /// The part of the pool that has been claimed.
+    pub claimed: Uint128,
+}
+
+#[cw_serde]
+pub struct Standing {
+    pub recipient: Addr,
+    pub points: Uint128,
+    pub share: Uint128,
+}
+
+#[cw_serde]
+pub struct Payout {
+    pub epoch: u64,
+    pub points: Uint128,
+    pub share: Uint128,
+    pub claimed: bool,
+}
@@ -0,0 +1,60 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Timestamp, Uint128};
+use cw_storage_plus::{Item, Map};
+use lazydev_reward_interface::contract::Rewards;
+
+use crate::msg::Config;
+
+/// The address of the lazydev contract, which is the only address that can call the `reward`
+/// entrypoint, and the already claimed rewards.
+pub const REWARDS: Rewards = Rewards::new("lazydev", "claimed_rewards");
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// The address that instantiated this contract, which is the only address that can sweep the
+/// unclaimed pools.
+pub const ADMIN: Item<Addr> = Item::new("admin");
+
+/// The start of epoch 0.
+pub const GENESIS: Item<Timestamp> = Item::new("genesis");
+
+/// The pool and total points of each epoch, keyed by epoch.
+pub const EPOCHS: Map<u64, Epoch> = Map::new("epochs");
+
+/// The points and payout of each contributor per epoch, keyed by `(recipient, epoch)`.
+pub const CONTRIBUTIONS: Map<(&Addr, u64), Contribution> = Map::new("contributions");
+
+/// The points of each contributor per epoch, keyed by `(epoch, recipient)`. This is an index of
+/// [`CONTRIBUTIONS`] for the standings of an epoch.
+pub const STANDINGS: Map<(u64, &Addr), Uint128> = Map::new("standings");
+
+#[cw_serde]
+#[derive(Default)]
+pub struct Epoch {
+    /// The amount of `denom` funded for this epoch.
+    pub pool: Uint128,
+    pub total_points: Uint128,
+    /// The part of `pool` that has been claimed.
+    #[serde(default)]
+    pub claimed: Uint128,
+}
+
+impl Epoch {
+    /// The share of the pool owed for `points`, rounded down.
+    #[must_use]
+    pub fn payout(&self, points: Uint128) -> Uint128 {
+        if self.total_points.is_zero() {
+            Uint128::zero()
+        } else {
+            self.pool.multiply_ratio(points, self.total_points)
+        }
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct Contribution {
+    pub points: Uint128,
+    /// The amount paid out to the contributor, if they have claimed their share.
+    pub claimed: Option<Uint128>,
+}
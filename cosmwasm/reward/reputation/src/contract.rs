@@ -0,0 +1,962 @@
+use cosmwasm_std::{
+    ensure, entry_point, to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env,
+    MessageInfo, Response, StdError, StdResult, Storage, Uint128,
+};
+use cw_storage_plus::Bound;
+use lazydev_reward_interface::{
+    contract::STORAGE_ACCESS_INFALLIBLE_MSG,
+    msg::{RewardScope, ValidateRewardConfigMsg},
+    reward::PrReward,
+    state::Repo,
+};
+
+use crate::{
+    error::Error,
+    msg::{
+        Config, ExecuteMsg, InstantiateMsg, LeaderboardResponse, MigrateMsg, QueryMsg,
+        ReputationConfig, ScoreBreakdown,
+    },
+    state::{
+        LeaderboardEntry, Score, CONFIG, CREDIT_PERIODS, GENESIS, GLOBAL_LEADERBOARD,
+        GLOBAL_SCORES, REPO_LEADERBOARDS, REPO_SCORES, REWARDS, SCORES,
+    },
+};
+
+const DEFAULT_LIMIT: u32 = 30;
+
+const MAX_LIMIT: u32 = 100;
+
+#[entry_point]
+#[allow(clippy::needless_pass_by_value)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    if let Some(decay) = &msg.config.decay {
+        if decay.period == 0 || decay.rate > Decimal::one() {
+            return Err(StdError::generic_err(
+                "decay period must be non-zero and rate must be at most 1",
+            ));
+        }
+    }
+
+    if msg.config.leaderboard_size > MAX_LIMIT {
+        return Err(StdError::generic_err(format!(
+            "leaderboard_size must be at most {MAX_LIMIT}"
+        )));
+    }
+
+    REWARDS.init(deps.storage, &msg.lazydev_address);
+
+    CONFIG
+        .save(deps.storage, &msg.config)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    GENESIS
+        .save(deps.storage, &env.block.time)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    GLOBAL_LEADERBOARD
+        .save(deps.storage, &vec![])
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    Ok(Response::default())
+}
+
+/// Whether this contract rewards PRs in `scope`.
+fn is_allowed(config: &Config, scope: &RewardScope) -> bool {
+    // org allowlists only apply to github, repos on other forges must be allowed individually
+    match scope {
+        RewardScope::Repo(repo) => {
+            (repo.forge.is_github() && config.valid_orgs.contains(&repo.org))
+                || config.valid_repos.contains(repo)
+        }
+        RewardScope::Org { forge, org } => forge.is_github() && config.valid_orgs.contains(org),
+    }
+}
+
+/// The current decay period. This is always 0 if scores don't decay.
+fn current_period(storage: &dyn Storage, env: &Env, config: &Config) -> u64 {
+    config.decay.as_ref().map_or(0, |decay| {
+        let genesis = GENESIS.load(storage).expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+        (env.block.time.seconds() - genesis.seconds()) / decay.period
+    })
+}
+
+fn pagination_limit(limit: Option<u32>) -> usize {
+    limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize
+}
+
+#[entry_point]
+#[allow(clippy::needless_pass_by_value)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let period = current_period(deps.storage, &env, &config);
+    let decay = config.decay.as_ref();
+
+    match msg {
+        QueryMsg::Rewards(msg) => Ok(to_json_binary(&REWARDS.query(
+            deps.storage,
+            &msg,
+            || {
+                let ReputationConfig { category, points } =
+                    ReputationConfig::parse(&msg.reward_config)?;
+
+                Ok(PrReward::Reputation { category, points })
+            },
+        )?)?),
+        QueryMsg::ValidateRewardConfig(ValidateRewardConfigMsg {
+            scope,
+            reward_config,
+        }) => {
+            ReputationConfig::parse(&reward_config)
+                .map_err(|err| StdError::generic_err(Error::InvalidConfig(err).to_string()))?;
+
+            ensure!(
+                is_allowed(&config, &scope),
+                StdError::generic_err(Error::InvalidScope(scope).to_string())
+            );
+
+            Ok(to_json_binary(&Empty {})?)
+        }
+        QueryMsg::Config {} => Ok(to_json_binary(&config)?),
+        QueryMsg::Score { address, repo } => {
+            let score = match repo {
+                Some(repo) => REPO_SCORES.may_load(deps.storage, (&address, repo))?,
+                None => GLOBAL_SCORES.may_load(deps.storage, &address)?,
+            };
+
+            Ok(to_json_binary(&score.map_or(Uint128::zero(), |score| {
+                score.points_at(decay, period)
+            }))?)
+        }
+        QueryMsg::ScoreBreakdown {
+            address,
+            start_after,
+            limit,
+        } => Ok(to_json_binary(
+            &SCORES
+                .sub_prefix(&address)
+                .range(
+                    deps.storage,
+                    start_after
+                        .as_ref()
+                        .map(|key| Bound::exclusive((key.repo.clone(), key.category.as_str()))),
+                    None,
+                    cosmwasm_std::Order::Ascending,
+                )
+                .take(pagination_limit(limit))
+                .map(|res| {
+                    res.map(|((repo, category), score)| ScoreBreakdown {
+                        repo,
+                        category,
+                        points: score.points_at(decay, period),
+                    })
+                })
+                .collect::<StdResult<Vec<_>>>()?,
+        )?),
+        QueryMsg::Leaderboard { repo, limit } => {
+            let leaderboard = match repo {
+                Some(repo) => REPO_LEADERBOARDS
+                    .may_load(deps.storage, repo)?
+                    .unwrap_or_default(),
+                None => GLOBAL_LEADERBOARD.load(deps.storage)?,
+            };
+
+            Ok(to_json_binary(
+                &leaderboard
+                    .into_iter()
+                    .take(pagination_limit(limit))
+                    .map(|entry| LeaderboardResponse {
+                        points: entry.score.points_at(decay, period),
+                        address: entry.address,
+                    })
+                    .collect::<Vec<_>>(),
+            )?)
+        }
+    }
+}
+
+#[entry_point]
+#[allow(clippy::needless_pass_by_value)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, Error> {
+    match msg {
+        ExecuteMsg::Reward(msg) => {
+            REWARDS.ensure_lazydev(deps.storage, &info.sender)?;
+
+            let config = CONFIG
+                .load(deps.storage)
+                .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+            let ReputationConfig { category, points } =
+                ReputationConfig::parse(&msg.reward_config).map_err(Error::InvalidConfig)?;
+
+            ensure!(
+                is_allowed(&config, &RewardScope::Repo(msg.repo.clone())),
+                Error::InvalidRepo(msg.repo)
+            );
+
+            let period = current_period(deps.storage, &env, &config);
+            let decay = config.decay.as_ref();
+
+            update_scores(
+                deps.storage,
+                &config,
+                period,
+                &msg.recipient_address,
+                &msg.repo,
+                &category,
+                |score| score.credit(decay, period, points),
+            );
+
+            CREDIT_PERIODS
+                .save(deps.storage, (msg.pr_id, msg.repo.clone()), &period)
+                .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+            // reputation is not divisible, so the protocol fee is ignored
+            let reward_event = REWARDS.claim(
+                deps.storage,
+                &msg,
+                &PrReward::Reputation { category, points },
+            );
+
+            Ok(Response::new().add_event(reward_event))
+        }
+        ExecuteMsg::Revoke(msg) => {
+            REWARDS.ensure_lazydev(deps.storage, &info.sender)?;
+
+            let repo = msg.reward.repo;
+            let pr_id = msg.reward.pr_id;
+
+            let (Some(PrReward::Reputation { category, points }), Some(credit_period)) = (
+                REWARDS
+                    .claimed(deps.storage, &repo, pr_id)
+                    .expect(STORAGE_ACCESS_INFALLIBLE_MSG),
+                CREDIT_PERIODS
+                    .may_load(deps.storage, (pr_id, repo.clone()))
+                    .expect(STORAGE_ACCESS_INFALLIBLE_MSG),
+            ) else {
+                return Err(Error::NotRewarded { repo, pr_id });
+            };
+
+            let config = CONFIG
+                .load(deps.storage)
+                .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+            let period = current_period(deps.storage, &env, &config);
+            let decay = config.decay.as_ref();
+
+            // the points have decayed along with the scores they were credited to
+            let points = decay.map_or(points, |decay| decay.apply(points, period - credit_period));
+
+            update_scores(
+                deps.storage,
+                &config,
+                period,
+                &msg.reward.recipient_address,
+                &repo,
+                &category,
+                |score| score.debit(decay, period, points),
+            );
+
+            // the claimed reward is kept, such that the PR can not be rewarded again
+            CREDIT_PERIODS.remove(deps.storage, (pr_id, repo));
+
+            Ok(Response::default())
+        }
+    }
+}
+
+/// Update the score of `address` in `repo` and `category`, in `repo` and across all repos with
+/// `update`, and update the leaderboards.
+fn update_scores(
+    storage: &mut dyn Storage,
+    config: &Config,
+    period: u64,
+    address: &Addr,
+    repo: &Repo,
+    category: &str,
+    update: impl Fn(Score) -> Score,
+) {
+    let update_score = |score: Option<Score>| -> Score { update(score.unwrap_or_default()) };
+
+    let score = update_score(
+        SCORES
+            .may_load(storage, (address, repo.clone(), category))
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG),
+    );
+    SCORES
+        .save(storage, (address, repo.clone(), category), &score)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    let repo_score = update_score(
+        REPO_SCORES
+            .may_load(storage, (address, repo.clone()))
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG),
+    );
+    REPO_SCORES
+        .save(storage, (address, repo.clone()), &repo_score)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    let global_score = update_score(
+        GLOBAL_SCORES
+            .may_load(storage, address)
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG),
+    );
+    GLOBAL_SCORES
+        .save(storage, address, &global_score)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    REPO_LEADERBOARDS
+        .update(storage, repo.clone(), |leaderboard| {
+            StdResult::Ok(update_leaderboard(
+                leaderboard.unwrap_or_default(),
+                config,
+                period,
+                address,
+                repo_score,
+            ))
+        })
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    GLOBAL_LEADERBOARD
+        .update(storage, |leaderboard| {
+            StdResult::Ok(update_leaderboard(
+                leaderboard,
+                config,
+                period,
+                address,
+                global_score,
+            ))
+        })
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+}
+
+/// Insert the new `score` of `address` into `leaderboard`, keeping the top
+/// [`Config::leaderboard_size`] entries. Addresses without points are removed.
+///
+/// Scores only change when they are credited or revoked, and otherwise decay at the same rate, so
+/// an address can only enter a leaderboard when it is credited. An address that was pushed out of
+/// a leaderboard is not restored when the score of another address is revoked, until it is credited
+/// again.
+fn update_leaderboard(
+    mut leaderboard: Vec<LeaderboardEntry>,
+    config: &Config,
+    period: u64,
+    address: &Addr,
+    score: Score,
+) -> Vec<LeaderboardEntry> {
+    // scores that fully decayed are dropped as well
+    leaderboard.retain(|entry| {
+        entry.address != address
+            && !entry
+                .score
+                .points_at(config.decay.as_ref(), period)
+                .is_zero()
+    });
+    if !score.points_at(config.decay.as_ref(), period).is_zero() {
+        leaderboard.push(LeaderboardEntry {
+            address: address.clone(),
+            score,
+        });
+    }
+
+    leaderboard.sort_by_cached_key(|entry| {
+        (
+            std::cmp::Reverse(entry.score.points_at(config.decay.as_ref(), period)),
+            entry.address.clone(),
+        )
+    });
+    leaderboard.truncate(config.leaderboard_size as usize);
+
+    leaderboard
+}
+
+#[entry_point]
+pub fn migrate(_: DepsMut, _: Env, _: MigrateMsg) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        from_json,
+        testing::{message_info, mock_dependencies, mock_env},
+    };
+    use lazydev_reward_interface::{
+        forge::Forge,
+        msg::{RevokeMsg, RewardMsg},
+    };
+
+    use super::*;
+    use crate::msg::{Decay, ScoreKey};
+
+    #[test]
+    fn leaderboard() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let lazydev = deps.api.addr_make("lazydev");
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&lazydev, &[]),
+            InstantiateMsg {
+                config: Config {
+                    decay: Some(Decay {
+                        period: 100,
+                        rate: Decimal::percent(50),
+                    }),
+                    leaderboard_size: 10,
+                    valid_repos: vec![],
+                    valid_orgs: vec!["unionlabs".to_owned()],
+                },
+                lazydev_address: lazydev.clone(),
+            },
+        )
+        .unwrap();
+
+        let mut reward = |env: &Env, pr_id, recipient: &Addr, reward_config: &str| {
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&lazydev, &[]),
+                ExecuteMsg::Reward(RewardMsg {
+                    repo: Repo::from(("unionlabs", "union")),
+                    pr_id,
+                    user_id: 1,
+                    recipient_address: recipient.clone(),
+                    reward_config: reward_config.to_owned(),
+                    protocol_fee: None,
+                    ordinal: 1,
+                }),
+            )
+            .unwrap();
+        };
+
+        reward(&env, 1, &alice, r#"{"category":"bug","points":"100"}"#);
+
+        env.block.time = env.block.time.plus_seconds(100);
+
+        // alice decayed to 50 points
+        reward(&env, 2, &bob, r#"{"category":"docs","points":"60"}"#);
+        reward(&env, 3, &alice, r#"{"category":"docs","points":"20"}"#);
+
+        let leaderboard = from_json::<Vec<LeaderboardResponse>>(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::Leaderboard {
+                    repo: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            leaderboard,
+            [
+                LeaderboardResponse {
+                    address: alice.clone(),
+                    points: Uint128::new(70),
+                },
+                LeaderboardResponse {
+                    address: bob,
+                    points: Uint128::new(60),
+                },
+            ]
+        );
+
+        env.block.time = env.block.time.plus_seconds(100);
+
+        let breakdown = from_json::<Vec<ScoreBreakdown>>(
+            query(
+                deps.as_ref(),
+                env,
+                QueryMsg::ScoreBreakdown {
+                    address: alice,
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            breakdown
+                .iter()
+                .map(|score| (score.category.as_str(), score.points.u128()))
+                .collect::<Vec<_>>(),
+            [("bug", 25), ("docs", 10)]
+        );
+    }
+
+    fn instantiate_reputation(deps: DepsMut, env: &Env, lazydev: &Addr, config: Config) {
+        instantiate(
+            deps,
+            env.clone(),
+            message_info(lazydev, &[]),
+            InstantiateMsg {
+                config,
+                lazydev_address: lazydev.clone(),
+            },
+        )
+        .unwrap();
+    }
+
+    fn reward_msg(repo: &str, pr_id: u64, recipient: &Addr, reward_config: &str) -> RewardMsg {
+        RewardMsg {
+            repo: Repo::from(("unionlabs", repo)),
+            pr_id,
+            user_id: 1,
+            recipient_address: recipient.clone(),
+            reward_config: reward_config.to_owned(),
+            protocol_fee: None,
+            ordinal: 1,
+        }
+    }
+
+    fn query_leaderboard(deps: Deps, env: &Env, repo: Option<Repo>) -> Vec<LeaderboardResponse> {
+        from_json(
+            query(
+                deps,
+                env.clone(),
+                QueryMsg::Leaderboard { repo, limit: None },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn revoked_points() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let lazydev = deps.api.addr_make("lazydev");
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+
+        instantiate_reputation(
+            deps.as_mut(),
+            &env,
+            &lazydev,
+            Config {
+                decay: Some(Decay {
+                    period: 100,
+                    rate: Decimal::percent(50),
+                }),
+                leaderboard_size: 10,
+                valid_repos: vec![],
+                valid_orgs: vec!["unionlabs".to_owned()],
+            },
+        );
+
+        for (pr_id, recipient, reward_config) in [
+            (1, &alice, r#"{"category":"bug","points":"100"}"#),
+            (2, &alice, r#"{"category":"docs","points":"40"}"#),
+            (3, &bob, r#"{"category":"docs","points":"60"}"#),
+        ] {
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&lazydev, &[]),
+                ExecuteMsg::Reward(reward_msg("union", pr_id, recipient, reward_config)),
+            )
+            .unwrap();
+        }
+
+        let revoke = |deps: DepsMut, env: &Env, pr_id, recipient: &Addr| {
+            execute(
+                deps,
+                env.clone(),
+                message_info(&lazydev, &[]),
+                ExecuteMsg::Revoke(RevokeMsg {
+                    reward: reward_msg("union", pr_id, recipient, ""),
+                    revert_pr_id: None,
+                }),
+            )
+        };
+
+        assert!(matches!(
+            revoke(deps.as_mut(), &env, 4, &alice),
+            Err(Error::NotRewarded { pr_id: 4, .. })
+        ));
+
+        env.block.time = env.block.time.plus_seconds(100);
+
+        // the 100 points of the bug decayed to 50 along with the score of alice, which falls
+        // behind bob
+        revoke(deps.as_mut(), &env, 1, &alice).unwrap();
+
+        assert_eq!(
+            query_leaderboard(deps.as_ref(), &env, None),
+            [
+                LeaderboardResponse {
+                    address: bob.clone(),
+                    points: Uint128::new(30),
+                },
+                LeaderboardResponse {
+                    address: alice.clone(),
+                    points: Uint128::new(20),
+                },
+            ]
+        );
+        assert_eq!(
+            from_json::<Uint128>(
+                query(
+                    deps.as_ref(),
+                    env.clone(),
+                    QueryMsg::Score {
+                        address: alice.clone(),
+                        repo: Some(Repo::from(("unionlabs", "union"))),
+                    },
+                )
+                .unwrap()
+            )
+            .unwrap(),
+            Uint128::new(20)
+        );
+
+        // a PR can only be revoked once
+        assert!(matches!(
+            revoke(deps.as_mut(), &env, 1, &alice),
+            Err(Error::NotRewarded { pr_id: 1, .. })
+        ));
+
+        // addresses without points are removed from the leaderboards
+        revoke(deps.as_mut(), &env, 2, &alice).unwrap();
+
+        for repo in [None, Some(Repo::from(("unionlabs", "union")))] {
+            assert_eq!(
+                query_leaderboard(deps.as_ref(), &env, repo),
+                [LeaderboardResponse {
+                    address: bob.clone(),
+                    points: Uint128::new(30),
+                }]
+            );
+        }
+    }
+
+    fn config(decay: Option<Decay>, leaderboard_size: u32) -> Config {
+        Config {
+            decay,
+            leaderboard_size,
+            valid_repos: vec![Repo {
+                forge: Forge::Gitlab {
+                    host: "gitlab.com".to_owned(),
+                },
+                org: "unionlabs".to_owned(),
+                repo: "union".to_owned(),
+            }],
+            valid_orgs: vec!["unionlabs".to_owned()],
+        }
+    }
+
+    #[test]
+    fn scopes() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let lazydev = deps.api.addr_make("lazydev");
+        let alice = deps.api.addr_make("alice");
+
+        instantiate_reputation(deps.as_mut(), &env, &lazydev, config(None, 10));
+
+        let gitlab = Forge::Gitlab {
+            host: "gitlab.com".to_owned(),
+        };
+        let validate = |scope: RewardScope, reward_config: &str| {
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::ValidateRewardConfig(ValidateRewardConfigMsg {
+                    scope,
+                    reward_config: reward_config.to_owned(),
+                }),
+            )
+        };
+        let reward_config = r#"{"category":"bug","points":"10"}"#;
+
+        for scope in [
+            RewardScope::Repo(Repo::from(("unionlabs", "union"))),
+            RewardScope::Repo(Repo {
+                forge: gitlab.clone(),
+                org: "unionlabs".to_owned(),
+                repo: "union".to_owned(),
+            }),
+            RewardScope::Org {
+                forge: Forge::Github,
+                org: "unionlabs".to_owned(),
+            },
+        ] {
+            validate(scope, reward_config).unwrap();
+        }
+
+        // org allowlists only apply to github
+        for scope in [
+            RewardScope::Repo(Repo::from(("other", "union"))),
+            RewardScope::Repo(Repo {
+                forge: gitlab.clone(),
+                org: "unionlabs".to_owned(),
+                repo: "voyager".to_owned(),
+            }),
+            RewardScope::Org {
+                forge: gitlab,
+                org: "unionlabs".to_owned(),
+            },
+        ] {
+            assert!(validate(scope, reward_config).is_err());
+        }
+
+        assert!(validate(
+            RewardScope::Repo(Repo::from(("unionlabs", "union"))),
+            r#"{"category":"bug"}"#
+        )
+        .is_err());
+
+        let mut msg = reward_msg("union", 1, &alice, reward_config);
+        msg.repo.org = "other".to_owned();
+
+        assert!(matches!(
+            execute(
+                deps.as_mut(),
+                env,
+                message_info(&lazydev, &[]),
+                ExecuteMsg::Reward(msg),
+            ),
+            Err(Error::InvalidRepo(_))
+        ));
+    }
+
+    #[test]
+    fn repo_leaderboards() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let lazydev = deps.api.addr_make("lazydev");
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        let carol = deps.api.addr_make("carol");
+
+        instantiate_reputation(deps.as_mut(), &env, &lazydev, config(None, 2));
+
+        for (repo, pr_id, recipient, points) in [
+            ("union", 1, &alice, "10"),
+            ("union", 2, &bob, "20"),
+            ("union", 3, &carol, "5"),
+            ("voyager", 1, &carol, "30"),
+        ] {
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&lazydev, &[]),
+                ExecuteMsg::Reward(reward_msg(
+                    repo,
+                    pr_id,
+                    recipient,
+                    &format!(r#"{{"category":"bug","points":"{points}"}}"#),
+                )),
+            )
+            .unwrap();
+        }
+
+        let entry = |address: &Addr, points| LeaderboardResponse {
+            address: address.clone(),
+            points: Uint128::new(points),
+        };
+
+        // carol is truncated from the leaderboard of union, and alice from the global leaderboard
+        assert_eq!(
+            query_leaderboard(
+                deps.as_ref(),
+                &env,
+                Some(Repo::from(("unionlabs", "union")))
+            ),
+            [entry(&bob, 20), entry(&alice, 10)]
+        );
+        assert_eq!(
+            query_leaderboard(
+                deps.as_ref(),
+                &env,
+                Some(Repo::from(("unionlabs", "voyager")))
+            ),
+            [entry(&carol, 30)]
+        );
+        assert_eq!(
+            query_leaderboard(deps.as_ref(), &env, None),
+            [entry(&carol, 35), entry(&bob, 20)]
+        );
+        assert_eq!(
+            query_leaderboard(
+                deps.as_ref(),
+                &env,
+                Some(Repo::from(("unionlabs", "other")))
+            ),
+            []
+        );
+
+        let top = from_json::<Vec<LeaderboardResponse>>(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::Leaderboard {
+                    repo: None,
+                    limit: Some(1),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(top, [entry(&carol, 35)]);
+    }
+
+    #[test]
+    fn score_breakdown_pagination() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let lazydev = deps.api.addr_make("lazydev");
+        let alice = deps.api.addr_make("alice");
+
+        instantiate_reputation(deps.as_mut(), &env, &lazydev, config(None, 10));
+
+        for (repo, pr_id, category) in [
+            ("voyager", 1, "bug"),
+            ("union", 1, "docs"),
+            ("union", 2, "bug"),
+        ] {
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&lazydev, &[]),
+                ExecuteMsg::Reward(reward_msg(
+                    repo,
+                    pr_id,
+                    &alice,
+                    &format!(r#"{{"category":"{category}","points":"{pr_id}"}}"#),
+                )),
+            )
+            .unwrap();
+        }
+
+        let breakdown = |start_after: Option<ScoreKey>| {
+            from_json::<Vec<ScoreBreakdown>>(
+                query(
+                    deps.as_ref(),
+                    env.clone(),
+                    QueryMsg::ScoreBreakdown {
+                        address: alice.clone(),
+                        start_after,
+                        limit: Some(2),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+            .into_iter()
+            .map(|score| (score.repo.repo, score.category, score.points.u128()))
+            .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            breakdown(None),
+            [
+                ("union".to_owned(), "bug".to_owned(), 2),
+                ("union".to_owned(), "docs".to_owned(), 1),
+            ]
+        );
+        assert_eq!(
+            breakdown(Some(ScoreKey {
+                repo: Repo::from(("unionlabs", "union")),
+                category: "docs".to_owned(),
+            })),
+            [("voyager".to_owned(), "bug".to_owned(), 1)]
+        );
+    }
+
+    #[test]
+    fn full_decay() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let lazydev = deps.api.addr_make("lazydev");
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+
+        instantiate_reputation(
+            deps.as_mut(),
+            &env,
+            &lazydev,
+            config(
+                Some(Decay {
+                    period: 100,
+                    rate: Decimal::one(),
+                }),
+                10,
+            ),
+        );
+
+        let reward = |deps: DepsMut, env: &Env, pr_id, recipient: &Addr| {
+            execute(
+                deps,
+                env.clone(),
+                message_info(&lazydev, &[]),
+                ExecuteMsg::Reward(reward_msg(
+                    "union",
+                    pr_id,
+                    recipient,
+                    r#"{"category":"bug","points":"100"}"#,
+                )),
+            )
+            .unwrap();
+        };
+
+        reward(deps.as_mut(), &env, 1, &alice);
+
+        let score = |deps: Deps, env: &Env| {
+            from_json::<Uint128>(
+                query(
+                    deps,
+                    env.clone(),
+                    QueryMsg::Score {
+                        address: alice.clone(),
+                        repo: None,
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+
+        // scores are kept in full until the end of the period
+        env.block.time = env.block.time.plus_seconds(99);
+        assert_eq!(score(deps.as_ref(), &env), Uint128::new(100));
+
+        env.block.time = env.block.time.plus_seconds(1);
+        assert_eq!(score(deps.as_ref(), &env), Uint128::zero());
+
+        // the fully decayed score of alice is dropped from the leaderboard
+        reward(deps.as_mut(), &env, 2, &bob);
+
+        assert_eq!(
+            query_leaderboard(deps.as_ref(), &env, None),
+            [LeaderboardResponse {
+                address: bob,
+                points: Uint128::new(100),
+            }]
+        );
+    }
+}
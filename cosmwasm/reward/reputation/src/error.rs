@@ -0,0 +1,16 @@
+use cosmwasm_std::StdError;
+use lazydev_reward_interface::{contract::OnlyLazydev, msg::RewardScope, state::Repo};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    OnlyLazydev(#[from] OnlyLazydev),
+    #[error("repo {}/{} not allowed", .0.org, .0.repo)]
+    InvalidRepo(Repo),
+    #[error("{0:?} not allowed")]
+    InvalidScope(RewardScope),
+    #[error("invalid config")]
+    InvalidConfig(StdError),
+    #[error("pr {pr_id} of {}/{} was not rewarded by this contract", .repo.org, .repo.repo)]
+    NotRewarded { repo: Repo, pr_id: u64 },
+}
@@ -0,0 +1,123 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{from_json, Addr, Decimal, Empty, StdResult, Uint128};
+use lazydev_reward_interface::{
+    msg::{QueryRewardsResponse, RevokeMsg, RewardMsg, ValidateRewardConfigMsg},
+    state::Repo,
+};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub config: Config,
+    pub lazydev_address: Addr,
+}
+
+#[cw_serde]
+pub struct Config {
+    /// How scores decay over time. If [`None`], scores never decay.
+    pub decay: Option<Decay>,
+    /// The amount of addresses kept in the leaderboard of each repo and in the global leaderboard.
+    pub leaderboard_size: u32,
+
+    /// The repos this contract will provide rewards for.
+    pub valid_repos: Vec<Repo>,
+    /// The github orgs this contract will provide rewards for. Repos on other forges must be listed
+    /// in `valid_repos`.
+    pub valid_orgs: Vec<String>,
+}
+
+/// Scores lose `rate` of their points at the end of every `period` seconds since the contract was
+/// instantiated. For example, a `rate` of `0.5` halves every score once per `period`.
+#[cw_serde]
+pub struct Decay {
+    pub period: u64,
+    pub rate: Decimal,
+}
+
+impl Decay {
+    /// `points` after decaying for `periods`, rounded down.
+    #[must_use]
+    pub fn apply(&self, points: Uint128, periods: u64) -> Uint128 {
+        let factor = (Decimal::one() - self.rate)
+            .checked_pow(u32::try_from(periods).unwrap_or(u32::MAX))
+            .unwrap_or_default();
+
+        points.mul_floor(factor)
+    }
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Credit the points in `reward_config` to the recipient.
+    Reward(RewardMsg),
+    /// Remove the points of a reverted PR, as decayed since they were credited, from the scores of
+    /// the recipient.
+    Revoke(RevokeMsg),
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(QueryRewardsResponse)]
+    Rewards(RewardMsg),
+    #[returns(Empty)]
+    ValidateRewardConfig(ValidateRewardConfigMsg),
+    #[returns(Config)]
+    Config {},
+    /// Returns the current score of `address` in `repo`, or across all repos if not set.
+    #[returns(Uint128)]
+    Score { address: Addr, repo: Option<Repo> },
+    /// Returns the current score of `address` per repo and category, ordered by repo and then
+    /// category.
+    #[returns(Vec<ScoreBreakdown>)]
+    ScoreBreakdown {
+        address: Addr,
+        start_after: Option<ScoreKey>,
+        limit: Option<u32>,
+    },
+    /// Returns the top `limit` addresses in `repo`, or across all repos if not set, ordered by
+    /// their current score. At most [`Config::leaderboard_size`] addresses are returned.
+    #[returns(Vec<LeaderboardResponse>)]
+    Leaderboard {
+        repo: Option<Repo>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
+
+/// The `reward_config` of this contract, as json.
+///
+/// ```json
+/// { "category": "bug", "points": "10" }
+/// ```
+#[cw_serde]
+pub struct ReputationConfig {
+    pub category: String,
+    pub points: Uint128,
+}
+
+impl ReputationConfig {
+    pub fn parse(reward_config: &str) -> StdResult<Self> {
+        from_json(reward_config)
+    }
+}
+
+#[cw_serde]
+pub struct ScoreKey {
+    pub repo: Repo,
+    pub category: String,
+}
+
+#[cw_serde]
+pub struct ScoreBreakdown {
+    pub repo: Repo,
+    pub category: String,
+    pub points: Uint128,
+}
+
+#[cw_serde]
+pub struct LeaderboardResponse {
+    pub address: Addr,
+    pub points: Uint128,
+}
@@ -0,0 +1,77 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Timestamp, Uint128};
+use cw_storage_plus::{Item, Map};
+use lazydev_reward_interface::{contract::Rewards, state::Repo};
+
+use crate::msg::{Config, Decay};
+
+/// The address of the lazydev contract, which is the only address that can call the `reward`
+/// entrypoint, and the already claimed rewards.
+pub const REWARDS: Rewards = Rewards::new("lazydev", "claimed_rewards");
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// The start of decay period 0.
+pub const GENESIS: Item<Timestamp> = Item::new("genesis");
+
+/// The score of each address per repo and category, keyed by `(address, repo, category)`.
+pub const SCORES: Map<(&Addr, Repo, &str), Score> = Map::new("scores");
+
+/// The score of each address per repo, keyed by `(address, repo)`.
+pub const REPO_SCORES: Map<(&Addr, Repo), Score> = Map::new("repo_scores");
+
+/// The decay period that each rewarded PR was credited in, keyed by `(pr_id, repo)`, such that its
+/// decayed points can be revoked.
+pub const CREDIT_PERIODS: Map<(u64, Repo), u64> = Map::new("credit_periods");
+
+/// The score of each address across all repos.
+pub const GLOBAL_SCORES: Map<&Addr, Score> = Map::new("global_scores");
+
+/// The top [`Config::leaderboard_size`] addresses of each repo, ordered by score.
+pub const REPO_LEADERBOARDS: Map<Repo, Vec<LeaderboardEntry>> = Map::new("repo_leaderboards");
+
+/// The top [`Config::leaderboard_size`] addresses across all repos, ordered by score.
+pub const GLOBAL_LEADERBOARD: Item<Vec<LeaderboardEntry>> = Item::new("global_leaderboard");
+
+/// A score as of a decay period. Since every score decays at the same rate, the order of scores is
+/// preserved as they decay.
+#[cw_serde]
+#[derive(Default)]
+pub struct Score {
+    pub points: Uint128,
+    pub period: u64,
+}
+
+impl Score {
+    /// The points of this score in `period`.
+    #[must_use]
+    pub fn points_at(&self, decay: Option<&Decay>, period: u64) -> Uint128 {
+        decay.map_or(self.points, |decay| {
+            decay.apply(self.points, period.saturating_sub(self.period))
+        })
+    }
+
+    /// This score in `period` with `points` added.
+    #[must_use]
+    pub fn credit(&self, decay: Option<&Decay>, period: u64, points: Uint128) -> Self {
+        Self {
+            points: self.points_at(decay, period) + points,
+            period,
+        }
+    }
+
+    /// This score in `period` with `points` removed, saturating at zero.
+    #[must_use]
+    pub fn debit(&self, decay: Option<&Decay>, period: u64, points: Uint128) -> Self {
+        Self {
+            points: self.points_at(decay, period).saturating_sub(points),
+            period,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct LeaderboardEntry {
+    pub address: Addr,
+    pub score: Score,
+}
@@ -0,0 +1,208 @@
+use cosmwasm_std::{
+    entry_point, to_json_binary, wasm_execute, Addr, Binary, Deps, DepsMut, Empty, Env,
+    MessageInfo, Response, StdResult, SubMsg,
+};
+use lazydev_reward_interface::{
+    contract::SERIALIZATION_INFALLIBLE_MSG,
+    msg::{
+        QueryRewardsResponse, RevokeMsg, RewardExecuteMsg, RewardQueryMsg, RewardScope,
+        ValidateRewardConfigMsg,
+    },
+};
+
+use crate::{
+    error::Error,
+    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SplitConfig},
+    state::LAZYDEV,
+};
+
+#[entry_point]
+#[allow(clippy::needless_pass_by_value)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    LAZYDEV.init(deps.storage, &msg.lazydev_address);
+
+    Ok(Response::default())
+}
+
+#[entry_point]
+#[allow(clippy::needless_pass_by_value)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Rewards(msg) => {
+            let config = SplitConfig::parse(&msg.reward_config)?;
+
+            let mut response = QueryRewardsResponse {
+                claimed: true,
+                rewards: vec![],
+            };
+
+            for (reward_contract, msg) in config.reward_msgs(&msg) {
+                let split = deps.querier.query_wasm_smart::<QueryRewardsResponse>(
+                    reward_contract,
+                    &RewardQueryMsg::Rewards(msg),
+                )?;
+
+                response.claimed &= split.claimed;
+                response.rewards.extend(split.rewards);
+            }
+
+            Ok(to_json_binary(&response)?)
+        }
+        QueryMsg::ValidateRewardConfig(ValidateRewardConfigMsg {
+            scope,
+            reward_config,
+        }) => {
+            let config = SplitConfig::parse(&reward_config)?;
+
+            for (split, amount) in config.splits.iter().zip(config.amounts()) {
+                validate_split(deps, &split.reward_contract, &scope, amount.to_string())?;
+            }
+
+            Ok(to_json_binary(&Empty {})?)
+        }
+    }
+}
+
+/// Ensure that the downstream reward contract of a split accepts its part of the reward.
+fn validate_split(
+    deps: Deps,
+    reward_contract: &Addr,
+    scope: &RewardScope,
+    reward_config: String,
+) -> StdResult<()> {
+    deps.querier.query_wasm_smart::<Empty>(
+        reward_contract,
+        &RewardQueryMsg::ValidateRewardConfig(ValidateRewardConfigMsg {
+            scope: scope.clone(),
+            reward_config,
+        }),
+    )?;
+
+    Ok(())
+}
+
+#[entry_point]
+#[allow(clippy::needless_pass_by_value)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, Error> {
+    match msg {
+        ExecuteMsg::Reward(msg) => {
+            LAZYDEV.ensure_lazydev(deps.storage, &info.sender)?;
+
+            let config = SplitConfig::parse(&msg.reward_config).map_err(Error::InvalidConfig)?;
+
+            let reward_msgs = config
+                .reward_msgs(&msg)
+                .into_iter()
+                .map(|(reward_contract, msg)| {
+                    SubMsg::new(
+                        wasm_execute(reward_contract, &RewardExecuteMsg::Reward(msg), vec![])
+                            .expect(SERIALIZATION_INFALLIBLE_MSG),
+                    )
+                });
+
+            // the downstream reward contracts emit the reward events
+            Ok(Response::new().add_submessages(reward_msgs))
+        }
+        ExecuteMsg::Revoke(msg) => {
+            LAZYDEV.ensure_lazydev(deps.storage, &info.sender)?;
+
+            let config =
+                SplitConfig::parse(&msg.reward.reward_config).map_err(Error::InvalidConfig)?;
+
+            // every split is revoked with the same message it was rewarded with
+            let revoke_msgs =
+                config
+                    .reward_msgs(&msg.reward)
+                    .into_iter()
+                    .map(|(reward_contract, reward)| {
+                        SubMsg::new(
+                            wasm_execute(
+                                reward_contract,
+                                &RewardExecuteMsg::Revoke(RevokeMsg {
+                                    reward,
+                                    revert_pr_id: msg.revert_pr_id,
+                                }),
+                                vec![],
+                            )
+                            .expect(SERIALIZATION_INFALLIBLE_MSG),
+                        )
+                    });
+
+            Ok(Response::new().add_submessages(revoke_msgs))
+        }
+    }
+}
+
+#[entry_point]
+pub fn migrate(_: DepsMut, _: Env, _: MigrateMsg) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::Uint128;
+    use lazydev_reward_interface::{
+        msg::{ProtocolFee, RewardMsg},
+        state::Repo,
+    };
+
+    use super::*;
+
+    #[test]
+    fn split() {
+        let config = SplitConfig::parse(
+            r#"{"amount":"101","splits":[{"reward_contract":"a","weight":80},{"reward_contract":"b","weight":20,"recipient":"treasury"}]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.amounts(), [Uint128::new(81), Uint128::new(20)]);
+
+        let msg = RewardMsg {
+            repo: Repo::from(("unionlabs", "union")),
+            pr_id: 1,
+            user_id: 2,
+            recipient_address: Addr::unchecked("contributor"),
+            reward_config: String::new(),
+            protocol_fee: Some(ProtocolFee {
+                bps: 100,
+                treasury: Addr::unchecked("treasury"),
+            }),
+            ordinal: 1,
+        };
+
+        let msgs = config.reward_msgs(&msg);
+
+        assert_eq!(msgs[0].1.recipient_address, msg.recipient_address);
+        assert_eq!(msgs[0].1.reward_config, "81");
+        assert_eq!(msgs[0].1.protocol_fee, msg.protocol_fee);
+        assert_eq!(msgs[1].0, Addr::unchecked("b"));
+        assert_eq!(msgs[1].1.recipient_address, Addr::unchecked("treasury"));
+        // no fee is charged on the split of the treasury
+        assert_eq!(msgs[1].1.protocol_fee, None);
+
+        assert!(SplitConfig::parse(r#"{"amount":"1","splits":[]}"#).is_err());
+        assert!(SplitConfig::parse(
+            r#"{"amount":"1","splits":[{"reward_contract":"a","weight":1},{"reward_contract":"a","weight":1,"recipient":"treasury"}]}"#
+        )
+        .is_err());
+        // the second split rounds down to zero
+        assert!(SplitConfig::parse(
+            r#"{"amount":"1","splits":[{"reward_contract":"a","weight":1},{"reward_contract":"b","weight":1}]}"#
+        )
+        .is_err());
+        assert!(SplitConfig::parse(
+            r#"{"amount":"2","splits":[{"reward_contract":"a","weight":1},{"reward_contract":"b","weight":1}]}"#
+        )
+        .is_ok());
+    }
+}
@@ -0,0 +1,10 @@
+use cosmwasm_std::StdError;
+use lazydev_reward_interface::contract::OnlyLazydev;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    OnlyLazydev(#[from] OnlyLazydev),
+    #[error("invalid config")]
+    InvalidConfig(StdError),
+}
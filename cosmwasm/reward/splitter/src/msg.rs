@@ -0,0 +1,157 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{ensure, from_json, Addr, Empty, StdError, StdResult, Uint128};
+use lazydev_reward_interface::msg::{
+    QueryRewardsResponse, RevokeMsg, RewardMsg, ValidateRewardConfigMsg,
+};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub lazydev_address: Addr,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    Reward(RewardMsg),
+    /// Forward the revoke to the reward contract of every split. If any of them rejects it, the
+    /// whole revoke fails.
+    Revoke(RevokeMsg),
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Returns the rewards of every split, as returned by the downstream reward contracts. The
+    /// rewards are only `claimed` if every split has been claimed.
+    #[returns(QueryRewardsResponse)]
+    Rewards(RewardMsg),
+    #[returns(Empty)]
+    ValidateRewardConfig(ValidateRewardConfigMsg),
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
+
+/// The `reward_config` of this contract, as json.
+///
+/// ```json
+/// {
+///   "amount": "100",
+///   "splits": [
+///     { "reward_contract": "contract_a", "weight": 80 },
+///     { "reward_contract": "contract_b", "weight": 20, "recipient": "treasury" }
+///   ]
+/// }
+/// ```
+#[cw_serde]
+pub struct SplitConfig {
+    /// The total amount of the reward, split between `splits` by weight.
+    pub amount: Uint128,
+    /// The splits of the reward. Every split must use a different reward contract, since reward
+    /// contracts only reward a PR once.
+    pub splits: Vec<Split>,
+}
+
+#[cw_serde]
+pub struct Split {
+    /// The downstream reward contract. This contract must be configured with the splitter as its
+    /// lazydev address, and must accept an amount as its `reward_config`, such as
+    /// `lazydev-token-reward`.
+    pub reward_contract: Addr,
+    pub weight: u64,
+    /// The fixed recipient of this split. If [`None`], this split is sent to the contributor. No
+    /// protocol fee is charged on a split whose recipient is the protocol treasury.
+    #[serde(default)]
+    pub recipient: Option<Addr>,
+}
+
+impl SplitConfig {
+    /// Parse and validate a `reward_config`.
+    pub fn parse(reward_config: &str) -> StdResult<Self> {
+        let config = from_json::<Self>(reward_config)?;
+
+        ensure!(
+            !config.splits.is_empty() && config.splits.iter().all(|split| split.weight > 0),
+            StdError::generic_err("splits must be non-empty and have non-zero weights")
+        );
+
+        for (i, split) in config.splits.iter().enumerate() {
+            ensure!(
+                !config.splits[..i]
+                    .iter()
+                    .any(|other| other.reward_contract == split.reward_contract),
+                StdError::generic_err(format!(
+                    "reward contract {} is used by more than one split",
+                    split.reward_contract
+                ))
+            );
+        }
+
+        // downstream reward contracts may reject zero amounts, which would fail the whole reward
+        for (split, amount) in config.splits.iter().zip(config.amounts()) {
+            ensure!(
+                !amount.is_zero(),
+                StdError::generic_err(format!(
+                    "the split of reward contract {} rounds down to zero",
+                    split.reward_contract
+                ))
+            );
+        }
+
+        Ok(config)
+    }
+
+    /// The amount of each split, in the same order as `splits`. Any remainder from rounding down
+    /// is added to the first split, such that the amounts always add up to `amount`.
+    #[must_use]
+    pub fn amounts(&self) -> Vec<Uint128> {
+        let total_weight = self
+            .splits
+            .iter()
+            .map(|split| u128::from(split.weight))
+            .sum::<u128>();
+
+        let mut amounts = self
+            .splits
+            .iter()
+            .map(|split| self.amount.multiply_ratio(split.weight, total_weight))
+            .collect::<Vec<_>>();
+
+        let remainder = self.amount - amounts.iter().sum::<Uint128>();
+        if let Some(first) = amounts.first_mut() {
+            *first += remainder;
+        }
+
+        amounts
+    }
+
+    /// The [`RewardMsg`] of each split, derived from the message sent to the splitter.
+    #[must_use]
+    pub fn reward_msgs(&self, msg: &RewardMsg) -> Vec<(Addr, RewardMsg)> {
+        self.splits
+            .iter()
+            .zip(self.amounts())
+            .map(|(split, amount)| {
+                let recipient_address = split
+                    .recipient
+                    .clone()
+                    .unwrap_or_else(|| msg.recipient_address.clone());
+
+                // the treasury would pay the fee to itself
+                let protocol_fee = msg
+                    .protocol_fee
+                    .clone()
+                    .filter(|fee| fee.treasury != recipient_address);
+
+                (
+                    split.reward_contract.clone(),
+                    RewardMsg {
+                        recipient_address,
+                        reward_config: amount.to_string(),
+                        protocol_fee,
+                        ..msg.clone()
+                    },
+                )
+            })
+            .collect()
+    }
+}
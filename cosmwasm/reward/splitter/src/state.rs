@@ -0,0 +1,7 @@
+use lazydev_reward_interface::contract::Lazydev;
+
+/// The address of the lazydev contract, which is the only address that can call the `reward`
+/// entrypoint.
+///
+/// NOTE: Claimed rewards are tracked by the downstream reward contracts, not by the splitter.
+pub const LAZYDEV: Lazydev = Lazydev::new("lazydev");
@@ -1,20 +1,20 @@
 use cosmwasm_std::{
     ensure, entry_point, instantiate2_address, to_json_binary, wasm_execute, Binary, Checksum,
-    CodeInfoResponse, Deps, DepsMut, Env, MessageInfo, QueryRequest, Response, StdResult, SubMsg,
-    Uint128, WasmMsg,
+    CodeInfoResponse, Deps, DepsMut, Empty, Env, MessageInfo, QueryRequest, Response, StdError,
+    StdResult, SubMsg, Uint128, WasmMsg,
 };
-use lazydev::{
+use lazydev_reward_interface::{
     contract::STORAGE_ACCESS_INFALLIBLE_MSG,
-    models::reward::PrReward,
-    msg::{QueryRewardsResponse, RewardMsg},
-    reward_event,
+    event::RewardFeeEvent,
+    msg::{RewardScope, ValidateRewardConfigMsg},
+    reward::PrReward,
 };
 use sha2::{Digest, Sha256};
 
 use crate::{
     error::Error,
     msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
-    state::{ADMIN, ALLOWED_ORGS, ALLOWED_REPOS, CLAIMED_REWARDS, LAZYDEV_ADDR, TOKEN_ADDR},
+    state::{ACCUMULATED_FEES, ADMIN, ALLOWED_ORGS, ALLOWED_REPOS, REWARDS, TOKEN_ADDR},
 };
 
 #[entry_point]
@@ -29,9 +29,7 @@ pub fn instantiate(
         .save(deps.storage, &info.sender)
         .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
 
-    LAZYDEV_ADDR
-        .save(deps.storage, &msg.lazydev_address)
-        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+    REWARDS.init(deps.storage, &msg.lazydev_address);
 
     ALLOWED_REPOS
         .save(deps.storage, &msg.config.valid_repos)
@@ -82,6 +80,25 @@ pub fn instantiate(
     }))
 }
 
+/// Whether this contract rewards PRs in `scope`.
+fn is_allowed(deps: Deps, scope: &RewardScope) -> bool {
+    let allowed_orgs = ALLOWED_ORGS
+        .load(deps.storage)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    // org allowlists only apply to github, repos on other forges must be allowed individually
+    match scope {
+        RewardScope::Repo(repo) => {
+            (repo.forge.is_github() && allowed_orgs.contains(&repo.org))
+                || ALLOWED_REPOS
+                    .load(deps.storage)
+                    .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+                    .contains(repo)
+        }
+        RewardScope::Org { forge, org } => forge.is_github() && allowed_orgs.contains(org),
+    }
+}
+
 fn get_code_hash(deps: Deps, code_id: u64) -> StdResult<Checksum> {
     Ok(deps
         .querier
@@ -95,29 +112,40 @@ fn get_code_hash(deps: Deps, code_id: u64) -> StdResult<Checksum> {
 #[allow(clippy::needless_pass_by_value)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::Rewards(RewardMsg {
-            repo,
-            pr_id,
-            user_id: _,
-            recipient_address: _,
+        QueryMsg::Rewards(msg) => Ok(to_json_binary(&REWARDS.query(
+            deps.storage,
+            &msg,
+            || {
+                let amount = msg.reward_config.parse::<Uint128>()?;
+
+                Ok(PrReward::Token {
+                    denom: TOKEN_ADDR.load(deps.storage)?.to_string(),
+                    amount: amount
+                        - msg
+                            .protocol_fee
+                            .as_ref()
+                            .map_or(Uint128::zero(), |fee| fee.fee_amount(amount)),
+                })
+            },
+        )?)?),
+        QueryMsg::ValidateRewardConfig(ValidateRewardConfigMsg {
+            scope,
             reward_config,
         }) => {
-            let response = match CLAIMED_REWARDS.may_load(deps.storage, (pr_id, repo))? {
-                Some(claimed_rewards) => QueryRewardsResponse {
-                    claimed: true,
-                    rewards: vec![claimed_rewards],
-                },
-                None => QueryRewardsResponse {
-                    claimed: false,
-                    rewards: vec![PrReward::Token {
-                        denom: TOKEN_ADDR.load(deps.storage)?.to_string(),
-                        amount: reward_config.parse()?,
-                    }],
-                },
-            };
+            reward_config
+                .parse::<Uint128>()
+                .map_err(|err| StdError::generic_err(Error::InvalidConfig(err).to_string()))?;
+
+            ensure!(
+                is_allowed(deps, &scope),
+                StdError::generic_err(Error::InvalidScope(scope).to_string())
+            );
 
-            Ok(to_json_binary(&response)?)
+            Ok(to_json_binary(&Empty {})?)
         }
+        QueryMsg::AccumulatedFees {} => Ok(to_json_binary(
+            &ACCUMULATED_FEES.may_load(deps.storage)?.unwrap_or_default(),
+        )?),
     }
 }
 
@@ -130,74 +158,96 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, Error> {
     match msg {
-        ExecuteMsg::Reward(RewardMsg {
-            repo,
-            pr_id,
-            user_id,
-            recipient_address,
-            reward_config,
-        }) => {
-            ensure!(
-                LAZYDEV_ADDR
-                    .load(deps.storage)
-                    .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
-                    == info.sender,
-                Error::OnlyLazydev
-            );
+        ExecuteMsg::Reward(msg) => {
+            REWARDS.ensure_lazydev(deps.storage, &info.sender)?;
 
             let cw20_token_addr = TOKEN_ADDR
                 .load(deps.storage)
                 .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
 
-            let reward_amount = reward_config
+            let reward_amount = msg
+                .reward_config
                 .parse::<Uint128>()
                 .map_err(Error::InvalidConfig)?;
 
             ensure!(
-                ALLOWED_ORGS
-                    .load(deps.storage)
-                    .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
-                    .contains(&repo.org)
-                    || ALLOWED_REPOS
-                        .load(deps.storage)
-                        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
-                        .contains(&repo),
-                Error::InvalidRepo(repo)
+                is_allowed(deps.as_ref(), &RewardScope::Repo(msg.repo.clone())),
+                Error::InvalidRepo(msg.repo)
             );
 
+            let fee_amount = msg
+                .protocol_fee
+                .as_ref()
+                .map_or(Uint128::zero(), |fee| fee.fee_amount(reward_amount));
+
             let reward = PrReward::Token {
                 denom: cw20_token_addr.to_string(),
-                amount: reward_amount,
+                amount: reward_amount - fee_amount,
             };
 
-            CLAIMED_REWARDS
-                .save(deps.storage, (pr_id, repo.clone()), &reward)
-                .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+            let reward_event = REWARDS.claim(deps.storage, &msg, &reward);
 
-            Ok(Response::new()
+            let mut response = Response::new();
+
+            if let Some(protocol_fee) = msg.protocol_fee.filter(|_| !fee_amount.is_zero()) {
+                let accumulated_fees = ACCUMULATED_FEES
+                    .may_load(deps.storage)
+                    .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+                    .unwrap_or_default();
+
+                ACCUMULATED_FEES
+                    .save(deps.storage, &(accumulated_fees + fee_amount))
+                    .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+                response = response
+                    .add_submessage(SubMsg::new(
+                        wasm_execute(
+                            cw20_token_addr.clone(),
+                            &cw20::Cw20ExecuteMsg::Mint {
+                                recipient: protocol_fee.treasury.to_string(),
+                                amount: fee_amount,
+                            },
+                            vec![],
+                        )
+                        .expect("works"),
+                    ))
+                    .add_event(RewardFeeEvent {
+                        fee: PrReward::Token {
+                            denom: cw20_token_addr.to_string(),
+                            amount: fee_amount,
+                        },
+                        repo: msg.repo,
+                        pr_id: msg.pr_id,
+                        treasury: protocol_fee.treasury,
+                    });
+            }
+
+            Ok(response
                 .add_submessage(SubMsg::new(
                     wasm_execute(
                         cw20_token_addr,
                         &cw20::Cw20ExecuteMsg::Mint {
-                            recipient: recipient_address.to_string(),
-                            amount: reward_amount,
+                            recipient: msg.recipient_address.to_string(),
+                            amount: reward_amount - fee_amount,
                         },
                         vec![],
                     )
                     .expect("works"),
                 ))
-                .add_event(reward_event(
-                    &reward,
-                    repo,
-                    pr_id,
-                    user_id,
-                    recipient_address.to_string(),
-                )))
+                .add_event(reward_event))
+        }
+        ExecuteMsg::Revoke(_) => {
+            REWARDS.ensure_lazydev(deps.storage, &info.sender)?;
+
+            // NOTE: minted tokens can not be clawed back
+            Err(Error::Irrevocable)
         }
     }
 }
 
 #[entry_point]
-pub fn migrate(_: DepsMut, _: Env, _: MigrateMsg) -> StdResult<Response> {
+pub fn migrate(deps: DepsMut, _: Env, _: MigrateMsg) -> StdResult<Response> {
+    REWARDS.migrate(deps.storage);
+
     Ok(Response::default())
 }
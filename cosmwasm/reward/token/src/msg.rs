@@ -1,7 +1,7 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Addr;
-use lazydev::{
-    msg::{QueryRewardsResponse, RewardMsg},
+use cosmwasm_std::{Addr, Empty, Uint128};
+use lazydev_reward_interface::{
+    msg::{QueryRewardsResponse, RevokeMsg, RewardMsg, ValidateRewardConfigMsg},
     state::Repo,
 };
 
@@ -22,7 +22,8 @@ pub struct Config {
 
     /// The repos this contract will provide rewards for.
     pub valid_repos: Vec<Repo>,
-    /// The orgs this contract will provide rewards for.
+    /// The github orgs this contract will provide rewards for. Repos on other forges must be listed
+    /// in `valid_repos`.
     pub valid_orgs: Vec<String>,
 
     /// The code id of the cw20 base code to instantiate the token with.
@@ -32,6 +33,9 @@ pub struct Config {
 #[cw_serde]
 pub enum ExecuteMsg {
     Reward(RewardMsg),
+    /// Revoking a reward always fails, since minted tokens can not be clawed back. The revert of
+    /// the PR reports the reward as not revoked.
+    Revoke(RevokeMsg),
 }
 
 #[cw_serde]
@@ -39,6 +43,11 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(QueryRewardsResponse)]
     Rewards(RewardMsg),
+    #[returns(Empty)]
+    ValidateRewardConfig(ValidateRewardConfigMsg),
+    /// Returns the total amount of tokens minted to the treasury as protocol fees.
+    #[returns(Uint128)]
+    AccumulatedFees {},
 }
 
 #[cw_serde]
@@ -1,12 +1,12 @@
-use cosmwasm_std::Addr;
-use cw_storage_plus::{Item, Map};
-use lazydev::{models::reward::PrReward, state::Repo};
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::Item;
+use lazydev_reward_interface::{contract::Rewards, state::Repo};
 
 pub const ADMIN: Item<Addr> = Item::new("admin");
 
-/// The address of the lazydev contract. Only this contract can call the
-/// `reward` entrypoint.
-pub const LAZYDEV_ADDR: Item<Addr> = Item::new("lazydev");
+/// The address of the lazydev contract, which is the only address that can call the `reward`
+/// entrypoint, and the already claimed rewards.
+pub const REWARDS: Rewards = Rewards::new("lazydev", "claimed_rewards");
 
 /// The address of the cw20 contract to mint tokens from.
 pub const TOKEN_ADDR: Item<Addr> = Item::new("token");
@@ -15,5 +15,5 @@ pub const ALLOWED_ORGS: Item<Vec<String>> = Item::new("allowed_orgs");
 
 pub const ALLOWED_REPOS: Item<Vec<Repo>> = Item::new("allowed_repos");
 
-/// Already claimed rewards, keyed by a tuple of `(pr_id, org, repo)`.
-pub const CLAIMED_REWARDS: Map<(u64, Repo), PrReward> = Map::new("claimed_rewards");
+/// The total amount of tokens minted to the treasury as protocol fees.
+pub const ACCUMULATED_FEES: Item<Uint128> = Item::new("accumulated_fees");
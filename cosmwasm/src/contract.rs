@@ -1,46 +1,84 @@
+use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    ensure, to_json_binary, to_json_string, wasm_execute, Addr, Binary, Deps, DepsMut, Env, Event,
-    MessageInfo, Response, StdResult, SubMsg, WasmMsg,
+    ensure, from_json, to_json_binary, to_json_string, wasm_execute, Addr, BankMsg, Binary, Coin,
+    Deps, DepsMut, Empty, Env, MessageInfo, QuerierWrapper, Reply, Response, StdResult, SubMsg,
+    SubMsgResult, WasmMsg,
 };
-use cw_storage_plus::{Map, PrimaryKey};
+use cw_storage_plus::{Bound, KeyDeserialize, Map, PrimaryKey};
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
     error::Error,
-    event,
+    event::{
+        CommitEvent, CommitKind, FeeCollectedEvent, FeeRefundedEvent, FundTipPoolEvent,
+        GrantRepoRoleEvent, LinkAccountEvent, LinkOrgEvent, LinkRepoEvent, PrRevertedEvent,
+        PrRewardEvent, RelayerTipEvent, RevokeFailedEvent, RevokeRepoRoleEvent,
+        RewardApprovedEvent, RewardAwaitingApprovalEvent, RewardDispatchedEvent,
+        RewardFinalizedEvent, RewardPendingEvent, RewardVetoedEvent, TipKind,
+        TransferRepoOwnershipEvent, UpdateConfigEvent, UpdateFeeExemptionEvent, UpdatePauseEvent,
+        UpdateRepoConfigEvent, UpdateRepoPauseEvent, UpdateRewardRecipientEvent,
+        UpdateRewardRegistryEvent, WithdrawTipPoolEvent,
+    },
+    forge::{Forge, ForgeExt, PullRequest},
     models::{
-        github::{PrBody, UserRepoBody},
-        reclaim::{JsonExtractedParameters, Proof, UserExtractedParameters},
+        github::{MembershipRole, MembershipState, OrgMembershipBody},
+        reclaim::{JsonExtractedParameters, Proof, ProofKind, UserExtractedParameters},
     },
     msg::{
-        ExecuteMsg, InstantiateMsg, LinkAccountMsg, LinkRepoMsg, MigrateMsg, PrEligibility,
-        QueryMsg, RewardExecuteMsg, RewardMsg, RewardPrMsg, VerifierMsg, VerifyProofMsg,
+        Allowance, ApproveRewardMsg, DeregisterRewardContractMsg, ExecuteMsg, FinalizeRewardMsg,
+        FundTipPoolMsg, GrantRepoRoleMsg, InstantiateMsg, LabelRewards, LinkAccountMsg, LinkOrgMsg,
+        LinkRepoMsg, LinkedOrg, LinkedUser, MigrateMsg, PauseMsg, PauseRepoMsg, PrEligibility,
+        ProtocolFee, QueryMsg, QueryRewardsResponse, RegisterRewardContractMsg,
+        RegisteredRewardContract, RepoRoleGrant, RepoRolesResponse, RepoStats, RevertPrMsg,
+        RevokeMsg, RevokeRepoRoleMsg, RewardAllowanceResponse, RewardExecuteMsg, RewardMsg,
+        RewardPrMsg, RewardQueryMsg, RewardScope, RewardedPrRef, SetFeeExemptionMsg,
+        SetRewardRecipientMsg, SimulateRewardPrMsg, SimulateRewardPrResponse,
+        TransferRepoOwnershipMsg, UnpauseMsg, UnpauseRepoMsg, UnverifiedCheck, UpdateConfigMsg,
+        UpdateRepoConfigMsg, UserStatsResponse, ValidateRewardConfigMsg, VerifierMsg,
+        VerifyProofMsg, VetoRewardMsg, WithdrawTipPoolMsg,
     },
     state::{
-        Commitment, Config, Repo, CONFIG, EXISTING_PROOFS, REPOS, REPO_COMMITMENTS, REWARDED_PRS,
-        USERS, USER_COMMITMENTS,
+        legacy, AccountRequirements, Commitment, Config, FeeConfig, FlatFee, LabelConfig,
+        PauseSwitch, PendingApproval, PendingReward, QueuedReward, RateLimit, RateLimitUsage,
+        RateLimits, Repo, RepoConfig, RepoPause, RepoRole, RewardContractId, RewardRecipient,
+        RewardStatus, RewardedPr, SelfDealingPolicy, SwitchPause, TipUsage, UserKey, UserProfile,
+        ACCUMULATED_FEES, CONFIG, DISPATCHED_REWARDS, EXISTING_PROOFS, FEE_EXEMPT_REPOS,
+        LINK_RELAYERS, ORGS, ORG_ADMIN_IDS, ORG_COMMITMENTS, ORG_OWNERS, PAUSE, PENDING_APPROVALS,
+        PENDING_REWARDS, REPOS, REPO_ADMIN_IDS, REPO_COMMITMENTS, REPO_OWNERS, REPO_PAUSES,
+        REPO_RATE_LIMITS, REPO_ROLES, REWARDED_PRS, REWARD_CODE_IDS, REWARD_CONTRACTS,
+        REWARD_RECIPIENTS, TIP_POOLS, TIP_USAGE, USERS, USER_COMMITMENTS, USER_PROFILES,
+        USER_RATE_LIMITS, USER_REPO_ORDINALS, USER_REPO_STATS, USER_REWARDED_PRS, USER_STATS,
     },
-    utils::{parse_github_api_pull_request_url, parse_github_api_repos_url, sha256},
+    utils::{owner_commitment_key, parse_rfc3339, sha256},
 };
 
-pub const SERIALIZATION_INFALLIBLE_MSG: &str = "serialization is infallible";
-pub const STORAGE_ACCESS_INFALLIBLE_MSG: &str = "storage access is infallible";
+pub use lazydev_reward_interface::contract::{
+    SERIALIZATION_INFALLIBLE_MSG, STORAGE_ACCESS_INFALLIBLE_MSG,
+};
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 #[allow(clippy::needless_pass_by_value)]
 pub fn instantiate(
     deps: DepsMut,
     _env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     msg: InstantiateMsg,
 ) -> StdResult<Response> {
     assert!(msg.commitment_delay_min_height < msg.commitment_delay_max_height);
+    assert!(msg.fee.as_ref().is_none_or(|fee| fee.bps <= MAX_BPS));
 
     let state = Config {
         // repo: msg.repo,
+        owner: info.sender,
         verifier_address: msg.verifier_address,
         commitment_delay_min_height: msg.commitment_delay_min_height,
         commitment_delay_max_height: msg.commitment_delay_max_height,
+        allowed_forges: msg.allowed_forges,
+        allowed_provider_hashes: msg.allowed_provider_hashes,
+        guardian: msg.guardian,
+        fee: msg.fee,
+        reward_registry_enabled: msg.reward_registry_enabled,
+        account_requirements: msg.account_requirements,
     };
 
     CONFIG.save(deps.storage, &state)?;
@@ -49,40 +87,212 @@ pub fn instantiate(
 }
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
-#[allow(clippy::needless_pass_by_value)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+#[allow(clippy::needless_pass_by_value, clippy::too_many_lines)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => Ok(to_json_binary(&CONFIG.load(deps.storage)?)?),
-        QueryMsg::LinkedAddress { github_user_id } => Ok(to_json_binary(
-            &USERS.may_load(deps.storage, github_user_id)?,
+        QueryMsg::LinkedAddress {
+            forge,
+            github_user_id,
+        } => Ok(to_json_binary(
+            &USERS.may_load(deps.storage, (forge, github_user_id))?,
         )?),
-        QueryMsg::UserCommitment { github_user_id } => Ok(to_json_binary(
-            &USER_COMMITMENTS.may_load(deps.storage, github_user_id)?,
+        QueryMsg::UserCommitment {
+            forge,
+            github_user_id,
+        } => Ok(to_json_binary(
+            &USER_COMMITMENTS.may_load(deps.storage, (forge, github_user_id))?,
         )?),
         QueryMsg::RepoCommitment { repo } => Ok(to_json_binary(
             &REPO_COMMITMENTS.may_load(deps.storage, repo)?,
         )?),
         QueryMsg::Repos {} => Ok(to_json_binary(
             &REPOS
+                .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+                .collect::<Result<Vec<_>, _>>()?,
+        )?),
+        QueryMsg::RepoConfig { repo } => Ok(to_json_binary(&REPOS.may_load(deps.storage, repo)?)?),
+        QueryMsg::RepoRoles { repo } => Ok(to_json_binary(&RepoRolesResponse {
+            owner: repo_owner(deps, &repo),
+            admin_id: REPO_ADMIN_IDS.may_load(deps.storage, repo.clone())?,
+            roles: REPO_ROLES
+                .prefix(repo)
+                .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+                .map(|res| res.map(|(address, roles)| RepoRoleGrant { address, roles }))
+                .collect::<Result<Vec<_>, _>>()?,
+        })?),
+        QueryMsg::OrgCommitment { forge, org } => Ok(to_json_binary(
+            &ORG_COMMITMENTS.may_load(deps.storage, (forge, org))?,
+        )?),
+        QueryMsg::Orgs {} => Ok(to_json_binary(
+            &ORGS
+                .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+                .map(|res| res.map(|(forge, org)| LinkedOrg { forge, org }))
+                .collect::<Result<Vec<_>, _>>()?,
+        )?),
+        QueryMsg::OrgConfig { forge, org } => {
+            Ok(to_json_binary(&ORGS.may_load(deps.storage, (forge, org))?)?)
+        }
+        QueryMsg::PauseStatus {} => {
+            let mut pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+            pause.clear_expired(env.block.height);
+
+            Ok(to_json_binary(&pause)?)
+        }
+        QueryMsg::RepoPauseStatus { repo } => Ok(to_json_binary(
+            &REPO_PAUSES
+                .may_load(deps.storage, repo)?
+                .filter(|pause| pause.is_active(env.block.height)),
+        )?),
+        QueryMsg::SimulateRewardPr(msg) => {
+            Ok(to_json_binary(&simulate_reward_pr(deps, &env, msg)?)?)
+        }
+        QueryMsg::RewardRegistry { start_after, limit } => Ok(to_json_binary(
+            &query_reward_registry(deps, start_after, limit)?,
+        )?),
+        QueryMsg::FeeExemptRepos {} => Ok(to_json_binary(
+            &FEE_EXEMPT_REPOS
+                .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+                .collect::<Result<Vec<_>, _>>()?,
+        )?),
+        QueryMsg::AccumulatedFees {} => Ok(to_json_binary(
+            &ACCUMULATED_FEES
                 .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+                .map(|res| res.map(|(denom, amount)| Coin { denom, amount }))
+                .collect::<Result<Vec<_>, _>>()?,
+        )?),
+        QueryMsg::TipPool { repo } => Ok(to_json_binary(
+            &TIP_POOLS
+                .prefix(repo)
+                .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+                .map(|res| res.map(|(denom, amount)| Coin { denom, amount }))
+                .collect::<Result<Vec<_>, _>>()?,
+        )?),
+        QueryMsg::UserByAddress { address } => Ok(to_json_binary(
+            &USERS
+                .idx
+                .address
+                .prefix(address)
+                .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+                .map(|res| {
+                    res.map(|(forge, github_user_id)| LinkedUser {
+                        forge,
+                        github_user_id,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        )?),
+        QueryMsg::UserStats {
+            forge,
+            github_user_id,
+            start_after,
+            limit,
+        } => Ok(to_json_binary(&query_user_stats(
+            deps,
+            (forge, github_user_id),
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::UserRewardedPrs {
+            forge,
+            github_user_id,
+            start_after,
+            limit,
+        } => Ok(to_json_binary(&query_user_rewarded_prs(
+            deps,
+            (forge, github_user_id),
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::RewardAllowance {
+            repo,
+            github_user_id,
+        } => {
+            let rate_limits = load_repo_config(deps, &repo)
+                .and_then(|repo_config| repo_config.rate_limits)
+                .unwrap_or_default();
+
+            let user_usage = USER_RATE_LIMITS.may_load(
+                deps.storage,
+                (repo.clone(), (repo.forge.clone(), github_user_id)),
+            )?;
+            let repo_usage = REPO_RATE_LIMITS.may_load(deps.storage, repo)?;
+
+            let allowance = |limit: RateLimit, usage: Option<RateLimitUsage>| Allowance {
+                remaining: limit.remaining(usage.as_ref(), env.block.time),
+                resets_at: limit.resets_at(env.block.time),
+            };
+
+            Ok(to_json_binary(&RewardAllowanceResponse {
+                user: rate_limits
+                    .per_user
+                    .map(|limit| allowance(limit, user_usage)),
+                repo: rate_limits
+                    .per_repo
+                    .map(|limit| allowance(limit, repo_usage)),
+            })?)
+        }
+        QueryMsg::UserProfile {
+            forge,
+            github_user_id,
+        } => Ok(to_json_binary(
+            &USER_PROFILES.may_load(deps.storage, (forge, github_user_id))?,
+        )?),
+        QueryMsg::RewardRecipients {
+            forge,
+            github_user_id,
+        } => Ok(to_json_binary(
+            &REWARD_RECIPIENTS
+                .may_load(deps.storage, (forge, github_user_id))?
+                .unwrap_or_default(),
+        )?),
+        QueryMsg::PendingRewards {
+            repo,
+            start_after,
+            limit,
+        } => Ok(to_json_binary(
+            &PENDING_REWARDS
+                .prefix(repo)
+                .range(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    cosmwasm_std::Order::Ascending,
+                )
+                .take(pagination_limit(limit))
+                .map(|res| res.map(|(_, pending)| pending))
+                .collect::<Result<Vec<_>, _>>()?,
+        )?),
+        QueryMsg::PendingApprovals {
+            repo,
+            start_after,
+            limit,
+        } => Ok(to_json_binary(
+            &PENDING_APPROVALS
+                .prefix(repo)
+                .range(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    cosmwasm_std::Order::Ascending,
+                )
+                .take(pagination_limit(limit))
+                .map(|res| res.map(|(_, pending_approvals)| pending_approvals))
                 .collect::<Result<Vec<_>, _>>()?
                 .into_iter()
-                .map(|((org, repo), _)| Repo { org, repo })
+                .flatten()
                 .collect::<Vec<_>>(),
         )?),
-        QueryMsg::RepoConfig { repo } => Ok(to_json_binary(
-            &REPOS.may_load(deps.storage, (repo.org, repo.repo))?,
-        )?),
         QueryMsg::QueryPrEligibility {
             repo,
             pr_id,
             github_user_id,
         } => {
-            let eligibility = if !USERS.has(deps.storage, github_user_id) {
+            let eligibility = if !USERS.has(deps.storage, (repo.forge.clone(), github_user_id)) {
                 // if the user has not yet linked, the pr is not eligible
                 PrEligibility::Ineligible
-            } else if REPOS.has(deps.storage, (repo.org.clone(), repo.repo.clone())) {
-                if REWARDED_PRS.has(deps.storage, (repo.org, repo.repo, pr_id)) {
+            } else if load_repo_config(deps, &repo).is_some() {
+                if REWARDED_PRS.has(deps.storage, (repo, pr_id)) {
                     PrEligibility::Claimed
                 } else {
                     PrEligibility::Eligible
@@ -97,63 +307,241 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     }
 }
 
+fn query_user_stats(
+    deps: Deps,
+    user: UserKey,
+    start_after: Option<Repo>,
+    limit: Option<u32>,
+) -> StdResult<UserStatsResponse> {
+    Ok(UserStatsResponse {
+        rewarded_prs: USER_STATS
+            .may_load(deps.storage, user.clone())?
+            .unwrap_or_default(),
+        repos: USER_REPO_STATS
+            .prefix(user)
+            .range(
+                deps.storage,
+                start_after.map(Bound::exclusive),
+                None,
+                cosmwasm_std::Order::Ascending,
+            )
+            .take(pagination_limit(limit))
+            .map(|res| res.map(|(repo, rewarded_prs)| RepoStats { repo, rewarded_prs }))
+            .collect::<Result<Vec<_>, _>>()?,
+    })
+}
+
+fn query_user_rewarded_prs(
+    deps: Deps,
+    user: UserKey,
+    start_after: Option<RewardedPrRef>,
+    limit: Option<u32>,
+) -> StdResult<Vec<RewardedPrRef>> {
+    USER_REWARDED_PRS
+        .prefix(user)
+        .keys(
+            deps.storage,
+            start_after.map(|pr| Bound::exclusive((pr.repo, pr.pr_id))),
+            None,
+            cosmwasm_std::Order::Ascending,
+        )
+        .take(pagination_limit(limit))
+        .map(|res| res.map(|(repo, pr_id)| RewardedPrRef { repo, pr_id }))
+        .collect()
+}
+
+/// Versions without forge support are detected by their config, which has no owner. Their state is
+/// re-keyed under [`Forge::Github`], see [`migrate_legacy`].
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 #[allow(clippy::needless_pass_by_value)]
-pub fn migrate(_: DepsMut, _: Env, _: MigrateMsg) -> StdResult<Response> {
+pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, Error> {
+    if CONFIG.load(deps.storage).is_err() {
+        let owner = msg.owner.ok_or(Error::MissingMigrationOwner)?;
+
+        migrate_legacy(&mut deps, &env, owner);
+    }
+
     Ok(Response::default())
 }
 
+/// Migrate the state of a version without forge support, see [`legacy`].
+///
+/// Users, user commitments, repos and rewarded PRs are re-keyed under [`Forge::Github`]. Rewarded
+/// PRs are marked as [`RewardStatus::Legacy`], since those versions did not record who they were
+/// rewarded to. Pending repo commitments are dropped, since they were made without the sender of
+/// the link (see [`owner_commitment_key`]) and could never be revealed. Repos linked by those
+/// versions have no owner until they are linked again.
+fn migrate_legacy(deps: &mut DepsMut, env: &Env, owner: Addr) {
+    let config = legacy::CONFIG
+        .load(deps.storage)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    CONFIG
+        .save(
+            deps.storage,
+            &Config {
+                owner,
+                verifier_address: config.verifier_address,
+                commitment_delay_min_height: config.commitment_delay_min_height,
+                commitment_delay_max_height: config.commitment_delay_max_height,
+                allowed_forges: vec![],
+                allowed_provider_hashes: vec![],
+                guardian: None,
+                fee: None,
+                reward_registry_enabled: false,
+                account_requirements: None,
+            },
+        )
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    for (user_id, address) in drain(deps, &legacy::USERS) {
+        USERS
+            .save(deps.storage, (Forge::Github, user_id), &address)
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+    }
+
+    for (user_id, commitment) in drain(deps, &legacy::USER_COMMITMENTS) {
+        USER_COMMITMENTS
+            .save(deps.storage, (Forge::Github, user_id), &commitment)
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+    }
+
+    drain(deps, &legacy::REPO_COMMITMENTS);
+
+    for (repo, repo_config) in drain(deps, &legacy::REPOS) {
+        REPOS
+            .save(deps.storage, Repo::from(repo), &repo_config)
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+    }
+
+    for ((org, repo, pr_id), ()) in drain(deps, &legacy::REWARDED_PRS) {
+        REWARDED_PRS
+            .save(
+                deps.storage,
+                (Repo::from((org, repo)), pr_id),
+                &RewardedPr {
+                    user_id: 0,
+                    recipient_address: env.contract.address.clone(),
+                    status: RewardStatus::Legacy,
+                    revert_pr_id: None,
+                },
+            )
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+    }
+}
+
+/// Remove every entry of a legacy map, returning the removed entries.
+fn drain<'a, K, V>(deps: &mut DepsMut, map: &Map<K, V>) -> Vec<(K, V)>
+where
+    K: PrimaryKey<'a> + KeyDeserialize<Output = K> + Clone + 'static,
+    V: Serialize + DeserializeOwned,
+{
+    let entries = map
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    for (key, _) in &entries {
+        map.remove(deps.storage, key.clone());
+    }
+
+    entries
+}
+
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 #[allow(clippy::needless_pass_by_value)]
 pub fn execute(
     mut deps: DepsMut,
     env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, Error> {
     let config = CONFIG.load(deps.storage).expect("config must exist");
 
     match msg {
+        ExecuteMsg::UpdateConfig(msg) => update_config(&mut deps, &info, msg, config),
+
         ExecuteMsg::CommitRepo(msg) => commit(
             &mut deps,
             &env,
+            CommitKind::Repo,
             msg.repo,
             msg.config,
             msg.commitment_key,
             &REPO_COMMITMENTS,
             &config,
         ),
-        ExecuteMsg::LinkRepo(msg) => link_repo(&mut deps, &env, msg, &config),
+        ExecuteMsg::LinkRepo(msg) => link_repo(&mut deps, &env, &info, msg, &config),
+
+        ExecuteMsg::CommitOrg(msg) => commit(
+            &mut deps,
+            &env,
+            CommitKind::Org,
+            (msg.forge, msg.org),
+            msg.config,
+            msg.commitment_key,
+            &ORG_COMMITMENTS,
+            &config,
+        ),
+        ExecuteMsg::LinkOrg(msg) => link_org(&mut deps, &env, &info, msg, &config),
+
+        ExecuteMsg::UpdateRepoConfig(msg) => update_repo_config(&mut deps, &info, msg, &config),
+        ExecuteMsg::GrantRepoRole(msg) => grant_repo_role(&mut deps, &info, msg),
+        ExecuteMsg::RevokeRepoRole(msg) => revoke_repo_role(&mut deps, &info, msg),
+        ExecuteMsg::TransferRepoOwnership(msg) => transfer_repo_ownership(&mut deps, &info, msg),
+
+        ExecuteMsg::Pause(msg) => pause(&mut deps, &env, &info, msg, &config),
+        ExecuteMsg::Unpause(msg) => unpause(&mut deps, &env, &info, msg, &config),
+        ExecuteMsg::PauseRepo(msg) => pause_repo(&mut deps, &info, msg),
+        ExecuteMsg::UnpauseRepo(msg) => unpause_repo(&mut deps, &info, msg),
 
         ExecuteMsg::CommitAccount(msg) => commit(
             &mut deps,
             &env,
-            msg.github_user_id,
+            CommitKind::Account,
+            (msg.forge, msg.github_user_id),
             msg.recipient_address,
             msg.commitment_key,
             &USER_COMMITMENTS,
             &config,
         ),
-        ExecuteMsg::LinkAccount(msg) => link_account(&mut deps, &env, msg, &config),
+        ExecuteMsg::LinkAccount(msg) => link_account(&mut deps, &env, &info, msg, &config),
+        ExecuteMsg::SetRewardRecipient(msg) => set_reward_recipient(&mut deps, &info, msg),
 
-        ExecuteMsg::RewardPr(msg) => reward_pr(&mut deps, msg, &config),
-        // ExecuteMsg::AdminResetAll => {
-        //     USERS.clear(deps.storage);
-        //     EXISTING_PROOFS.clear(deps.storage);
-        //     USER_COMMITMENTS.clear(deps.storage);
-        //     REPO_COMMITMENTS.clear(deps.storage);
-        //     REPOS.clear(deps.storage);
-        //     REWARDED_PRS.clear(deps.storage);
+        ExecuteMsg::RewardPr(msg) => reward_pr(&mut deps, &env, &info, msg, &config),
+        ExecuteMsg::VetoReward(msg) => veto_reward(&mut deps, &env, &info, msg),
+        ExecuteMsg::FinalizeReward(msg) => finalize_reward(&mut deps, &env, msg),
+        ExecuteMsg::RevertPr(msg) => revert_pr(&mut deps, &info, msg, &config),
+        ExecuteMsg::ApproveReward(msg) => approve_reward(&mut deps, &env, &info, msg),
+
+        ExecuteMsg::SetFeeExemption(msg) => set_fee_exemption(&mut deps, &info, msg, &config),
+
+        ExecuteMsg::FundTipPool(msg) => fund_tip_pool(&mut deps, &info, msg),
+        ExecuteMsg::WithdrawTipPool(msg) => withdraw_tip_pool(&mut deps, &info, msg),
+
+        ExecuteMsg::RegisterRewardContract(msg) => {
+            register_reward_contract(&mut deps, &info, msg, &config)
+        }
+        ExecuteMsg::DeregisterRewardContract(msg) => {
+            deregister_reward_contract(&mut deps, &info, msg, &config)
+        } // ExecuteMsg::AdminResetAll => {
+          //     USERS.clear(deps.storage);
+          //     EXISTING_PROOFS.clear(deps.storage);
+          //     USER_COMMITMENTS.clear(deps.storage);
+          //     REPO_COMMITMENTS.clear(deps.storage);
+          //     REPOS.clear(deps.storage);
+          //     REWARDED_PRS.clear(deps.storage);
 
-        //     Ok(Response::default())
-        // }
+          //     Ok(Response::default())
+          // }
     }
 }
 
-#[allow(clippy::needless_pass_by_value)] // leave me alone man
-fn commit<'a, Key: PrimaryKey<'a>, Value: Clone + Serialize + DeserializeOwned>(
+#[allow(clippy::needless_pass_by_value, clippy::too_many_arguments)] // leave me alone man
+fn commit<'a, Key: PrimaryKey<'a> + Serialize, Value: Clone + Serialize + DeserializeOwned>(
     deps: &mut DepsMut,
     env: &Env,
+    kind: CommitKind,
     key: Key,
     value: Value,
     commitment_key: Binary,
@@ -179,58 +567,118 @@ fn commit<'a, Key: PrimaryKey<'a>, Value: Clone + Serialize + DeserializeOwned>(
     store
         .save(
             deps.storage,
-            key,
+            key.clone(),
             &Commitment {
-                commitment_key,
+                commitment_key: commitment_key.clone(),
                 commitment_height: env.block.height,
                 value: value.clone(),
             },
         )
         .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
 
-    Ok(
-        Response::new().add_event(Event::new("commit").add_attribute(
-            std::str::from_utf8(store.namespace_bytes()).expect("valid utf8; qed;"),
-            to_json_string(&value).expect(SERIALIZATION_INFALLIBLE_MSG),
-        )),
-    )
+    Ok(Response::new().add_event(CommitEvent {
+        kind,
+        key: to_json_string(&key).expect(SERIALIZATION_INFALLIBLE_MSG),
+        value: to_json_string(&value).expect(SERIALIZATION_INFALLIBLE_MSG),
+        commitment_key,
+    }))
+}
+
+/// Validate the admin user proof of a [`LinkRepoMsg`], returning the forge user id of the admin.
+///
+/// NOTE: This does not verify the signatures of the proof, which is done by the verifier contract.
+fn validate_admin_user_proof(
+    deps: &mut DepsMut,
+    forge: &Forge,
+    repo_admin_proof: &Proof,
+    admin_user_proof: &Proof,
+    repo_admin_id: Option<u64>,
+    config: &Config,
+) -> Result<u64, Error> {
+    ensure_new_proof(deps, admin_user_proof)?;
+
+    let parameters =
+        admin_user_proof.validate_parameters(admin_user_proof.user_proof_kind()?, config)?;
+
+    ensure!(parameters.url == forge.user_url(), Error::InvalidUserUrl);
+
+    // both proofs must have been generated by the same prover, such that the admin of the repo
+    // proof is the user of the user proof
+    ensure!(
+        admin_user_proof.signed_claim.claim.owner == repo_admin_proof.signed_claim.claim.owner,
+        Error::AdminUserProofOwnerMismatch
+    );
+
+    let admin_id = admin_user_proof
+        .deserialize_context::<UserExtractedParameters>()?
+        .extracted_parameters
+        .id
+        .parse::<u64>()
+        .map_err(|_| Error::InvalidUserId)?;
+
+    if let Some(repo_admin_id) = repo_admin_id {
+        ensure!(
+            repo_admin_id == admin_id,
+            Error::AdminIdMismatch {
+                expected: repo_admin_id,
+                found: admin_id,
+            }
+        );
+    }
+
+    Ok(admin_id)
 }
 
 fn link_repo(
     deps: &mut DepsMut,
     env: &Env,
+    info: &MessageInfo,
     msg: LinkRepoMsg,
     config: &Config,
 ) -> Result<Response, Error> {
+    ensure_not_paused(deps.as_ref(), env, PauseSwitch::LinkRepos)?;
+    ensure_forge_allowed(config, &msg.repo.forge)?;
+
     ensure_new_proof(deps, &msg.repo_admin_permissions_proof)?;
 
-    let admin_permissions_body = msg
+    let repo_admin = msg.repo.forge.deserialize_repo_admin(
+        &msg.repo_admin_permissions_proof
+            .deserialize_context::<JsonExtractedParameters>()?
+            .extracted_parameters,
+    )?;
+
+    ensure!(repo_admin.is_admin, Error::InsufficientPermissions);
+
+    let parameters = msg
         .repo_admin_permissions_proof
-        .deserialize_context::<JsonExtractedParameters>()?
-        .extracted_parameters
-        .deserialize_json::<UserRepoBody>()?;
+        .validate_parameters(ProofKind::RepoAdmin, config)?;
+    let repo = msg
+        .repo
+        .forge
+        .parse_repo_url(&parameters.url)
+        .ok_or(Error::InvalidCollaboratorUrl)?;
 
-    ensure!(
-        admin_permissions_body.permissions.admin,
-        Error::InsufficientPermissions
-    );
+    ensure!(repo == msg.repo, Error::InvalidRepo);
 
-    let parameters = msg.repo_admin_permissions_proof.deserialize_parameters()?;
-    let (org, repo) =
-        parse_github_api_repos_url(&parameters.url).ok_or(Error::InvalidCollaboratorUrl)?;
+    let admin_id = match (repo_admin.user_id, &msg.admin_user_proof) {
+        (Some(admin_id), None) => admin_id,
+        (_, Some(admin_user_proof)) => validate_admin_user_proof(
+            deps,
+            &repo.forge,
+            &msg.repo_admin_permissions_proof,
+            admin_user_proof,
+            repo_admin.user_id,
+            config,
+        )?,
+        (None, None) => return Err(Error::MissingAdminUserProof),
+    };
 
-    let commitment_key = sha256(msg.secret.as_slice());
+    let commitment_key = owner_commitment_key(&msg.secret, &info.sender);
 
     let commitment = REPO_COMMITMENTS
-        .may_load(
-            deps.storage,
-            Repo {
-                org: org.to_owned(),
-                repo: repo.to_owned(),
-            },
-        )
+        .may_load(deps.storage, repo.clone())
         .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
-        .ok_or(Error::RepoCommitmentNotFound(msg.repo.clone()))?;
+        .ok_or(Error::RepoCommitmentNotFound(repo.clone()))?;
 
     ensure!(
         (config.commitment_delay_min_height..config.commitment_delay_max_height)
@@ -245,176 +693,5091 @@ fn link_repo(
         Error::InvalidCommitment
     );
 
+    validate_repo_config(
+        deps.as_ref(),
+        config,
+        &RewardScope::Repo(repo.clone()),
+        &msg.config,
+    )?;
+
     // remove commitment now that the repo has been linked
-    REPO_COMMITMENTS.remove(
-        deps.storage,
-        Repo {
-            org: org.to_owned(),
-            repo: repo.to_owned(),
-        },
-    );
+    REPO_COMMITMENTS.remove(deps.storage, repo.clone());
+
+    clear_repo_roles(deps, &repo);
+
+    REPO_OWNERS
+        .save(deps.storage, repo.clone(), &info.sender)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    REPO_ADMIN_IDS
+        .save(deps.storage, repo.clone(), &admin_id)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
 
     REPOS
-        .save(deps.storage, (msg.repo.org, msg.repo.repo), &msg.config)
+        .save(deps.storage, repo.clone(), &msg.config)
         .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
 
     Ok(Response::new()
-        .add_submessages([verify_proof_sub_msg(
-            &config.verifier_address,
-            msg.repo_admin_permissions_proof,
-        )])
-        .add_event(Event::new("link_repo").add_attribute("repo", format!("{org}/{repo}"))))
+        .add_submessages(
+            [msg.repo_admin_permissions_proof]
+                .into_iter()
+                .chain(msg.admin_user_proof)
+                .map(|proof| verify_proof_sub_msg(&config.verifier_address, proof)),
+        )
+        .add_event(LinkRepoEvent {
+            repo,
+            owner: info.sender.clone(),
+        }))
 }
 
-fn link_account(
+fn update_config(
     deps: &mut DepsMut,
-    env: &Env,
-    msg: LinkAccountMsg,
+    info: &MessageInfo,
+    msg: UpdateConfigMsg,
+    mut config: Config,
+) -> Result<Response, Error> {
+    ensure!(info.sender == config.owner, Error::NotOwner);
+
+    if let Some(owner) = msg.owner {
+        config.owner = owner;
+    }
+
+    if let Some(allowed_forges) = msg.allowed_forges {
+        config.allowed_forges = allowed_forges;
+    }
+
+    if let Some(allowed_provider_hashes) = msg.allowed_provider_hashes {
+        config.allowed_provider_hashes = allowed_provider_hashes;
+    }
+
+    if let Some(guardian) = msg.guardian {
+        config.guardian = guardian;
+    }
+
+    if let Some(fee) = msg.fee {
+        if let Some(fee) = &fee {
+            ensure!(fee.bps <= MAX_BPS, Error::InvalidFeeBps(fee.bps));
+        }
+        config.fee = fee;
+    }
+
+    if let Some(reward_registry_enabled) = msg.reward_registry_enabled {
+        config.reward_registry_enabled = reward_registry_enabled;
+    }
+
+    if let Some(account_requirements) = msg.account_requirements {
+        config.account_requirements = account_requirements;
+    }
+
+    CONFIG
+        .save(deps.storage, &config)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    Ok(Response::new().add_event(UpdateConfigEvent { config }))
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn update_repo_config(
+    deps: &mut DepsMut,
+    info: &MessageInfo,
+    msg: UpdateRepoConfigMsg,
     config: &Config,
 ) -> Result<Response, Error> {
-    ensure_new_proof(deps, &msg.proof)?;
+    ensure_repo_role(
+        deps.as_ref(),
+        &msg.repo,
+        &info.sender,
+        RepoRole::ConfigEditor,
+    )?;
 
-    let context = msg.proof.deserialize_context::<UserExtractedParameters>()?;
+    validate_repo_config(
+        deps.as_ref(),
+        config,
+        &RewardScope::Repo(msg.repo.clone()),
+        &msg.config,
+    )?;
 
-    let github_user_id = context
-        .extracted_parameters
-        .id
-        .parse::<u64>()
-        .map_err(|_| Error::InvalidUserId)?;
+    REPOS
+        .save(deps.storage, msg.repo.clone(), &msg.config)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
 
-    let commitment_key = sha256(msg.secret);
+    Ok(Response::new().add_event(UpdateRepoConfigEvent {
+        repo: msg.repo,
+        sender: info.sender.clone(),
+        config: msg.config,
+    }))
+}
 
-    let commitment = USER_COMMITMENTS
-        .may_load(deps.storage, github_user_id)
+fn grant_repo_role(
+    deps: &mut DepsMut,
+    info: &MessageInfo,
+    msg: GrantRepoRoleMsg,
+) -> Result<Response, Error> {
+    ensure_repo_owner(deps.as_ref(), &msg.repo, &info.sender)?;
+
+    let key = (msg.repo.clone(), msg.address);
+
+    let mut roles = REPO_ROLES
+        .may_load(deps.storage, key.clone())
         .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
-        .ok_or(Error::UserCommitmentNotFound(github_user_id))?;
+        .unwrap_or_default();
 
-    ensure!(
-        (config.commitment_delay_min_height..config.commitment_delay_max_height)
-            .contains(&(env.block.height - commitment.commitment_height)),
-        Error::CommitmentExpired
-    );
+    if !roles.contains(&msg.role) {
+        roles.push(msg.role);
+        roles.sort();
+    }
 
-    ensure!(
-        commitment.commitment_key == commitment_key,
-        Error::InvalidCommitmentKey
-    );
+    REPO_ROLES
+        .save(deps.storage, key.clone(), &roles)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
 
-    USER_COMMITMENTS.remove(deps.storage, github_user_id);
-    USERS
-        .save(deps.storage, github_user_id, &msg.recipient_address)
+    Ok(Response::new().add_event(GrantRepoRoleEvent {
+        repo: msg.repo,
+        address: key.1,
+        role: msg.role,
+    }))
+}
+
+fn revoke_repo_role(
+    deps: &mut DepsMut,
+    info: &MessageInfo,
+    msg: RevokeRepoRoleMsg,
+) -> Result<Response, Error> {
+    ensure_repo_owner(deps.as_ref(), &msg.repo, &info.sender)?;
+
+    let key = (msg.repo.clone(), msg.address);
+
+    let mut roles = REPO_ROLES
+        .may_load(deps.storage, key.clone())
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        .unwrap_or_default();
+
+    roles.retain(|role| *role != msg.role);
+
+    if roles.is_empty() {
+        REPO_ROLES.remove(deps.storage, key.clone());
+    } else {
+        REPO_ROLES
+            .save(deps.storage, key.clone(), &roles)
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+    }
+
+    Ok(Response::new().add_event(RevokeRepoRoleEvent {
+        repo: msg.repo,
+        address: key.1,
+        role: msg.role,
+    }))
+}
+
+fn transfer_repo_ownership(
+    deps: &mut DepsMut,
+    info: &MessageInfo,
+    msg: TransferRepoOwnershipMsg,
+) -> Result<Response, Error> {
+    ensure_repo_owner(deps.as_ref(), &msg.repo, &info.sender)?;
+
+    clear_repo_roles(deps, &msg.repo);
+
+    REPO_OWNERS
+        .save(deps.storage, msg.repo.clone(), &msg.new_owner)
         .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
 
-    Ok(Response::new().add_submessage(verify_proof_sub_msg(&config.verifier_address, msg.proof)))
+    Ok(Response::new().add_event(TransferRepoOwnershipEvent {
+        repo: msg.repo,
+        previous_owner: info.sender.clone(),
+        new_owner: msg.new_owner,
+    }))
 }
 
-fn reward_pr(deps: &mut DepsMut, msg: RewardPrMsg, config: &Config) -> Result<Response, Error> {
-    let context = msg.proof.deserialize_context::<JsonExtractedParameters>()?;
+fn link_org(
+    deps: &mut DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    msg: LinkOrgMsg,
+    config: &Config,
+) -> Result<Response, Error> {
+    ensure_not_paused(deps.as_ref(), env, PauseSwitch::LinkRepos)?;
+    ensure_forge_allowed(config, &msg.forge)?;
+    ensure!(
+        msg.forge.supports_org_linking(),
+        Error::UnsupportedForge(msg.forge)
+    );
+
+    ensure_new_proof(deps, &msg.org_admin_membership_proof)?;
+
+    let membership_body = msg
+        .org_admin_membership_proof
+        .deserialize_context::<JsonExtractedParameters>()?
+        .extracted_parameters
+        .deserialize_json::<OrgMembershipBody>()?;
 
-    let body = serde_json_wasm::from_str::<PrBody>(&context.extracted_parameters.json)
-        .map_err(Error::InvalidExtractedParameters)?;
+    ensure!(
+        membership_body.state == MembershipState::Active
+            && membership_body.role == MembershipRole::Admin,
+        Error::InsufficientOrgPermissions
+    );
 
-    let url = msg.proof.deserialize_parameters()?.url;
+    let parameters = msg
+        .org_admin_membership_proof
+        .validate_parameters(ProofKind::OrgAdmin, config)?;
+    let org = msg
+        .forge
+        .parse_org_membership_url(&parameters.url)
+        .ok_or(Error::InvalidOrgMembershipUrl)?;
 
-    let (org, repo, pr_id) = parse_github_api_pull_request_url(&url).ok_or(Error::InvalidPrUrl)?;
+    ensure!(org == msg.org, Error::InvalidOrg);
 
-    // sanity check
-    assert_eq!(body.number, pr_id);
+    let commitment_key = owner_commitment_key(&msg.secret, &info.sender);
 
-    let recipient_address = USERS
-        .may_load(deps.storage, body.user.id)
+    let commitment = ORG_COMMITMENTS
+        .may_load(deps.storage, (msg.forge.clone(), msg.org.clone()))
         .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
-        .ok_or(Error::UserNotFound(body.user.id))?;
+        .ok_or(Error::OrgCommitmentNotFound(msg.org.clone()))?;
 
     ensure!(
-        url == format!(
-            "https://api.github.com/repos/{}/{}/pulls/{}",
-            org, repo, body.number
-        ),
-        Error::InvalidRepo
+        (config.commitment_delay_min_height..config.commitment_delay_max_height)
+            .contains(&(env.block.height - commitment.commitment_height)),
+        Error::CommitmentExpired
     );
 
-    ensure!(body.merged, Error::PrNotMerged);
-
+    // ensure that the originally committed config is the same as the value provided in the link
+    // message
     ensure!(
-        REWARDED_PRS
-            .may_load(deps.storage, (org.to_owned(), repo.to_owned(), body.number))
-            .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
-            .is_none(),
-        Error::PrAlreadyRewarded(body.number)
+        commitment_key == commitment.commitment_key && commitment.value == msg.config,
+        Error::InvalidCommitment
     );
 
-    REWARDED_PRS
+    validate_repo_config(
+        deps.as_ref(),
+        config,
+        &RewardScope::Org {
+            forge: msg.forge.clone(),
+            org: msg.org.clone(),
+        },
+        &msg.config,
+    )?;
+
+    // remove commitment now that the org has been linked
+    ORG_COMMITMENTS.remove(deps.storage, (msg.forge.clone(), msg.org.clone()));
+
+    clear_org_repo_roles(deps, &msg.forge, &msg.org);
+
+    ORG_OWNERS
         .save(
             deps.storage,
-            (org.to_owned(), repo.to_owned(), body.number),
-            &(),
+            (msg.forge.clone(), msg.org.clone()),
+            &info.sender,
         )
         .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
 
-    let repo_config = REPOS
-        .load(deps.storage, (org.to_owned(), repo.to_owned()))
-        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+    ORGS.save(
+        deps.storage,
+        (msg.forge.clone(), msg.org.clone()),
+        &msg.config,
+    )
+    .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    match membership_body.user {
+        Some(admin) => ORG_ADMIN_IDS
+            .save(
+                deps.storage,
+                (msg.forge.clone(), msg.org.clone()),
+                &admin.id,
+            )
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG),
+        None => ORG_ADMIN_IDS.remove(deps.storage, (msg.forge.clone(), msg.org.clone())),
+    }
 
     Ok(Response::new()
-        .add_submessage(verify_proof_sub_msg(&config.verifier_address, msg.proof))
-        .add_submessages(body.labels.into_iter().flat_map(|label| {
-            repo_config
-                .label_configs
-                .iter()
-                .filter(move |label_config| label_config.label_id == label.id)
-                .map(|label_config| {
-                    let callback = RewardMsg {
-                        repo: Repo {
-                            org: org.to_owned(),
-                            repo: repo.to_owned(),
-                        },
-                        pr_id,
-                        user_id: body.user.id,
-                        recipient_address: recipient_address.clone(),
-                        reward_config: label_config.reward_config.clone(),
-                    };
-
-                    SubMsg::reply_never(
-                        wasm_execute(
-                            label_config.reward_contract.clone(),
-                            &RewardExecuteMsg::Reward(callback),
-                            vec![],
-                        )
-                        .expect(SERIALIZATION_INFALLIBLE_MSG),
-                    )
-                })
+        .add_submessages([verify_proof_sub_msg(
+            &config.verifier_address,
+            msg.org_admin_membership_proof,
+        )])
+        .add_event(LinkOrgEvent {
+            forge: msg.forge,
+            org: msg.org,
         }))
-        .add_event(Event::new(event::PR_REWARD).add_attributes([
-            (event::attribute::ORG, org.to_owned()),
-            (event::attribute::REPO, repo.to_owned()),
-            (event::attribute::PR, pr_id.to_string()),
-            (event::attribute::USER, body.user.id.to_string()),
-            (event::attribute::RECIPIENT, recipient_address.to_string()),
-        ])))
 }
 
-#[must_use]
-pub fn verify_proof_sub_msg(contract: &Addr, proof: Proof) -> SubMsg {
-    SubMsg::reply_never(WasmMsg::Execute {
-        contract_addr: contract.to_string(),
-        msg: to_json_binary(&VerifierMsg::VerifyProof(VerifyProofMsg { proof }))
-            .expect(SERIALIZATION_INFALLIBLE_MSG),
-        funds: vec![],
-    })
+/// Removes the roles granted in `repo`, so that a new owner starts out with a clean access control
+/// list.
+fn clear_repo_roles(deps: &mut DepsMut, repo: &Repo) {
+    let delegates = REPO_ROLES
+        .prefix(repo.clone())
+        .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .collect::<Result<Vec<_>, _>>()
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    for address in delegates {
+        REPO_ROLES.remove(deps.storage, (repo.clone(), address));
+    }
 }
 
-/// Ensures that the proof has not been used yet, and saves the checksum of it if it is new.
-fn ensure_new_proof(deps: &mut DepsMut, proof: &Proof) -> Result<(), Error> {
-    let proof_hash = sha256(serde_json_wasm::to_vec(&proof).expect(SERIALIZATION_INFALLIBLE_MSG));
+/// Removes the roles granted in the repos of the org that are owned through the org, since they
+/// were granted by the previous owner of the org.
+fn clear_org_repo_roles(deps: &mut DepsMut, forge: &Forge, org: &str) {
+    let delegates = REPO_ROLES
+        .keys(
+            deps.storage,
+            Some(Bound::inclusive((
+                Repo {
+                    forge: forge.clone(),
+                    org: org.to_owned(),
+                    repo: String::new(),
+                },
+                Addr::unchecked(""),
+            ))),
+            None,
+            cosmwasm_std::Order::Ascending,
+        )
+        .map(|res| res.expect(STORAGE_ACCESS_INFALLIBLE_MSG))
+        .take_while(|(repo, _)| repo.forge == *forge && repo.org == org)
+        .filter(|(repo, _)| !REPO_OWNERS.has(deps.storage, repo.clone()))
+        .collect::<Vec<_>>();
 
-    if EXISTING_PROOFS.has(deps.storage, proof_hash.clone()) {
-        return Err(Error::ProofAlreadySubmitted);
+    for key in delegates {
+        REPO_ROLES.remove(deps.storage, key);
     }
+}
 
-    EXISTING_PROOFS
-        .save(deps.storage, proof_hash.clone(), &())
-        .expect(SERIALIZATION_INFALLIBLE_MSG);
+fn link_account(
+    deps: &mut DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    msg: LinkAccountMsg,
+    config: &Config,
+) -> Result<Response, Error> {
+    ensure_not_paused(deps.as_ref(), env, PauseSwitch::LinkAccounts)?;
+    ensure_forge_allowed(config, &msg.forge)?;
+
+    ensure_new_proof(deps, &msg.proof)?;
+
+    let parameters = msg
+        .proof
+        .validate_parameters(msg.proof.user_proof_kind()?, config)?;
+
+    ensure!(
+        parameters.url == msg.forge.user_url(),
+        Error::InvalidUserUrl
+    );
+
+    let context = msg.proof.deserialize_context::<UserExtractedParameters>()?;
+
+    let github_user_id = context
+        .extracted_parameters
+        .id
+        .parse::<u64>()
+        .map_err(|_| Error::InvalidUserId)?;
+
+    let profile = parse_user_profile(&context.extracted_parameters)?;
+
+    if let Some(account_requirements) = &config.account_requirements {
+        ensure_account_requirements(env, github_user_id, profile.as_ref(), account_requirements)?;
+    }
+
+    let commitment_key = sha256(msg.secret);
+
+    let commitment = USER_COMMITMENTS
+        .may_load(deps.storage, (msg.forge.clone(), github_user_id))
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        .ok_or(Error::UserCommitmentNotFound(github_user_id))?;
+
+    ensure!(
+        (config.commitment_delay_min_height..config.commitment_delay_max_height)
+            .contains(&(env.block.height - commitment.commitment_height)),
+        Error::CommitmentExpired
+    );
+
+    ensure!(
+        commitment.commitment_key == commitment_key,
+        Error::InvalidCommitmentKey
+    );
+
+    USER_COMMITMENTS.remove(deps.storage, (msg.forge.clone(), github_user_id));
+    USERS
+        .save(
+            deps.storage,
+            (msg.forge.clone(), github_user_id),
+            &msg.recipient_address,
+        )
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    // the relayer is only tipped once a pr of the user is rewarded, see `RelayerTips::link_account`
+    if info.sender == msg.recipient_address {
+        LINK_RELAYERS.remove(deps.storage, (msg.forge.clone(), github_user_id));
+    } else {
+        LINK_RELAYERS
+            .save(
+                deps.storage,
+                (msg.forge.clone(), github_user_id),
+                &info.sender,
+            )
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+    }
+
+    // recipients set by a previously linked address must not receive rewards
+    REWARD_RECIPIENTS.remove(deps.storage, (msg.forge.clone(), github_user_id));
+
+    // a stale profile from a previous link must not be checked against account requirements
+    match &profile {
+        Some(profile) => USER_PROFILES
+            .save(deps.storage, (msg.forge.clone(), github_user_id), profile)
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG),
+        None => USER_PROFILES.remove(deps.storage, (msg.forge.clone(), github_user_id)),
+    }
+
+    Ok(Response::new()
+        .add_submessage(verify_proof_sub_msg(&config.verifier_address, msg.proof))
+        .add_event(LinkAccountEvent {
+            forge: msg.forge,
+            user_id: github_user_id,
+            recipient: msg.recipient_address,
+        }))
+}
+
+fn set_reward_recipient(
+    deps: &mut DepsMut,
+    info: &MessageInfo,
+    msg: SetRewardRecipientMsg,
+) -> Result<Response, Error> {
+    let user = (msg.forge.clone(), msg.github_user_id);
+
+    let linked_address = USERS
+        .may_load(deps.storage, user.clone())
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        .ok_or(Error::UserNotFound(msg.github_user_id))?;
+
+    ensure!(
+        linked_address == info.sender,
+        Error::NotLinkedAddress(msg.github_user_id)
+    );
+
+    let mut recipients = REWARD_RECIPIENTS
+        .may_load(deps.storage, user.clone())
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        .unwrap_or_default();
+
+    recipients.retain(|recipient| recipient.reward_contract != msg.reward_contract);
+
+    if let Some(recipient) = &msg.recipient {
+        recipients.push(RewardRecipient {
+            reward_contract: msg.reward_contract.clone(),
+            recipient: recipient.clone(),
+        });
+    }
+
+    if recipients.is_empty() {
+        REWARD_RECIPIENTS.remove(deps.storage, user);
+    } else {
+        REWARD_RECIPIENTS
+            .save(deps.storage, user, &recipients)
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+    }
+
+    Ok(Response::new().add_event(UpdateRewardRecipientEvent {
+        forge: msg.forge,
+        user_id: msg.github_user_id,
+        reward_contract: msg.reward_contract,
+        recipient: msg.recipient,
+    }))
+}
+
+/// Parse the account details of a [`ProofKind::UserProfile`] proof, or [`None`] if they were not
+/// extracted.
+fn parse_user_profile(parameters: &UserExtractedParameters) -> Result<Option<UserProfile>, Error> {
+    let (Some(created_at), Some(public_repos), Some(followers)) = (
+        &parameters.created_at,
+        &parameters.public_repos,
+        &parameters.followers,
+    ) else {
+        return Ok(None);
+    };
+
+    Ok(Some(UserProfile {
+        created_at: parse_rfc3339(created_at).ok_or(Error::InvalidUserProfile)?,
+        public_repos: public_repos
+            .parse()
+            .map_err(|_| Error::InvalidUserProfile)?,
+        followers: followers.parse().map_err(|_| Error::InvalidUserProfile)?,
+    }))
+}
+
+/// Ensure that the account of `user_id` meets `requirements`.
+fn ensure_account_requirements(
+    env: &Env,
+    user_id: u64,
+    profile: Option<&UserProfile>,
+    requirements: &AccountRequirements,
+) -> Result<(), Error> {
+    if *requirements == AccountRequirements::default() {
+        return Ok(());
+    }
+
+    let profile = profile.ok_or(Error::MissingUserProfile(user_id))?;
+
+    if let Some(min_account_age) = requirements.min_account_age {
+        ensure!(
+            env.block.time.seconds() >= profile.created_at.seconds() + min_account_age,
+            Error::AccountTooNew {
+                user_id,
+                min_account_age
+            }
+        );
+    }
+
+    if let Some(min_public_repos) = requirements.min_public_repos {
+        ensure!(
+            profile.public_repos >= min_public_repos,
+            Error::TooFewPublicRepos {
+                user_id,
+                min_public_repos
+            }
+        );
+    }
+
+    if let Some(min_followers) = requirements.min_followers {
+        ensure!(
+            profile.followers >= min_followers,
+            Error::TooFewFollowers {
+                user_id,
+                min_followers
+            }
+        );
+    }
 
     Ok(())
 }
+
+fn reward_pr(
+    deps: &mut DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    msg: RewardPrMsg,
+    config: &Config,
+) -> Result<Response, Error> {
+    let (repo, pull_request) = validate_pull_request_proof(&msg.forge, &msg.proof, config)?;
+
+    let pr = validate_pr_reward(deps.as_ref(), env, repo, pull_request, None)?;
+
+    let status = if pr.repo_config.challenge_period.is_some() {
+        RewardStatus::Pending
+    } else {
+        RewardStatus::Dispatched
+    };
+
+    save_rewarded_pr(
+        deps,
+        &pr.repo,
+        pr.pull_request.number,
+        (pr.repo.forge.clone(), pr.pull_request.author_id),
+        &pr.recipient_address,
+        status,
+    );
+
+    record_rate_limits(deps, env, &pr);
+
+    let response = Response::new()
+        .add_submessage(verify_proof_sub_msg(&config.verifier_address, msg.proof))
+        .add_event(PrRewardEvent {
+            repo: pr.repo.clone(),
+            pr_id: pr.pull_request.number,
+            user_id: pr.pull_request.author_id,
+            recipient: pr.recipient_address.clone(),
+        });
+
+    let (response, protocol_fee, held_fee) = charge_fee(deps, env, info, &pr, config, response)?;
+
+    let mut rewards = vec![];
+    let mut pending_approvals = vec![];
+
+    for label_config in pr.label_configs() {
+        let reward = QueuedReward {
+            label_id: label_config.label_id,
+            reward_contract: label_config.reward_contract.clone(),
+            msg: pr.reward_msg(&deps.querier, label_config, protocol_fee.clone()),
+        };
+
+        match &label_config.approval {
+            Some(approval) => pending_approvals.push(PendingApproval {
+                reward,
+                approvers: approval.approvers.clone(),
+                threshold: approval.threshold,
+                approvals: vec![],
+                expires_at_height: env.block.height + approval.expires_after_blocks,
+            }),
+            None => rewards.push(reward),
+        }
+    }
+
+    let response = response.add_events(pending_approvals.iter().map(|pending_approval| {
+        RewardAwaitingApprovalEvent {
+            repo: pr.repo.clone(),
+            pr_id: pr.pull_request.number,
+            label_id: pending_approval.reward.label_id,
+            expires_at_height: pending_approval.expires_at_height,
+        }
+    }));
+
+    if !pending_approvals.is_empty() {
+        PENDING_APPROVALS
+            .save(
+                deps.storage,
+                (pr.repo.clone(), pr.pull_request.number),
+                &pending_approvals,
+            )
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+    }
+
+    let Some(challenge_period) = &pr.repo_config.challenge_period else {
+        return Ok(dispatch_rewards(
+            deps,
+            response,
+            &pr.repo,
+            pr.pull_request.number,
+            rewards,
+        ));
+    };
+
+    let pending = PendingReward {
+        pr_id: pr.pull_request.number,
+        user_id: pr.pull_request.author_id,
+        recipient_address: pr.recipient_address.clone(),
+        challenge_ends_at_height: env.block.height + challenge_period.blocks,
+        rewarded_at: env.block.time,
+        relayer: info.sender.clone(),
+        held_fee,
+        rewards,
+    };
+
+    PENDING_REWARDS
+        .save(deps.storage, (pr.repo.clone(), pending.pr_id), &pending)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    Ok(response.add_event(RewardPendingEvent {
+        repo: pr.repo,
+        pr_id: pending.pr_id,
+        challenge_ends_at_height: pending.challenge_ends_at_height,
+    }))
+}
+
+/// Send every reward of a PR to its reward contract, see [`reply`].
+fn dispatch_rewards(
+    deps: &mut DepsMut,
+    mut response: Response,
+    repo: &Repo,
+    pr_id: u64,
+    rewards: Vec<QueuedReward>,
+) -> Response {
+    if !rewards.is_empty() {
+        DISPATCHED_REWARDS
+            .update(deps.storage, (repo.clone(), pr_id), |dispatched| {
+                let mut dispatched = dispatched.unwrap_or_default();
+                dispatched.extend(rewards.iter().cloned());
+                StdResult::Ok(dispatched)
+            })
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+    }
+
+    for reward in rewards {
+        let event = RewardDispatchedEvent {
+            repo: reward.msg.repo.clone(),
+            pr_id: reward.msg.pr_id,
+            label_id: reward.label_id,
+            reward_contract: reward.reward_contract.clone(),
+        };
+
+        // rewards are atomic with the PR being marked as rewarded, a failing reward contract
+        // reverts the whole message
+        response = response
+            .add_submessage(SubMsg::reply_never(
+                wasm_execute(
+                    reward.reward_contract,
+                    &RewardExecuteMsg::Reward(reward.msg),
+                    vec![],
+                )
+                .expect(SERIALIZATION_INFALLIBLE_MSG),
+            ))
+            .add_event(event);
+    }
+
+    response
+}
+
+fn veto_reward(
+    deps: &mut DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    msg: VetoRewardMsg,
+) -> Result<Response, Error> {
+    let pending = load_pending_reward(deps.as_ref(), &msg.repo, msg.pr_id)?;
+
+    ensure!(
+        env.block.height < pending.challenge_ends_at_height,
+        Error::ChallengePeriodEnded(pending.challenge_ends_at_height)
+    );
+
+    let is_guardian = load_repo_config(deps.as_ref(), &msg.repo)
+        .and_then(|repo_config| repo_config.challenge_period)
+        .and_then(|challenge_period| challenge_period.guardian)
+        .is_some_and(|guardian| guardian == info.sender);
+
+    if !is_guardian {
+        ensure_repo_role(
+            deps.as_ref(),
+            &msg.repo,
+            &info.sender,
+            RepoRole::RewardManager,
+        )?;
+    }
+
+    PENDING_REWARDS.remove(deps.storage, (msg.repo.clone(), msg.pr_id));
+    PENDING_APPROVALS.remove(deps.storage, (msg.repo.clone(), msg.pr_id));
+
+    REWARDED_PRS
+        .save(
+            deps.storage,
+            (msg.repo.clone(), msg.pr_id),
+            &RewardedPr {
+                user_id: pending.user_id,
+                recipient_address: pending.recipient_address.clone(),
+                status: RewardStatus::Vetoed,
+                revert_pr_id: None,
+            },
+        )
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    remove_from_user_stats(deps, &msg.repo, msg.pr_id, pending.user_id);
+    refund_rate_limits(deps, &msg.repo, &pending);
+
+    let mut response = Response::new().add_event(RewardVetoedEvent {
+        repo: msg.repo.clone(),
+        pr_id: msg.pr_id,
+        sender: info.sender.clone(),
+    });
+
+    // relayers are not tipped for vetoed rewards, and get their fee back
+    if let Some(FlatFee { fee, .. }) = pending.held_fee {
+        response = response
+            .add_message(BankMsg::Send {
+                to_address: pending.relayer.to_string(),
+                amount: vec![fee.clone()],
+            })
+            .add_event(FeeRefundedEvent {
+                repo: msg.repo,
+                pr_id: msg.pr_id,
+                fee,
+                relayer: pending.relayer,
+            });
+    }
+
+    Ok(response)
+}
+
+fn finalize_reward(
+    deps: &mut DepsMut,
+    env: &Env,
+    msg: FinalizeRewardMsg,
+) -> Result<Response, Error> {
+    let FinalizeRewardMsg { repo, pr_id } = msg;
+
+    ensure_not_paused(deps.as_ref(), env, PauseSwitch::Rewards)?;
+    ensure_repo_not_paused(deps.as_ref(), env, &repo)?;
+
+    let pending = load_pending_reward(deps.as_ref(), &repo, pr_id)?;
+
+    ensure!(
+        env.block.height >= pending.challenge_ends_at_height,
+        Error::ChallengePeriodActive(pending.challenge_ends_at_height)
+    );
+
+    PENDING_REWARDS.remove(deps.storage, (repo.clone(), pr_id));
+
+    REWARDED_PRS
+        .save(
+            deps.storage,
+            (repo.clone(), pr_id),
+            &RewardedPr {
+                user_id: pending.user_id,
+                recipient_address: pending.recipient_address,
+                status: RewardStatus::Dispatched,
+                revert_pr_id: None,
+            },
+        )
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    let response = Response::new().add_event(RewardFinalizedEvent {
+        repo: repo.clone(),
+        pr_id,
+    });
+
+    let response = collect_fee(deps, &repo, pr_id, pending.held_fee, response);
+
+    let response = tip_relayers(
+        deps,
+        env,
+        &pending.relayer,
+        &repo,
+        pr_id,
+        pending.user_id,
+        response,
+    );
+
+    Ok(dispatch_rewards(
+        deps,
+        response,
+        &repo,
+        pr_id,
+        pending.rewards,
+    ))
+}
+
+fn revert_pr(
+    deps: &mut DepsMut,
+    info: &MessageInfo,
+    msg: RevertPrMsg,
+    config: &Config,
+) -> Result<Response, Error> {
+    ensure_repo_role(
+        deps.as_ref(),
+        &msg.repo,
+        &info.sender,
+        RepoRole::RewardManager,
+    )?;
+
+    let rewarded_pr = REWARDED_PRS
+        .may_load(deps.storage, (msg.repo.clone(), msg.pr_id))
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        .ok_or(Error::PrNotRewarded(msg.pr_id))?;
+
+    // pending rewards are vetoed instead
+    ensure!(
+        rewarded_pr.status == RewardStatus::Dispatched,
+        Error::InvalidRewardStatus {
+            pr_id: msg.pr_id,
+            status: rewarded_pr.status,
+        }
+    );
+
+    let mut response = Response::new();
+
+    let revert_pr_id = match msg.revert_proof {
+        Some(revert_proof) => {
+            ensure_new_proof(deps, &revert_proof)?;
+
+            let (repo, revert_pr) =
+                validate_pull_request_proof(&msg.repo.forge, &revert_proof, config)?;
+
+            ensure!(
+                repo == msg.repo && revert_pr.merged && revert_pr.references(msg.pr_id),
+                Error::InvalidRevertPr(revert_pr.number)
+            );
+
+            response = response
+                .add_submessage(verify_proof_sub_msg(&config.verifier_address, revert_proof));
+
+            Some(revert_pr.number)
+        }
+        None => None,
+    };
+
+    REWARDED_PRS
+        .save(
+            deps.storage,
+            (msg.repo.clone(), msg.pr_id),
+            &RewardedPr {
+                status: RewardStatus::Reverted,
+                revert_pr_id,
+                ..rewarded_pr
+            },
+        )
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    remove_from_user_stats(deps, &msg.repo, msg.pr_id, rewarded_pr.user_id);
+
+    let rewards = DISPATCHED_REWARDS
+        .may_load(deps.storage, (msg.repo.clone(), msg.pr_id))
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        .unwrap_or_default();
+
+    DISPATCHED_REWARDS.remove(deps.storage, (msg.repo.clone(), msg.pr_id));
+    PENDING_APPROVALS.remove(deps.storage, (msg.repo.clone(), msg.pr_id));
+
+    for reward in rewards {
+        let dispatched = RewardDispatch {
+            repo: msg.repo.clone(),
+            pr_id: msg.pr_id,
+            label_id: reward.label_id,
+            reward_contract: reward.reward_contract.clone(),
+        };
+
+        response = response.add_submessage(
+            SubMsg::reply_on_error(
+                wasm_execute(
+                    reward.reward_contract,
+                    &RewardExecuteMsg::Revoke(RevokeMsg {
+                        reward: reward.msg,
+                        revert_pr_id,
+                    }),
+                    vec![],
+                )
+                .expect(SERIALIZATION_INFALLIBLE_MSG),
+                REVOKE_REPLY_ID,
+            )
+            .with_payload(to_json_binary(&dispatched).expect(SERIALIZATION_INFALLIBLE_MSG)),
+        );
+    }
+
+    Ok(response.add_event(PrRevertedEvent {
+        repo: msg.repo,
+        pr_id: msg.pr_id,
+        sender: info.sender.clone(),
+        revert_pr_id,
+    }))
+}
+
+fn approve_reward(
+    deps: &mut DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    msg: ApproveRewardMsg,
+) -> Result<Response, Error> {
+    ensure_not_paused(deps.as_ref(), env, PauseSwitch::Rewards)?;
+    ensure_repo_not_paused(deps.as_ref(), env, &msg.repo)?;
+
+    let not_found = || Error::PendingApprovalNotFound {
+        pr_id: msg.pr_id,
+        label_id: msg.label_id,
+    };
+
+    let pending_approvals = PENDING_APPROVALS
+        .may_load(deps.storage, (msg.repo.clone(), msg.pr_id))
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        .ok_or_else(not_found)?;
+
+    ensure!(
+        pending_approvals
+            .iter()
+            .any(|pending_approval| pending_approval.reward.label_id == msg.label_id),
+        not_found()
+    );
+
+    let mut response = Response::new();
+    let mut is_approver = false;
+    let mut remaining = vec![];
+    let mut approved = vec![];
+
+    // a label can have several label configs, all of which are approved by the same approval
+    for mut pending_approval in pending_approvals {
+        if pending_approval.reward.label_id != msg.label_id
+            || !pending_approval.approvers.contains(&info.sender)
+        {
+            remaining.push(pending_approval);
+            continue;
+        }
+
+        ensure!(
+            env.block.height < pending_approval.expires_at_height,
+            Error::ApprovalExpired(pending_approval.expires_at_height)
+        );
+
+        is_approver = true;
+
+        if !pending_approval.approvals.contains(&info.sender) {
+            pending_approval.approvals.push(info.sender.clone());
+        }
+
+        let approvals = pending_approval.approvals.len() as u64;
+
+        response = response.add_event(RewardApprovedEvent {
+            repo: msg.repo.clone(),
+            pr_id: msg.pr_id,
+            label_id: msg.label_id,
+            sender: info.sender.clone(),
+            approvals,
+        });
+
+        if approvals >= u64::from(pending_approval.threshold) {
+            approved.push(pending_approval.reward);
+        } else {
+            remaining.push(pending_approval);
+        }
+    }
+
+    ensure!(
+        is_approver,
+        Error::NotApprover {
+            pr_id: msg.pr_id,
+            label_id: msg.label_id,
+        }
+    );
+
+    if remaining.is_empty() {
+        PENDING_APPROVALS.remove(deps.storage, (msg.repo.clone(), msg.pr_id));
+    } else {
+        PENDING_APPROVALS
+            .save(deps.storage, (msg.repo.clone(), msg.pr_id), &remaining)
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+    }
+
+    // rewards approved during the challenge period are finalized with the other rewards of the pr
+    if let Some(mut pending) = PENDING_REWARDS
+        .may_load(deps.storage, (msg.repo.clone(), msg.pr_id))
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+    {
+        pending.rewards.extend(approved);
+
+        PENDING_REWARDS
+            .save(deps.storage, (msg.repo, msg.pr_id), &pending)
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+        return Ok(response);
+    }
+
+    Ok(dispatch_rewards(
+        deps, response, &msg.repo, msg.pr_id, approved,
+    ))
+}
+
+/// Remove a PR that is no longer rewarded from the rewarded PRs and stats of its author. The PR
+/// stays in [`REWARDED_PRS`] such that it can not be rewarded again, and keeps counting towards the
+/// [`USER_REPO_ORDINALS`] of its author.
+fn remove_from_user_stats(deps: &mut DepsMut, repo: &Repo, pr_id: u64, user_id: u64) {
+    let user = (repo.forge.clone(), user_id);
+
+    USER_REWARDED_PRS.remove(deps.storage, (user.clone(), (repo.clone(), pr_id)));
+
+    USER_STATS
+        .update(deps.storage, user.clone(), |count| {
+            StdResult::Ok(count.unwrap_or_default().saturating_sub(1))
+        })
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    USER_REPO_STATS
+        .update(deps.storage, (user, repo.clone()), |count| {
+            StdResult::Ok(count.unwrap_or_default().saturating_sub(1))
+        })
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+}
+
+fn load_pending_reward(deps: Deps, repo: &Repo, pr_id: u64) -> Result<PendingReward, Error> {
+    PENDING_REWARDS
+        .may_load(deps.storage, (repo.clone(), pr_id))
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        .ok_or_else(|| Error::PendingRewardNotFound {
+            repo: repo.clone(),
+            pr_id,
+        })
+}
+
+/// Validate a pull request proof, returning the repo and the pull request that it is a proof of.
+///
+/// NOTE: This does not verify the signatures of the proof, which is done by the verifier contract.
+fn validate_pull_request_proof(
+    forge: &Forge,
+    proof: &Proof,
+    config: &Config,
+) -> Result<(Repo, PullRequest), Error> {
+    ensure_forge_allowed(config, forge)?;
+
+    let context = proof.deserialize_context::<JsonExtractedParameters>()?;
+
+    let pull_request = forge.deserialize_pull_request(&context.extracted_parameters)?;
+
+    let url = proof
+        .validate_parameters(ProofKind::PullRequest, config)?
+        .url;
+
+    let (repo, pr_id) = forge
+        .parse_pull_request_url(&url)
+        .ok_or(Error::InvalidPrUrl)?;
+
+    ensure!(pull_request.number == pr_id, Error::InvalidRepo);
+
+    Ok((repo, pull_request))
+}
+
+/// A pull request that is eligible for rewards, see [`validate_pr_reward`].
+struct EligiblePr {
+    repo: Repo,
+    pull_request: PullRequest,
+    /// The linked address of the author.
+    recipient_address: Addr,
+    /// The recipients set by the author for specific reward contracts, see [`RewardRecipient`].
+    reward_recipients: Vec<RewardRecipient>,
+    repo_config: RepoConfig,
+    /// The position of this PR among the rewarded PRs of its author in the repo, starting at 1.
+    ordinal: u64,
+}
+
+impl EligiblePr {
+    /// The label configs matching the labels of the pull request, whose tier contains the ordinal of
+    /// the pull request.
+    fn label_configs(&self) -> impl Iterator<Item = &LabelConfig> {
+        self.pull_request
+            .label_ids
+            .iter()
+            .flat_map(move |label_id| {
+                self.repo_config
+                    .label_configs
+                    .iter()
+                    .filter(move |label_config| {
+                        label_config.label_id == *label_id
+                            && label_config
+                                .tier
+                                .as_ref()
+                                .is_none_or(|tier| tier.contains(self.ordinal))
+                    })
+            })
+    }
+
+    /// The recipient of the rewards from `reward_contract`, falling back to the linked address if
+    /// the author hasn't set one.
+    fn recipient(&self, querier: &QuerierWrapper, reward_contract: &Addr) -> Addr {
+        let find = |id: &RewardContractId| {
+            self.reward_recipients
+                .iter()
+                .find(|recipient| recipient.reward_contract == *id)
+                .map(|recipient| recipient.recipient.clone())
+        };
+
+        find(&RewardContractId::Address(reward_contract.clone()))
+            .or_else(|| {
+                // only query the code id if there is a recipient for a code id
+                self.reward_recipients
+                    .iter()
+                    .any(|recipient| {
+                        matches!(recipient.reward_contract, RewardContractId::CodeId(_))
+                    })
+                    .then(|| querier.query_wasm_contract_info(reward_contract).ok())
+                    .flatten()
+                    .and_then(|contract_info| {
+                        find(&RewardContractId::CodeId(contract_info.code_id))
+                    })
+            })
+            .unwrap_or_else(|| self.recipient_address.clone())
+    }
+
+    fn reward_msg(
+        &self,
+        querier: &QuerierWrapper,
+        label_config: &LabelConfig,
+        protocol_fee: Option<ProtocolFee>,
+    ) -> RewardMsg {
+        RewardMsg {
+            repo: self.repo.clone(),
+            pr_id: self.pull_request.number,
+            user_id: self.pull_request.author_id,
+            recipient_address: self.recipient(querier, &label_config.reward_contract),
+            reward_config: label_config.reward_config.clone(),
+            protocol_fee,
+            ordinal: self.ordinal,
+        }
+    }
+}
+
+/// Ensure that a pull request in `repo` is eligible for rewards, without writing any state.
+///
+/// If `unverified` is set, the policy checks that depend on data missing from `pull_request` are
+/// skipped and recorded in it, instead of failing.
+fn validate_pr_reward(
+    deps: Deps,
+    env: &Env,
+    repo: Repo,
+    pull_request: PullRequest,
+    mut unverified: Option<&mut Vec<UnverifiedCheck>>,
+) -> Result<EligiblePr, Error> {
+    ensure_not_paused(deps, env, PauseSwitch::Rewards)?;
+
+    ensure_repo_not_paused(deps, env, &repo)?;
+
+    let recipient_address = USERS
+        .may_load(deps.storage, (repo.forge.clone(), pull_request.author_id))
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        .ok_or(Error::UserNotFound(pull_request.author_id))?;
+
+    ensure!(pull_request.merged, Error::PrNotMerged);
+
+    ensure!(
+        !REWARDED_PRS.has(deps.storage, (repo.clone(), pull_request.number)),
+        Error::PrAlreadyRewarded(pull_request.number)
+    );
+
+    let repo_config = load_repo_config(deps, &repo).ok_or(Error::RepoNotLinked(repo.clone()))?;
+
+    if let Some(rate_limits) = &repo_config.rate_limits {
+        ensure_within_rate_limits(
+            deps,
+            env,
+            &repo,
+            &pull_request,
+            rate_limits,
+            unverified.as_deref_mut(),
+        )?;
+    }
+
+    if let Some(self_dealing) = &repo_config.self_dealing {
+        ensure_no_self_dealing(
+            deps,
+            &repo,
+            &pull_request,
+            &recipient_address,
+            self_dealing,
+            unverified,
+        )?;
+    }
+
+    if let Some(account_requirements) = &repo_config.account_requirements {
+        let profile = USER_PROFILES
+            .may_load(deps.storage, (repo.forge.clone(), pull_request.author_id))
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+        ensure_account_requirements(
+            env,
+            pull_request.author_id,
+            profile.as_ref(),
+            account_requirements,
+        )?;
+    }
+
+    let ordinal = USER_REPO_ORDINALS
+        .may_load(
+            deps.storage,
+            ((repo.forge.clone(), pull_request.author_id), repo.clone()),
+        )
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        .unwrap_or_default()
+        + 1;
+
+    let reward_recipients = REWARD_RECIPIENTS
+        .may_load(deps.storage, (repo.forge.clone(), pull_request.author_id))
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        .unwrap_or_default();
+
+    Ok(EligiblePr {
+        repo,
+        pull_request,
+        recipient_address,
+        reward_recipients,
+        repo_config,
+        ordinal,
+    })
+}
+
+/// Record `check` in `unverified` if it is set, otherwise fail with `error`. See
+/// [`validate_pr_reward`].
+fn skip_unverified(
+    unverified: Option<&mut Vec<UnverifiedCheck>>,
+    check: UnverifiedCheck,
+    error: Error,
+) -> Result<(), Error> {
+    let unverified = unverified.ok_or(error)?;
+
+    unverified.push(check);
+
+    Ok(())
+}
+
+/// Ensure that `pull_request` is not a PR of an admin of `repo` rewarding themselves, according to
+/// `policy`.
+fn ensure_no_self_dealing(
+    deps: Deps,
+    repo: &Repo,
+    pull_request: &PullRequest,
+    recipient_address: &Addr,
+    policy: &SelfDealingPolicy,
+    mut unverified: Option<&mut Vec<UnverifiedCheck>>,
+) -> Result<(), Error> {
+    if policy.reject_self_merged {
+        match pull_request.merged_by_id {
+            Some(merged_by_id) => {
+                ensure!(merged_by_id != pull_request.author_id, Error::SelfMergedPr);
+            }
+            None => skip_unverified(
+                unverified.as_deref_mut(),
+                UnverifiedCheck::RejectSelfMerged,
+                Error::MissingMergedBy,
+            )?,
+        }
+    }
+
+    if policy.reject_linker {
+        // the linker of the repo is its owner, which is the linker of the org for repos that are
+        // owned through their org, even if they have their own config, see `repo_owner`
+        let admin_id = if REPO_OWNERS.has(deps.storage, repo.clone()) {
+            REPO_ADMIN_IDS
+                .may_load(deps.storage, repo.clone())
+                .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        } else {
+            ORG_ADMIN_IDS
+                .may_load(deps.storage, (repo.forge.clone(), repo.org.clone()))
+                .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        };
+        let owner = repo_owner(deps, repo);
+
+        ensure!(
+            admin_id != Some(pull_request.author_id) && owner.as_ref() != Some(recipient_address),
+            Error::PrAuthorIsLinker(pull_request.author_id)
+        );
+    }
+
+    if let Some(allowed_associations) = &policy.allowed_associations {
+        match &pull_request.author_association {
+            Some(author_association) => ensure!(
+                allowed_associations.contains(author_association),
+                Error::AuthorAssociationNotAllowed(author_association.clone())
+            ),
+            None => skip_unverified(
+                unverified,
+                UnverifiedCheck::AllowedAssociations,
+                Error::MissingAuthorAssociation,
+            )?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Ensure that rewarding `pull_request` does not exceed the rate limits of `repo`.
+fn ensure_within_rate_limits(
+    deps: Deps,
+    env: &Env,
+    repo: &Repo,
+    pull_request: &PullRequest,
+    rate_limits: &RateLimits,
+    unverified: Option<&mut Vec<UnverifiedCheck>>,
+) -> Result<(), Error> {
+    let now = env.block.time;
+
+    if let Some(limit) = &rate_limits.per_user {
+        let usage = USER_RATE_LIMITS
+            .may_load(
+                deps.storage,
+                (repo.clone(), (repo.forge.clone(), pull_request.author_id)),
+            )
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+        ensure!(
+            limit.remaining(usage.as_ref(), now) > 0,
+            Error::UserRateLimited {
+                repo: repo.clone(),
+                user_id: pull_request.author_id,
+                resets_at: limit.resets_at(now),
+            }
+        );
+    }
+
+    if let Some(limit) = &rate_limits.per_repo {
+        let usage = REPO_RATE_LIMITS
+            .may_load(deps.storage, repo.clone())
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+        ensure!(
+            limit.remaining(usage.as_ref(), now) > 0,
+            Error::RepoRateLimited {
+                repo: repo.clone(),
+                resets_at: limit.resets_at(now),
+            }
+        );
+    }
+
+    if let Some(min_merge_delay) = rate_limits.min_merge_delay {
+        match (pull_request.created_at, pull_request.merged_at) {
+            (Some(created_at), Some(merged_at)) => ensure!(
+                merged_at >= created_at.plus_seconds(min_merge_delay),
+                Error::MergedTooSoon(min_merge_delay)
+            ),
+            _ => skip_unverified(
+                unverified,
+                UnverifiedCheck::MinMergeDelay,
+                Error::MissingPrTimestamps,
+            )?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Record a rewarded PR in the rate limits of its repo, see [`ensure_within_rate_limits`].
+fn record_rate_limits(deps: &mut DepsMut, env: &Env, pr: &EligiblePr) {
+    let Some(rate_limits) = &pr.repo_config.rate_limits else {
+        return;
+    };
+
+    if let Some(limit) = &rate_limits.per_user {
+        let key = (
+            pr.repo.clone(),
+            (pr.repo.forge.clone(), pr.pull_request.author_id),
+        );
+
+        let usage = USER_RATE_LIMITS
+            .may_load(deps.storage, key.clone())
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+        USER_RATE_LIMITS
+            .save(deps.storage, key, &limit.record(usage, env.block.time))
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+    }
+
+    if let Some(limit) = &rate_limits.per_repo {
+        let usage = REPO_RATE_LIMITS
+            .may_load(deps.storage, pr.repo.clone())
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+        REPO_RATE_LIMITS
+            .save(
+                deps.storage,
+                pr.repo.clone(),
+                &limit.record(usage, env.block.time),
+            )
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+    }
+}
+
+/// Refund the rate limit usage of a vetoed reward, see [`record_rate_limits`].
+fn refund_rate_limits(deps: &mut DepsMut, repo: &Repo, pending: &PendingReward) {
+    let Some(rate_limits) =
+        load_repo_config(deps.as_ref(), repo).and_then(|repo_config| repo_config.rate_limits)
+    else {
+        return;
+    };
+
+    if let Some(limit) = &rate_limits.per_user {
+        let key = (repo.clone(), (repo.forge.clone(), pending.user_id));
+
+        if let Some(usage) = USER_RATE_LIMITS
+            .may_load(deps.storage, key.clone())
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        {
+            USER_RATE_LIMITS
+                .save(deps.storage, key, &limit.refund(usage, pending.rewarded_at))
+                .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+        }
+    }
+
+    if let Some(limit) = &rate_limits.per_repo {
+        if let Some(usage) = REPO_RATE_LIMITS
+            .may_load(deps.storage, repo.clone())
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        {
+            REPO_RATE_LIMITS
+                .save(
+                    deps.storage,
+                    repo.clone(),
+                    &limit.refund(usage, pending.rewarded_at),
+                )
+                .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+        }
+    }
+}
+
+/// Run all of the validations of [`reward_pr`] without writing any state, and query the rewards
+/// of every matching label config.
+fn simulate_reward_pr(
+    deps: Deps,
+    env: &Env,
+    msg: SimulateRewardPrMsg,
+) -> StdResult<SimulateRewardPrResponse> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let mut unverified = vec![];
+
+    let pr = match msg {
+        SimulateRewardPrMsg::Proof(msg) => validate_pull_request_proof(
+            &msg.forge, &msg.proof, &config,
+        )
+        .and_then(|(repo, pull_request)| validate_pr_reward(deps, env, repo, pull_request, None)),
+        SimulateRewardPrMsg::PullRequest {
+            repo,
+            pr_id,
+            github_user_id,
+            label_ids,
+        } => validate_pr_reward(
+            deps,
+            env,
+            repo,
+            PullRequest {
+                number: pr_id,
+                author_id: github_user_id,
+                merged: true,
+                label_ids,
+                created_at: None,
+                merged_at: None,
+                merged_by_id: None,
+                author_association: None,
+                body: None,
+            },
+            Some(&mut unverified),
+        ),
+    };
+
+    let pr = match pr {
+        Ok(pr) => pr,
+        Err(error) => {
+            return Ok(SimulateRewardPrResponse {
+                error: Some(error.to_string()),
+                recipient_address: None,
+                ordinal: None,
+                flat_fee: None,
+                labels: vec![],
+                unverified,
+            })
+        }
+    };
+
+    let fee = applicable_fee(deps, &config, &pr.repo);
+    let protocol_fee = fee.and_then(FeeConfig::protocol_fee);
+
+    let labels = pr
+        .label_configs()
+        .map(|label_config| {
+            let rewards = deps.querier.query_wasm_smart::<QueryRewardsResponse>(
+                &label_config.reward_contract,
+                &RewardQueryMsg::Rewards(pr.reward_msg(
+                    &deps.querier,
+                    label_config,
+                    protocol_fee.clone(),
+                )),
+            );
+
+            LabelRewards {
+                label_id: label_config.label_id,
+                reward_contract: label_config.reward_contract.clone(),
+                error: rewards.as_ref().err().map(ToString::to_string),
+                rewards: rewards.ok(),
+            }
+        })
+        .collect();
+
+    Ok(SimulateRewardPrResponse {
+        error: None,
+        recipient_address: Some(pr.recipient_address),
+        ordinal: Some(pr.ordinal),
+        flat_fee: fee.and_then(|fee| fee.flat_fee.clone()),
+        labels,
+        unverified,
+    })
+}
+
+/// Mark a PR as rewarded, and update the stats of the user.
+fn save_rewarded_pr(
+    deps: &mut DepsMut,
+    repo: &Repo,
+    pr_id: u64,
+    user: UserKey,
+    recipient_address: &Addr,
+    status: RewardStatus,
+) {
+    REWARDED_PRS
+        .save(
+            deps.storage,
+            (repo.clone(), pr_id),
+            &RewardedPr {
+                user_id: user.1,
+                recipient_address: recipient_address.clone(),
+                status,
+                revert_pr_id: None,
+            },
+        )
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    USER_REWARDED_PRS
+        .save(deps.storage, (user.clone(), (repo.clone(), pr_id)), &())
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    USER_STATS
+        .update(deps.storage, user.clone(), |count| {
+            StdResult::Ok(count.unwrap_or_default() + 1)
+        })
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    USER_REPO_STATS
+        .update(deps.storage, (user.clone(), repo.clone()), |count| {
+            StdResult::Ok(count.unwrap_or_default() + 1)
+        })
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    USER_REPO_ORDINALS
+        .update(deps.storage, (user, repo.clone()), |count| {
+            StdResult::Ok(count.unwrap_or_default() + 1)
+        })
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+}
+
+/// Basis points in 100%.
+const MAX_BPS: u16 = 10_000;
+
+/// Charge the protocol fee for rewarding a PR, unless the repo is exempt, and tip the relayers.
+///
+/// The flat fee must be attached to the message, and is sent to the treasury. If the repo has a
+/// challenge period, the flat fee is returned to be held instead, and both the fee and the tips are
+/// only paid once the reward is finalized, see [`finalize_reward`]. The returned [`ProtocolFee`] is
+/// passed on to the reward contracts.
+fn charge_fee(
+    deps: &mut DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    pr: &EligiblePr,
+    config: &Config,
+    response: Response,
+) -> Result<(Response, Option<ProtocolFee>, Option<FlatFee>), Error> {
+    let (flat_fee, protocol_fee) = validate_fee(deps.as_ref(), info, &pr.repo, config)?;
+
+    if pr.repo_config.challenge_period.is_some() {
+        return Ok((response, protocol_fee, flat_fee));
+    }
+
+    let response = collect_fee(deps, &pr.repo, pr.pull_request.number, flat_fee, response);
+
+    let response = tip_relayers(
+        deps,
+        env,
+        &info.sender,
+        &pr.repo,
+        pr.pull_request.number,
+        pr.pull_request.author_id,
+        response,
+    );
+
+    Ok((response, protocol_fee, None))
+}
+
+/// Ensure that the flat fee for rewarding a PR is attached to the message, returning it along with
+/// the [`ProtocolFee`] that is passed on to the reward contracts. The flat fee is only sent to the
+/// treasury by [`collect_fee`].
+fn validate_fee(
+    deps: Deps,
+    info: &MessageInfo,
+    repo: &Repo,
+    config: &Config,
+) -> Result<(Option<FlatFee>, Option<ProtocolFee>), Error> {
+    let Some(fee) = applicable_fee(deps, config, repo) else {
+        return Ok((None, None));
+    };
+
+    let flat_fee = match &fee.flat_fee {
+        Some(flat_fee) => {
+            ensure!(
+                info.funds == [flat_fee.clone()],
+                Error::InvalidFeeFunds(flat_fee.clone())
+            );
+
+            Some(FlatFee {
+                fee: flat_fee.clone(),
+                treasury: fee.treasury.clone(),
+            })
+        }
+        None => None,
+    };
+
+    Ok((flat_fee, fee.protocol_fee()))
+}
+
+/// Send the flat fee for rewarding a PR to the treasury, if there is one.
+fn collect_fee(
+    deps: &mut DepsMut,
+    repo: &Repo,
+    pr_id: u64,
+    flat_fee: Option<FlatFee>,
+    response: Response,
+) -> Response {
+    let Some(FlatFee { fee, treasury }) = flat_fee else {
+        return response;
+    };
+
+    ACCUMULATED_FEES
+        .update(deps.storage, fee.denom.clone(), |total| {
+            StdResult::Ok(total.unwrap_or_default() + fee.amount)
+        })
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    response
+        .add_message(BankMsg::Send {
+            to_address: treasury.to_string(),
+            amount: vec![fee.clone()],
+        })
+        .add_event(FeeCollectedEvent {
+            repo: repo.clone(),
+            pr_id,
+            fee,
+            treasury,
+        })
+}
+
+/// The fee config that applies to `repo`, or [`None`] if there is no fee or the repo is exempt.
+fn applicable_fee<'a>(deps: Deps, config: &'a Config, repo: &Repo) -> Option<&'a FeeConfig> {
+    config
+        .fee
+        .as_ref()
+        .filter(|_| !FEE_EXEMPT_REPOS.has(deps.storage, repo.clone()))
+}
+
+fn set_fee_exemption(
+    deps: &mut DepsMut,
+    info: &MessageInfo,
+    msg: SetFeeExemptionMsg,
+    config: &Config,
+) -> Result<Response, Error> {
+    ensure!(info.sender == config.owner, Error::NotOwner);
+
+    if msg.exempt {
+        FEE_EXEMPT_REPOS
+            .save(deps.storage, msg.repo.clone(), &())
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+    } else {
+        FEE_EXEMPT_REPOS.remove(deps.storage, msg.repo.clone());
+    }
+
+    Ok(Response::new().add_event(UpdateFeeExemptionEvent {
+        repo: msg.repo,
+        exempt: msg.exempt,
+    }))
+}
+
+fn fund_tip_pool(
+    deps: &mut DepsMut,
+    info: &MessageInfo,
+    msg: FundTipPoolMsg,
+) -> Result<Response, Error> {
+    let FundTipPoolMsg { repo } = msg;
+
+    ensure!(
+        load_repo_config(deps.as_ref(), &repo).is_some(),
+        Error::RepoNotLinked(repo.clone())
+    );
+    ensure!(
+        info.funds.iter().any(|coin| !coin.amount.is_zero()),
+        Error::NoTipPoolFunds
+    );
+
+    let mut response = Response::new();
+
+    for coin in info.funds.iter().filter(|coin| !coin.amount.is_zero()) {
+        TIP_POOLS
+            .update(deps.storage, (repo.clone(), coin.denom.clone()), |total| {
+                StdResult::Ok(total.unwrap_or_default() + coin.amount)
+            })
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+        response = response.add_event(FundTipPoolEvent {
+            repo: repo.clone(),
+            sender: info.sender.clone(),
+            amount: coin.clone(),
+        });
+    }
+
+    Ok(response)
+}
+
+fn withdraw_tip_pool(
+    deps: &mut DepsMut,
+    info: &MessageInfo,
+    msg: WithdrawTipPoolMsg,
+) -> Result<Response, Error> {
+    ensure_repo_role(deps.as_ref(), &msg.repo, &info.sender, RepoRole::Treasurer)?;
+
+    let key = (msg.repo.clone(), msg.amount.denom.clone());
+
+    let available = TIP_POOLS
+        .may_load(deps.storage, key.clone())
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        .unwrap_or_default();
+
+    ensure!(
+        available >= msg.amount.amount,
+        Error::InsufficientTipPool(Coin {
+            denom: msg.amount.denom,
+            amount: available,
+        })
+    );
+
+    TIP_POOLS
+        .save(deps.storage, key, &(available - msg.amount.amount))
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![msg.amount.clone()],
+        })
+        .add_event(WithdrawTipPoolEvent {
+            repo: msg.repo,
+            recipient: info.sender.clone(),
+            amount: msg.amount,
+        }))
+}
+
+/// Tip the sender of a `RewardPr`, and the relayer that linked the account of the author if there
+/// is one, from the tip pool of the repo, see [`RelayerTips`]. Tips over the per block limit of the
+/// repo or the remaining funds of its tip pool are skipped.
+fn tip_relayers(
+    deps: &mut DepsMut,
+    env: &Env,
+    sender: &Addr,
+    repo: &Repo,
+    pr_id: u64,
+    author_id: u64,
+    mut response: Response,
+) -> Response {
+    let Some(relayer_tips) =
+        load_repo_config(deps.as_ref(), repo).and_then(|repo_config| repo_config.relayer_tips)
+    else {
+        return response;
+    };
+
+    let user = (repo.forge.clone(), author_id);
+
+    let link_relayer = relayer_tips.link_account.as_ref().and_then(|tip| {
+        LINK_RELAYERS
+            .may_load(deps.storage, user.clone())
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+            .map(|relayer| (TipKind::LinkAccount, relayer, tip))
+    });
+
+    let tips = relayer_tips
+        .reward_pr
+        .as_ref()
+        .map(|tip| (TipKind::RewardPr, sender.clone(), tip))
+        .into_iter()
+        .chain(link_relayer);
+
+    for (kind, relayer, tip) in tips {
+        if !take_tip(deps, env, repo, tip, relayer_tips.max_per_block) {
+            continue;
+        }
+
+        if kind == TipKind::LinkAccount {
+            LINK_RELAYERS.remove(deps.storage, user.clone());
+        }
+
+        response = response
+            .add_message(BankMsg::Send {
+                to_address: relayer.to_string(),
+                amount: vec![tip.clone()],
+            })
+            .add_event(RelayerTipEvent {
+                repo: repo.clone(),
+                pr_id,
+                kind,
+                relayer,
+                tip: tip.clone(),
+            });
+    }
+
+    response
+}
+
+/// Deduct `tip` from the tip pool of `repo` and count it towards the tips of the current block,
+/// unless either would be exceeded.
+fn take_tip(deps: &mut DepsMut, env: &Env, repo: &Repo, tip: &Coin, max_per_block: u32) -> bool {
+    let count = TIP_USAGE
+        .may_load(deps.storage, repo.clone())
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        .filter(|usage| usage.height == env.block.height)
+        .map_or(0, |usage| usage.count);
+
+    let available = TIP_POOLS
+        .may_load(deps.storage, (repo.clone(), tip.denom.clone()))
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        .unwrap_or_default();
+
+    if count >= max_per_block || available < tip.amount {
+        return false;
+    }
+
+    TIP_POOLS
+        .save(
+            deps.storage,
+            (repo.clone(), tip.denom.clone()),
+            &(available - tip.amount),
+        )
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    TIP_USAGE
+        .save(
+            deps.storage,
+            repo.clone(),
+            &TipUsage {
+                height: env.block.height,
+                count: count + 1,
+            },
+        )
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    true
+}
+
+fn register_reward_contract(
+    deps: &mut DepsMut,
+    info: &MessageInfo,
+    msg: RegisterRewardContractMsg,
+    config: &Config,
+) -> Result<Response, Error> {
+    ensure!(info.sender == config.owner, Error::NotOwner);
+
+    let registered = match &msg.id {
+        RewardContractId::CodeId(code_id) => REWARD_CODE_IDS.has(deps.storage, *code_id),
+        RewardContractId::Address(address) => REWARD_CONTRACTS.has(deps.storage, address.clone()),
+    };
+
+    ensure!(!registered, Error::RewardContractAlreadyRegistered(msg.id));
+
+    match &msg.id {
+        RewardContractId::CodeId(code_id) => {
+            deps.querier
+                .query_wasm_code_info(*code_id)
+                .map_err(|_| Error::CodeNotFound(*code_id))?;
+
+            REWARD_CODE_IDS
+                .save(deps.storage, *code_id, &msg.metadata)
+                .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+        }
+        RewardContractId::Address(address) => {
+            deps.querier
+                .query_wasm_contract_info(address)
+                .map_err(|_| Error::ContractNotFound(address.clone()))?;
+
+            REWARD_CONTRACTS
+                .save(deps.storage, address.clone(), &msg.metadata)
+                .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+        }
+    }
+
+    Ok(Response::new().add_event(UpdateRewardRegistryEvent {
+        reward_contract: msg.id,
+        metadata: Some(msg.metadata),
+    }))
+}
+
+/// Code ids are listed before contracts, such that a page starting after a contract only contains
+/// contracts.
+fn query_reward_registry(
+    deps: Deps,
+    start_after: Option<RewardContractId>,
+    limit: Option<u32>,
+) -> StdResult<Vec<RegisteredRewardContract>> {
+    let (code_ids_start, contracts_start) = match start_after {
+        None => (None, None),
+        Some(RewardContractId::CodeId(code_id)) => (Some(Bound::exclusive(code_id)), None),
+        Some(RewardContractId::Address(address)) => (
+            Some(Bound::exclusive(u64::MAX)),
+            Some(Bound::exclusive(address)),
+        ),
+    };
+
+    REWARD_CODE_IDS
+        .range(
+            deps.storage,
+            code_ids_start,
+            None,
+            cosmwasm_std::Order::Ascending,
+        )
+        .map(|res| {
+            res.map(|(code_id, metadata)| RegisteredRewardContract {
+                id: RewardContractId::CodeId(code_id),
+                metadata,
+            })
+        })
+        .chain(
+            REWARD_CONTRACTS
+                .range(
+                    deps.storage,
+                    contracts_start,
+                    None,
+                    cosmwasm_std::Order::Ascending,
+                )
+                .map(|res| {
+                    res.map(|(address, metadata)| RegisteredRewardContract {
+                        id: RewardContractId::Address(address),
+                        metadata,
+                    })
+                }),
+        )
+        .take(pagination_limit(limit))
+        .collect()
+}
+
+fn deregister_reward_contract(
+    deps: &mut DepsMut,
+    info: &MessageInfo,
+    msg: DeregisterRewardContractMsg,
+    config: &Config,
+) -> Result<Response, Error> {
+    ensure!(info.sender == config.owner, Error::NotOwner);
+
+    match &msg.id {
+        RewardContractId::CodeId(code_id) => REWARD_CODE_IDS.remove(deps.storage, *code_id),
+        RewardContractId::Address(address) => {
+            REWARD_CONTRACTS.remove(deps.storage, address.clone());
+        }
+    }
+
+    Ok(Response::new().add_event(UpdateRewardRegistryEvent {
+        reward_contract: msg.id,
+        metadata: None,
+    }))
+}
+
+/// The reply id of the revoke submessages dispatched in [`revert_pr`].
+pub const REVOKE_REPLY_ID: u64 = 2;
+
+/// The payload of the revoke submessages dispatched in [`revert_pr`].
+#[cw_serde]
+struct RewardDispatch {
+    repo: Repo,
+    pr_id: u64,
+    label_id: u64,
+    reward_contract: Addr,
+}
+
+/// Revokes are dispatched with [`SubMsg::reply_on_error`], such that a single failing reward
+/// contract does not prevent the PR from being marked as reverted. The failure is recorded in a
+/// [`RevokeFailedEvent`].
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+#[allow(clippy::needless_pass_by_value)]
+pub fn reply(_deps: DepsMut, _env: Env, reply: Reply) -> Result<Response, Error> {
+    ensure!(reply.id == REVOKE_REPLY_ID, Error::UnknownReplyId(reply.id));
+
+    let SubMsgResult::Err(error) = reply.result else {
+        return Ok(Response::default());
+    };
+
+    let dispatched =
+        from_json::<RewardDispatch>(&reply.payload).expect(SERIALIZATION_INFALLIBLE_MSG);
+
+    Ok(Response::new().add_event(RevokeFailedEvent {
+        repo: dispatched.repo,
+        pr_id: dispatched.pr_id,
+        label_id: dispatched.label_id,
+        reward_contract: dispatched.reward_contract,
+        error,
+    }))
+}
+
+fn pause(
+    deps: &mut DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    msg: PauseMsg,
+    config: &Config,
+) -> Result<Response, Error> {
+    let is_owner = info.sender == config.owner;
+
+    ensure!(
+        is_owner || config.guardian.as_ref() == Some(&info.sender),
+        Error::NotOwnerOrGuardian
+    );
+
+    let mut pause = PAUSE
+        .may_load(deps.storage)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        .unwrap_or_default();
+
+    pause.clear_expired(env.block.height);
+
+    let new_pause = SwitchPause {
+        expires_at_height: msg.expires_at_height,
+    };
+
+    for (switch, paused) in [
+        (PauseSwitch::LinkAccounts, msg.link_accounts),
+        (PauseSwitch::LinkRepos, msg.link_repos),
+        (PauseSwitch::Rewards, msg.rewards),
+    ] {
+        if !paused {
+            continue;
+        }
+
+        let current_pause = pause.switch_mut(switch);
+
+        ensure!(
+            is_owner || current_pause.is_none_or(|current| !new_pause.ends_before(&current)),
+            Error::PauseShortened(switch)
+        );
+
+        *current_pause = Some(new_pause);
+    }
+
+    PAUSE
+        .save(deps.storage, &pause)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    Ok(Response::new().add_event(UpdatePauseEvent {
+        sender: info.sender.clone(),
+        pause,
+    }))
+}
+
+fn unpause(
+    deps: &mut DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    msg: UnpauseMsg,
+    config: &Config,
+) -> Result<Response, Error> {
+    ensure!(info.sender == config.owner, Error::NotOwner);
+
+    let mut pause = PAUSE
+        .may_load(deps.storage)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        .unwrap_or_default();
+
+    pause.clear_expired(env.block.height);
+
+    for (switch, unpaused) in [
+        (PauseSwitch::LinkAccounts, msg.link_accounts),
+        (PauseSwitch::LinkRepos, msg.link_repos),
+        (PauseSwitch::Rewards, msg.rewards),
+    ] {
+        if unpaused {
+            *pause.switch_mut(switch) = None;
+        }
+    }
+
+    PAUSE
+        .save(deps.storage, &pause)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    Ok(Response::new().add_event(UpdatePauseEvent {
+        sender: info.sender.clone(),
+        pause,
+    }))
+}
+
+fn pause_repo(
+    deps: &mut DepsMut,
+    info: &MessageInfo,
+    msg: PauseRepoMsg,
+) -> Result<Response, Error> {
+    ensure_repo_role(deps.as_ref(), &msg.repo, &info.sender, RepoRole::Pauser)?;
+
+    let pause = RepoPause {
+        expires_at_height: msg.expires_at_height,
+    };
+
+    REPO_PAUSES
+        .save(deps.storage, msg.repo.clone(), &pause)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG);
+
+    Ok(Response::new().add_event(UpdateRepoPauseEvent {
+        repo: msg.repo,
+        sender: info.sender.clone(),
+        pause: Some(pause),
+    }))
+}
+
+fn unpause_repo(
+    deps: &mut DepsMut,
+    info: &MessageInfo,
+    msg: UnpauseRepoMsg,
+) -> Result<Response, Error> {
+    ensure_repo_role(deps.as_ref(), &msg.repo, &info.sender, RepoRole::Pauser)?;
+
+    REPO_PAUSES.remove(deps.storage, msg.repo.clone());
+
+    Ok(Response::new().add_event(UpdateRepoPauseEvent {
+        repo: msg.repo,
+        sender: info.sender.clone(),
+        pause: None,
+    }))
+}
+
+/// Ensures that rewards of `repo` are not paused at the current height.
+fn ensure_repo_not_paused(deps: Deps, env: &Env, repo: &Repo) -> Result<(), Error> {
+    ensure!(
+        !REPO_PAUSES
+            .may_load(deps.storage, repo.clone())
+            .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+            .is_some_and(|pause| pause.is_active(env.block.height)),
+        Error::RepoPaused(repo.clone())
+    );
+
+    Ok(())
+}
+
+/// Ensures that `switch` is not paused at the current height.
+fn ensure_not_paused(deps: Deps, env: &Env, switch: PauseSwitch) -> Result<(), Error> {
+    let paused = PAUSE
+        .may_load(deps.storage)
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        .is_some_and(|pause| pause.is_paused(switch, env.block.height));
+
+    ensure!(!paused, Error::Paused(switch));
+
+    Ok(())
+}
+
+/// The amount of entries returned by paginated queries if no limit is provided.
+const DEFAULT_LIMIT: u32 = 30;
+/// The maximum amount of entries returned by paginated queries.
+const MAX_LIMIT: u32 = 100;
+
+fn pagination_limit(limit: Option<u32>) -> usize {
+    limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize
+}
+
+/// The owner of the repo, falling back to the owner of its org if the repo has no owner of its own.
+fn repo_owner(deps: Deps, repo: &Repo) -> Option<Addr> {
+    REPO_OWNERS
+        .may_load(deps.storage, repo.clone())
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        .or_else(|| {
+            ORG_OWNERS
+                .may_load(deps.storage, (repo.forge.clone(), repo.org.clone()))
+                .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        })
+}
+
+/// Ensures that `sender` is the owner of the repo, see [`repo_owner`].
+fn ensure_repo_owner(deps: Deps, repo: &Repo, sender: &Addr) -> Result<(), Error> {
+    let owner = repo_owner(deps, repo).ok_or(Error::RepoNotLinked(repo.clone()))?;
+
+    ensure!(owner == sender, Error::NotRepoOwner(repo.clone()));
+
+    Ok(())
+}
+
+/// Ensures that `sender` is either the owner of the repo, or has been granted `role` by the owner.
+fn ensure_repo_role(deps: Deps, repo: &Repo, sender: &Addr, role: RepoRole) -> Result<(), Error> {
+    let owner = repo_owner(deps, repo).ok_or(Error::RepoNotLinked(repo.clone()))?;
+
+    if owner == sender {
+        return Ok(());
+    }
+
+    let roles = REPO_ROLES
+        .may_load(deps.storage, (repo.clone(), sender.clone()))
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        .unwrap_or_default();
+
+    ensure!(
+        roles.contains(&role),
+        Error::MissingRepoRole(repo.clone(), role)
+    );
+
+    Ok(())
+}
+
+/// Ensure that the reward contract of every label config in `repo_config` is registered (if the
+/// reward registry is enabled) and accepts its reward config for `scope`, see
+/// [`RewardQueryMsg::ValidateRewardConfig`].
+fn validate_repo_config(
+    deps: Deps,
+    config: &Config,
+    scope: &RewardScope,
+    repo_config: &RepoConfig,
+) -> Result<(), Error> {
+    if let Some(rate_limits) = &repo_config.rate_limits {
+        ensure!(
+            rate_limits
+                .per_user
+                .iter()
+                .chain(&rate_limits.per_repo)
+                .all(|limit| limit.period > 0),
+            Error::InvalidRateLimit
+        );
+    }
+
+    if let Some(challenge_period) = &repo_config.challenge_period {
+        ensure!(challenge_period.blocks > 0, Error::InvalidChallengePeriod);
+    }
+
+    if let Some(relayer_tips) = &repo_config.relayer_tips {
+        ensure!(
+            relayer_tips.max_per_block > 0
+                && relayer_tips
+                    .reward_pr
+                    .iter()
+                    .chain(&relayer_tips.link_account)
+                    .all(|tip| !tip.amount.is_zero()),
+            Error::InvalidRelayerTips
+        );
+    }
+
+    for label_config in &repo_config.label_configs {
+        ensure!(
+            label_config.tier.as_ref().is_none_or(|tier| {
+                tier.min_ordinal.unwrap_or(u64::MIN) <= tier.max_ordinal.unwrap_or(u64::MAX)
+            }),
+            Error::InvalidTier(label_config.label_id)
+        );
+
+        if let Some(approval) = &label_config.approval {
+            let mut approvers = approval.approvers.iter().collect::<Vec<_>>();
+            approvers.sort();
+            approvers.dedup();
+
+            ensure!(
+                approval.threshold > 0
+                    && approval.threshold as usize <= approvers.len()
+                    && approvers.len() == approval.approvers.len()
+                    && approval.expires_after_blocks > 0,
+                Error::InvalidApprovalRequirement(label_config.label_id)
+            );
+        }
+
+        if config.reward_registry_enabled {
+            ensure_reward_contract_registered(deps, &label_config.reward_contract)?;
+        }
+
+        deps.querier
+            .query_wasm_smart::<Empty>(
+                &label_config.reward_contract,
+                &RewardQueryMsg::ValidateRewardConfig(ValidateRewardConfigMsg {
+                    scope: scope.clone(),
+                    reward_config: label_config.reward_config.clone(),
+                }),
+            )
+            .map_err(|error| Error::InvalidRewardConfig {
+                label_id: label_config.label_id,
+                reward_contract: label_config.reward_contract.clone(),
+                error: error.to_string(),
+            })?;
+    }
+
+    Ok(())
+}
+
+/// Ensure that `reward_contract` is in the reward registry, either individually or by its code id.
+fn ensure_reward_contract_registered(deps: Deps, reward_contract: &Addr) -> Result<(), Error> {
+    if REWARD_CONTRACTS.has(deps.storage, reward_contract.clone()) {
+        return Ok(());
+    }
+
+    let code_id = deps
+        .querier
+        .query_wasm_contract_info(reward_contract)
+        .map_err(|_| Error::ContractNotFound(reward_contract.clone()))?
+        .code_id;
+
+    ensure!(
+        REWARD_CODE_IDS.has(deps.storage, code_id),
+        Error::RewardContractNotRegistered(reward_contract.clone())
+    );
+
+    Ok(())
+}
+
+/// Load the config for a repo, falling back to the default config of the repo's org if the repo has
+/// not been linked individually.
+fn load_repo_config(deps: Deps, repo: &Repo) -> Option<RepoConfig> {
+    REPOS
+        .may_load(deps.storage, repo.clone())
+        .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        .or_else(|| {
+            ORGS.may_load(deps.storage, (repo.forge.clone(), repo.org.clone()))
+                .expect(STORAGE_ACCESS_INFALLIBLE_MSG)
+        })
+}
+
+/// Ensures that the forge is either github or has been allowlisted by the owner.
+fn ensure_forge_allowed(config: &Config, forge: &Forge) -> Result<(), Error> {
+    ensure!(
+        forge.is_github() || config.allowed_forges.contains(forge),
+        Error::ForgeNotAllowed(forge.clone())
+    );
+
+    Ok(())
+}
+
+#[must_use]
+pub fn verify_proof_sub_msg(contract: &Addr, proof: Proof) -> SubMsg {
+    SubMsg::reply_never(WasmMsg::Execute {
+        contract_addr: contract.to_string(),
+        msg: to_json_binary(&VerifierMsg::VerifyProof(VerifyProofMsg { proof }))
+            .expect(SERIALIZATION_INFALLIBLE_MSG),
+        funds: vec![],
+    })
+}
+
+/// Ensures that the proof has not been used yet, and saves the checksum of it if it is new.
+fn ensure_new_proof(deps: &mut DepsMut, proof: &Proof) -> Result<(), Error> {
+    let proof_hash = sha256(serde_json_wasm::to_vec(&proof).expect(SERIALIZATION_INFALLIBLE_MSG));
+
+    if EXISTING_PROOFS.has(deps.storage, proof_hash.clone()) {
+        return Err(Error::ProofAlreadySubmitted);
+    }
+
+    EXISTING_PROOFS
+        .save(deps.storage, proof_hash.clone(), &())
+        .expect(SERIALIZATION_INFALLIBLE_MSG);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{message_info, mock_dependencies};
+
+    use super::*;
+    use crate::{
+        models::{
+            github::{AuthorAssociation, Owner, Permissions, PrBody, User, UserRepoBody},
+            reclaim::{ClaimInfo, CompleteClaimData, Context, Parameters, SignedClaim},
+        },
+        state::{ChallengePeriod, Pause, RateLimit, RelayerTips, RewardContractMetadata, Tier},
+    };
+
+    /// An unsigned proof of `url` with the response matches of `kind`.
+    fn proof(kind: ProofKind, url: &str, extracted_parameters: &impl Serialize) -> Proof {
+        Proof {
+            claim_info: ClaimInfo {
+                provider: "http".to_owned(),
+                parameters: to_json_string(&Parameters {
+                    body: String::new(),
+                    method: "GET".to_owned(),
+                    response_matches: kind.expected_response_matches(),
+                    response_redactions: vec![],
+                    url: url.to_owned(),
+                })
+                .unwrap(),
+                context: to_json_string(&Context {
+                    extracted_parameters,
+                })
+                .unwrap(),
+            },
+            signed_claim: SignedClaim {
+                claim: CompleteClaimData {
+                    identifier: "0x00".to_owned(),
+                    owner: "0x00".to_owned(),
+                    epoch: 1,
+                    timestamp_s: 0,
+                },
+                signatures: vec![],
+            },
+        }
+    }
+
+    /// An unsigned proof of `url` whose extracted json is `body`.
+    fn json_proof(kind: ProofKind, url: &str, body: &impl Serialize) -> Proof {
+        proof(
+            kind,
+            url,
+            &JsonExtractedParameters {
+                json: to_json_string(body).unwrap(),
+            },
+        )
+    }
+
+    fn empty_repo_config() -> RepoConfig {
+        RepoConfig {
+            label_configs: vec![],
+            rate_limits: None,
+            account_requirements: None,
+            self_dealing: None,
+            challenge_period: None,
+            relayer_tips: None,
+        }
+    }
+
+    #[test]
+    fn migrate_from_legacy_version() {
+        let mut deps = mock_dependencies();
+        let env = cosmwasm_std::testing::mock_env();
+
+        let owner = deps.api.addr_make("owner");
+        let verifier = deps.api.addr_make("verifier");
+        let user = deps.api.addr_make("user");
+        let reward_contract = deps.api.addr_make("reward");
+
+        let commitment = |value| Commitment {
+            commitment_key: sha256(b"secret").into(),
+            commitment_height: 1,
+            value,
+        };
+        let repo_config = RepoConfig {
+            label_configs: vec![LabelConfig {
+                label_id: 7,
+                reward_contract: reward_contract.clone(),
+                reward_config: "{}".to_owned(),
+                tier: None,
+                approval: None,
+            }],
+            ..empty_repo_config()
+        };
+
+        // the state of versions without forge support
+        deps.as_mut().storage.set(
+            CONFIG.as_slice(),
+            format!(
+                r#"{{"verifier_address":"{verifier}","commitment_delay_min_height":0,"commitment_delay_max_height":1}}"#
+            )
+            .as_bytes(),
+        );
+        legacy::USERS.save(deps.as_mut().storage, 1, &user).unwrap();
+        legacy::USER_COMMITMENTS
+            .save(deps.as_mut().storage, 2, &commitment(user.clone()))
+            .unwrap();
+        legacy::REPO_COMMITMENTS
+            .save(
+                deps.as_mut().storage,
+                ("unionlabs".to_owned(), "other".to_owned()),
+                &Commitment {
+                    commitment_key: sha256(b"secret").into(),
+                    commitment_height: 1,
+                    value: empty_repo_config(),
+                },
+            )
+            .unwrap();
+        deps.as_mut().storage.set(
+            &legacy::REPOS.key(("unionlabs".to_owned(), "union".to_owned())),
+            format!(
+                r#"{{"label_configs":[{{"label_id":7,"reward_contract":"{reward_contract}","reward_config":"{{}}"}}]}}"#
+            )
+            .as_bytes(),
+        );
+        legacy::REWARDED_PRS
+            .save(
+                deps.as_mut().storage,
+                ("unionlabs".to_owned(), "union".to_owned(), 3),
+                &(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            migrate(deps.as_mut(), env.clone(), MigrateMsg::default()),
+            Err(Error::MissingMigrationOwner)
+        );
+
+        migrate(
+            deps.as_mut(),
+            env.clone(),
+            MigrateMsg {
+                owner: Some(owner.clone()),
+            },
+        )
+        .unwrap();
+
+        let config = CONFIG.load(deps.as_ref().storage).unwrap();
+        assert_eq!(config.owner, owner);
+        assert_eq!(config.verifier_address, verifier);
+
+        let repo = Repo::from(("unionlabs", "union"));
+        assert_eq!(
+            USERS
+                .load(deps.as_ref().storage, (Forge::Github, 1))
+                .unwrap(),
+            user
+        );
+        assert_eq!(
+            USERS
+                .idx
+                .address
+                .prefix(user.clone())
+                .keys(
+                    deps.as_ref().storage,
+                    None,
+                    None,
+                    cosmwasm_std::Order::Ascending
+                )
+                .collect::<StdResult<Vec<_>>>()
+                .unwrap(),
+            vec![(Forge::Github, 1)]
+        );
+        assert_eq!(
+            USER_COMMITMENTS
+                .load(deps.as_ref().storage, (Forge::Github, 2))
+                .unwrap(),
+            commitment(user)
+        );
+        assert!(REPO_COMMITMENTS.is_empty(deps.as_ref().storage));
+        assert_eq!(load_repo_config(deps.as_ref(), &repo), Some(repo_config));
+        assert_eq!(
+            REWARDED_PRS
+                .load(deps.as_ref().storage, (repo.clone(), 3))
+                .unwrap()
+                .status,
+            RewardStatus::Legacy
+        );
+
+        // none of the legacy entries are left behind, the maps share their namespaces with the
+        // re-keyed entries
+        assert!(!legacy::USERS.has(deps.as_ref().storage, 1));
+        assert!(!legacy::USER_COMMITMENTS.has(deps.as_ref().storage, 2));
+        assert!(!legacy::REPOS.has(
+            deps.as_ref().storage,
+            ("unionlabs".to_owned(), "union".to_owned())
+        ));
+        assert!(!legacy::REWARDED_PRS.has(
+            deps.as_ref().storage,
+            ("unionlabs".to_owned(), "union".to_owned(), 3)
+        ));
+
+        // migrating again does not touch the migrated state
+        migrate(deps.as_mut(), env, MigrateMsg::default()).unwrap();
+        assert_eq!(CONFIG.load(deps.as_ref().storage).unwrap(), config);
+        assert!(REWARDED_PRS.has(deps.as_ref().storage, (repo, 3)));
+    }
+
+    #[test]
+    fn link_repo_admin_id() {
+        let mut deps = mock_dependencies();
+        let env = cosmwasm_std::testing::mock_env();
+
+        let config = Config {
+            owner: deps.api.addr_make("owner"),
+            verifier_address: deps.api.addr_make("verifier"),
+            commitment_delay_min_height: 0,
+            commitment_delay_max_height: 1,
+            allowed_forges: vec![],
+            allowed_provider_hashes: vec![],
+            guardian: None,
+            fee: None,
+            reward_registry_enabled: false,
+            account_requirements: None,
+        };
+        let linker = deps.api.addr_make("linker");
+        let frontrunner = deps.api.addr_make("frontrunner");
+
+        let repo = Repo::from(("unionlabs", "union"));
+        let secret = Binary::from(b"secret");
+
+        REPO_COMMITMENTS
+            .save(
+                deps.as_mut().storage,
+                repo.clone(),
+                &Commitment {
+                    commitment_key: owner_commitment_key(&secret, &linker).into(),
+                    commitment_height: env.block.height,
+                    value: empty_repo_config(),
+                },
+            )
+            .unwrap();
+
+        // the repo is owned by an org, so the repo proof does not identify the admin
+        let repo_proof = json_proof(
+            ProofKind::RepoAdmin,
+            &Forge::Github.repo_url("unionlabs", "union"),
+            &UserRepoBody {
+                permissions: Permissions { admin: true },
+                owner: Some(Owner {
+                    id: 100,
+                    ty: Some("Organization".to_owned()),
+                }),
+            },
+        );
+        let user_proof = |owner: &str| {
+            let mut proof = proof(
+                ProofKind::User,
+                &Forge::Github.user_url(),
+                &UserExtractedParameters {
+                    id: "7".to_owned(),
+                    created_at: None,
+                    public_repos: None,
+                    followers: None,
+                },
+            );
+            owner.clone_into(&mut proof.signed_claim.claim.owner);
+            proof
+        };
+
+        let mut link = |sender: &Addr, admin_user_proof| {
+            // the proofs of failed attempts are not stored, since failed messages are reverted
+            EXISTING_PROOFS.clear(deps.as_mut().storage);
+
+            link_repo(
+                &mut deps.as_mut(),
+                &env,
+                &message_info(sender, &[]),
+                LinkRepoMsg {
+                    repo: repo.clone(),
+                    config: empty_repo_config(),
+                    secret: secret.clone(),
+                    repo_admin_permissions_proof: repo_proof.clone(),
+                    admin_user_proof,
+                },
+                &config,
+            )
+        };
+
+        assert_eq!(link(&linker, None), Err(Error::MissingAdminUserProof));
+        assert_eq!(
+            link(&linker, Some(user_proof("0x01"))),
+            Err(Error::AdminUserProofOwnerMismatch)
+        );
+        // the commitment is bound to the linker, so the revealed secret can't be replayed
+        assert_eq!(
+            link(&frontrunner, Some(user_proof("0x00"))),
+            Err(Error::InvalidCommitment)
+        );
+
+        let response = link(&linker, Some(user_proof("0x00"))).unwrap();
+        assert_eq!(response.messages.len(), 2);
+
+        assert_eq!(REPO_ADMIN_IDS.load(deps.as_ref().storage, repo).unwrap(), 7);
+    }
+
+    #[test]
+    fn link_org_and_fallback() {
+        let mut deps = mock_dependencies();
+        let env = cosmwasm_std::testing::mock_env();
+
+        let config = Config {
+            owner: deps.api.addr_make("owner"),
+            verifier_address: deps.api.addr_make("verifier"),
+            commitment_delay_min_height: 0,
+            commitment_delay_max_height: 1,
+            allowed_forges: vec![],
+            allowed_provider_hashes: vec![],
+            guardian: None,
+            fee: None,
+            reward_registry_enabled: false,
+            account_requirements: None,
+        };
+
+        let org_config = RepoConfig {
+            rate_limits: Some(RateLimits {
+                per_user: Some(RateLimit {
+                    max_prs: 1,
+                    period: 60,
+                }),
+                per_repo: None,
+                min_merge_delay: None,
+            }),
+            ..empty_repo_config()
+        };
+        let secret = Binary::from(b"secret");
+        let linker = deps.api.addr_make("linker");
+        let delegate = deps.api.addr_make("delegate");
+
+        ORG_COMMITMENTS
+            .save(
+                deps.as_mut().storage,
+                (Forge::Github, "unionlabs".to_owned()),
+                &Commitment {
+                    commitment_key: owner_commitment_key(&secret, &linker).into(),
+                    commitment_height: env.block.height,
+                    value: org_config.clone(),
+                },
+            )
+            .unwrap();
+
+        // roles granted by a previous owner of the org, or by the owners of other repos
+        let repo = Repo::from(("unionlabs", "union"));
+        let owned_repo = Repo::from(("unionlabs", "owned"));
+        let other_repo = Repo::from(("other", "union"));
+        REPO_OWNERS
+            .save(deps.as_mut().storage, owned_repo.clone(), &delegate)
+            .unwrap();
+        for repo in [&repo, &owned_repo, &other_repo] {
+            REPO_ROLES
+                .save(
+                    deps.as_mut().storage,
+                    (repo.clone(), delegate.clone()),
+                    &vec![RepoRole::Pauser],
+                )
+                .unwrap();
+        }
+
+        let link = |org: &str, role| LinkOrgMsg {
+            forge: Forge::Github,
+            org: "unionlabs".to_owned(),
+            config: org_config.clone(),
+            secret: secret.clone(),
+            org_admin_membership_proof: json_proof(
+                ProofKind::OrgAdmin,
+                &format!("https://api.github.com/user/memberships/orgs/{org}"),
+                &OrgMembershipBody {
+                    state: MembershipState::Active,
+                    role,
+                    user: Some(User { id: 7 }),
+                },
+            ),
+        };
+
+        assert_eq!(
+            link_org(
+                &mut deps.as_mut(),
+                &env,
+                &message_info(&linker, &[]),
+                link("unionlabs", MembershipRole::Member),
+                &config
+            ),
+            Err(Error::InsufficientOrgPermissions)
+        );
+        assert_eq!(
+            link_org(
+                &mut deps.as_mut(),
+                &env,
+                &message_info(&linker, &[]),
+                link("other", MembershipRole::Admin),
+                &config
+            ),
+            Err(Error::InvalidOrg)
+        );
+
+        // a member proving the active admin membership of another user of the org
+        let mut other_membership = link("unionlabs", MembershipRole::Admin);
+        other_membership.org_admin_membership_proof = json_proof(
+            ProofKind::OrgAdmin,
+            "https://api.github.com/orgs/unionlabs/memberships/admin",
+            &OrgMembershipBody {
+                state: MembershipState::Active,
+                role: MembershipRole::Admin,
+                user: Some(User { id: 7 }),
+            },
+        );
+        assert_eq!(
+            link_org(
+                &mut deps.as_mut(),
+                &env,
+                &message_info(&linker, &[]),
+                other_membership,
+                &config
+            ),
+            Err(Error::InvalidOrgMembershipUrl)
+        );
+        // the commitment is bound to the linker, so the revealed secret can't be replayed
+        assert_eq!(
+            link_org(
+                &mut deps.as_mut(),
+                &env,
+                &message_info(&delegate, &[]),
+                link("unionlabs", MembershipRole::Admin),
+                &config
+            ),
+            Err(Error::InvalidCommitment)
+        );
+        // the proofs of failed attempts are not stored, since failed messages are reverted
+        EXISTING_PROOFS.clear(deps.as_mut().storage);
+
+        link_org(
+            &mut deps.as_mut(),
+            &env,
+            &message_info(&linker, &[]),
+            link("unionlabs", MembershipRole::Admin),
+            &config,
+        )
+        .unwrap();
+
+        let org = (Forge::Github, "unionlabs".to_owned());
+        assert_eq!(
+            ORGS.load(deps.as_ref().storage, org.clone()).unwrap(),
+            org_config
+        );
+        assert_eq!(
+            ORG_ADMIN_IDS
+                .load(deps.as_ref().storage, org.clone())
+                .unwrap(),
+            7
+        );
+        assert!(!ORG_COMMITMENTS.has(deps.as_ref().storage, org));
+
+        // the linker owns the repos of the org that have no owner of their own
+        assert_eq!(repo_owner(deps.as_ref(), &repo), Some(linker.clone()));
+        assert_eq!(
+            repo_owner(deps.as_ref(), &owned_repo),
+            Some(delegate.clone())
+        );
+        assert_eq!(repo_owner(deps.as_ref(), &other_repo), None);
+        ensure_repo_role(deps.as_ref(), &repo, &linker, RepoRole::Treasurer).unwrap();
+        assert_eq!(
+            ensure_repo_role(deps.as_ref(), &other_repo, &linker, RepoRole::Treasurer),
+            Err(Error::RepoNotLinked(other_repo.clone()))
+        );
+
+        // only the roles granted in repos owned through the org are cleared
+        assert!(!REPO_ROLES.has(deps.as_ref().storage, (repo.clone(), delegate.clone())));
+        assert!(REPO_ROLES.has(deps.as_ref().storage, (owned_repo, delegate.clone())));
+        assert!(REPO_ROLES.has(deps.as_ref().storage, (other_repo, delegate)));
+
+        // repos of the org fall back to the org config, unless they are linked individually
+        assert_eq!(load_repo_config(deps.as_ref(), &repo), Some(org_config));
+        assert_eq!(
+            load_repo_config(deps.as_ref(), &Repo::from(("other", "union"))),
+            None
+        );
+
+        REPOS
+            .save(deps.as_mut().storage, repo.clone(), &empty_repo_config())
+            .unwrap();
+        assert_eq!(
+            load_repo_config(deps.as_ref(), &repo),
+            Some(empty_repo_config())
+        );
+    }
+
+    #[test]
+    fn repo_roles() {
+        let mut deps = mock_dependencies();
+
+        let repo = Repo::from(("unionlabs", "union"));
+        let owner = deps.api.addr_make("owner");
+        let editor = deps.api.addr_make("editor");
+        let new_owner = deps.api.addr_make("new_owner");
+
+        REPO_OWNERS
+            .save(deps.as_mut().storage, repo.clone(), &owner)
+            .unwrap();
+
+        assert_eq!(
+            ensure_repo_role(deps.as_ref(), &repo, &editor, RepoRole::ConfigEditor),
+            Err(Error::MissingRepoRole(repo.clone(), RepoRole::ConfigEditor))
+        );
+
+        let grant = GrantRepoRoleMsg {
+            repo: repo.clone(),
+            address: editor.clone(),
+            role: RepoRole::ConfigEditor,
+        };
+
+        assert_eq!(
+            grant_repo_role(
+                &mut deps.as_mut(),
+                &message_info(&editor, &[]),
+                grant.clone()
+            ),
+            Err(Error::NotRepoOwner(repo.clone()))
+        );
+
+        grant_repo_role(&mut deps.as_mut(), &message_info(&owner, &[]), grant).unwrap();
+
+        ensure_repo_role(deps.as_ref(), &repo, &editor, RepoRole::ConfigEditor).unwrap();
+        ensure_repo_role(deps.as_ref(), &repo, &owner, RepoRole::Treasurer).unwrap();
+
+        revoke_repo_role(
+            &mut deps.as_mut(),
+            &message_info(&owner, &[]),
+            RevokeRepoRoleMsg {
+                repo: repo.clone(),
+                address: editor.clone(),
+                role: RepoRole::ConfigEditor,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            ensure_repo_role(deps.as_ref(), &repo, &editor, RepoRole::ConfigEditor),
+            Err(Error::MissingRepoRole(repo.clone(), RepoRole::ConfigEditor))
+        );
+        assert!(!REPO_ROLES.has(deps.as_ref().storage, (repo.clone(), editor.clone())));
+
+        // the roles delegated by the previous owner are cleared on transfer
+        grant_repo_role(
+            &mut deps.as_mut(),
+            &message_info(&owner, &[]),
+            GrantRepoRoleMsg {
+                repo: repo.clone(),
+                address: editor.clone(),
+                role: RepoRole::Pauser,
+            },
+        )
+        .unwrap();
+        transfer_repo_ownership(
+            &mut deps.as_mut(),
+            &message_info(&owner, &[]),
+            TransferRepoOwnershipMsg {
+                repo: repo.clone(),
+                new_owner: new_owner.clone(),
+            },
+        )
+        .unwrap();
+        assert_eq!(repo_owner(deps.as_ref(), &repo), Some(new_owner));
+        assert!(!REPO_ROLES.has(deps.as_ref().storage, (repo, editor)));
+    }
+
+    #[test]
+    fn user_by_address() {
+        let mut deps = mock_dependencies();
+
+        let address = deps.api.addr_make("address");
+        let other = deps.api.addr_make("other");
+
+        USERS
+            .save(deps.as_mut().storage, (Forge::Github, 1), &address)
+            .unwrap();
+        USERS
+            .save(deps.as_mut().storage, (Forge::Github, 2), &address)
+            .unwrap();
+        // relinking a user to a different address removes it from the old address' index
+        USERS
+            .save(deps.as_mut().storage, (Forge::Github, 2), &other)
+            .unwrap();
+
+        let users: Vec<LinkedUser> = cosmwasm_std::from_json(
+            query(
+                deps.as_ref(),
+                cosmwasm_std::testing::mock_env(),
+                QueryMsg::UserByAddress { address },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            users,
+            vec![LinkedUser {
+                forge: Forge::Github,
+                github_user_id: 1
+            }]
+        );
+    }
+
+    #[test]
+    fn pause() {
+        let mut deps = mock_dependencies();
+        let mut env = cosmwasm_std::testing::mock_env();
+
+        let owner = deps.api.addr_make("owner");
+        let guardian = deps.api.addr_make("guardian");
+        let other = deps.api.addr_make("other");
+
+        let config = Config {
+            owner: owner.clone(),
+            verifier_address: deps.api.addr_make("verifier"),
+            commitment_delay_min_height: 0,
+            commitment_delay_max_height: 1,
+            allowed_forges: vec![],
+            allowed_provider_hashes: vec![],
+            guardian: Some(guardian.clone()),
+            fee: None,
+            reward_registry_enabled: false,
+            account_requirements: None,
+        };
+
+        let msg = PauseMsg {
+            rewards: true,
+            expires_at_height: Some(env.block.height + 10),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            super::pause(
+                &mut deps.as_mut(),
+                &env,
+                &message_info(&other, &[]),
+                msg,
+                &config
+            ),
+            Err(Error::NotOwnerOrGuardian)
+        );
+
+        super::pause(
+            &mut deps.as_mut(),
+            &env,
+            &message_info(&guardian, &[]),
+            msg,
+            &config,
+        )
+        .unwrap();
+
+        assert_eq!(
+            ensure_not_paused(deps.as_ref(), &env, PauseSwitch::Rewards),
+            Err(Error::Paused(PauseSwitch::Rewards))
+        );
+        ensure_not_paused(deps.as_ref(), &env, PauseSwitch::LinkAccounts).unwrap();
+
+        // the guardian can only pause
+        assert_eq!(
+            unpause(
+                &mut deps.as_mut(),
+                &env,
+                &message_info(&guardian, &[]),
+                UnpauseMsg {
+                    rewards: true,
+                    ..Default::default()
+                },
+                &config
+            ),
+            Err(Error::NotOwner)
+        );
+
+        // the owner pauses linking accounts indefinitely, which the guardian cannot shorten
+        super::pause(
+            &mut deps.as_mut(),
+            &env,
+            &message_info(&owner, &[]),
+            PauseMsg {
+                link_accounts: true,
+                ..Default::default()
+            },
+            &config,
+        )
+        .unwrap();
+        assert_eq!(
+            super::pause(
+                &mut deps.as_mut(),
+                &env,
+                &message_info(&guardian, &[]),
+                PauseMsg {
+                    link_accounts: true,
+                    expires_at_height: Some(env.block.height + 20),
+                    ..Default::default()
+                },
+                &config
+            ),
+            Err(Error::PauseShortened(PauseSwitch::LinkAccounts))
+        );
+        assert_eq!(
+            super::pause(
+                &mut deps.as_mut(),
+                &env,
+                &message_info(&guardian, &[]),
+                PauseMsg {
+                    rewards: true,
+                    expires_at_height: Some(env.block.height + 5),
+                    ..Default::default()
+                },
+                &config
+            ),
+            Err(Error::PauseShortened(PauseSwitch::Rewards))
+        );
+
+        // each switch keeps its own expiry
+        env.block.height += 10;
+        ensure_not_paused(deps.as_ref(), &env, PauseSwitch::Rewards).unwrap();
+        assert_eq!(
+            ensure_not_paused(deps.as_ref(), &env, PauseSwitch::LinkAccounts),
+            Err(Error::Paused(PauseSwitch::LinkAccounts))
+        );
+
+        // a new pause does not revive the expired pause of rewards
+        super::pause(
+            &mut deps.as_mut(),
+            &env,
+            &message_info(&guardian, &[]),
+            PauseMsg {
+                link_repos: true,
+                ..Default::default()
+            },
+            &config,
+        )
+        .unwrap();
+        ensure_not_paused(deps.as_ref(), &env, PauseSwitch::Rewards).unwrap();
+        assert_eq!(
+            PAUSE.load(deps.as_ref().storage).unwrap(),
+            Pause {
+                link_accounts: Some(SwitchPause {
+                    expires_at_height: None
+                }),
+                link_repos: Some(SwitchPause {
+                    expires_at_height: None
+                }),
+                rewards: None,
+            }
+        );
+
+        // the owner can lift pauses early
+        super::pause(
+            &mut deps.as_mut(),
+            &env,
+            &message_info(&owner, &[]),
+            PauseMsg {
+                link_accounts: true,
+                expires_at_height: Some(env.block.height + 1),
+                ..Default::default()
+            },
+            &config,
+        )
+        .unwrap();
+        unpause(
+            &mut deps.as_mut(),
+            &env,
+            &message_info(&owner, &[]),
+            UnpauseMsg {
+                link_repos: true,
+                ..Default::default()
+            },
+            &config,
+        )
+        .unwrap();
+        env.block.height += 1;
+        ensure_not_paused(deps.as_ref(), &env, PauseSwitch::LinkAccounts).unwrap();
+        ensure_not_paused(deps.as_ref(), &env, PauseSwitch::LinkRepos).unwrap();
+    }
+
+    #[test]
+    fn update_config() {
+        let mut deps = mock_dependencies();
+
+        let owner = deps.api.addr_make("owner");
+        let guardian = deps.api.addr_make("guardian");
+        let treasury = deps.api.addr_make("treasury");
+
+        let config = Config {
+            owner: owner.clone(),
+            verifier_address: deps.api.addr_make("verifier"),
+            commitment_delay_min_height: 0,
+            commitment_delay_max_height: 1,
+            allowed_forges: vec![],
+            allowed_provider_hashes: vec![],
+            guardian: Some(guardian.clone()),
+            fee: Some(FeeConfig {
+                treasury: treasury.clone(),
+                flat_fee: None,
+                bps: 250,
+            }),
+            reward_registry_enabled: false,
+            account_requirements: None,
+        };
+
+        // omitted fields are left unchanged, `null` fields are removed
+        let msg = from_json::<UpdateConfigMsg>(r#"{"fee":null}"#).unwrap();
+        assert_eq!(msg.guardian, None);
+        assert_eq!(msg.fee, Some(None));
+
+        super::update_config(
+            &mut deps.as_mut(),
+            &message_info(&owner, &[]),
+            msg,
+            config.clone(),
+        )
+        .unwrap();
+
+        let updated = CONFIG.load(deps.as_ref().storage).unwrap();
+        assert_eq!(updated.guardian, Some(guardian));
+        assert_eq!(updated.fee, None);
+
+        assert_eq!(
+            super::update_config(
+                &mut deps.as_mut(),
+                &message_info(&owner, &[]),
+                UpdateConfigMsg {
+                    fee: Some(Some(FeeConfig {
+                        treasury,
+                        flat_fee: None,
+                        bps: MAX_BPS + 1,
+                    })),
+                    ..Default::default()
+                },
+                config,
+            ),
+            Err(Error::InvalidFeeBps(MAX_BPS + 1))
+        );
+    }
+
+    #[test]
+    fn reward_registry() {
+        let mut deps = mock_dependencies();
+
+        let owner = deps.api.addr_make("owner");
+        let mut contracts = [
+            deps.api.addr_make("contract-a"),
+            deps.api.addr_make("contract-b"),
+        ];
+        contracts.sort();
+
+        let config = Config {
+            owner: owner.clone(),
+            verifier_address: deps.api.addr_make("verifier"),
+            commitment_delay_min_height: 0,
+            commitment_delay_max_height: 1,
+            allowed_forges: vec![],
+            allowed_provider_hashes: vec![],
+            guardian: None,
+            fee: None,
+            reward_registry_enabled: true,
+            account_requirements: None,
+        };
+
+        let creator = deps.api.addr_make("creator");
+        deps.querier.update_wasm(move |query| match query {
+            cosmwasm_std::WasmQuery::CodeInfo { code_id } if *code_id < 3 => {
+                cosmwasm_std::SystemResult::Ok(cosmwasm_std::ContractResult::Ok(
+                    to_json_binary(&cosmwasm_std::CodeInfoResponse::new(
+                        *code_id,
+                        creator.clone(),
+                        cosmwasm_std::Checksum::generate(b"code"),
+                    ))
+                    .unwrap(),
+                ))
+            }
+            cosmwasm_std::WasmQuery::ContractInfo { .. } => {
+                cosmwasm_std::SystemResult::Ok(cosmwasm_std::ContractResult::Ok(
+                    to_json_binary(&cosmwasm_std::ContractInfoResponse::new(
+                        3,
+                        creator.clone(),
+                        None,
+                        false,
+                        None,
+                    ))
+                    .unwrap(),
+                ))
+            }
+            _ => cosmwasm_std::SystemResult::Ok(cosmwasm_std::ContractResult::Err(
+                "not found".to_owned(),
+            )),
+        });
+
+        let metadata = |name: &str| RewardContractMetadata {
+            name: name.to_owned(),
+            description: String::new(),
+        };
+        let register =
+            |deps: &mut cosmwasm_std::OwnedDeps<_, _, _>, sender: &Addr, id: RewardContractId| {
+                register_reward_contract(
+                    &mut deps.as_mut(),
+                    &message_info(sender, &[]),
+                    RegisterRewardContractMsg {
+                        metadata: metadata("reward"),
+                        id,
+                    },
+                    &config,
+                )
+            };
+
+        assert_eq!(
+            register(&mut deps, &contracts[0], RewardContractId::CodeId(1)),
+            Err(Error::NotOwner)
+        );
+        assert_eq!(
+            register(&mut deps, &owner, RewardContractId::CodeId(3)),
+            Err(Error::CodeNotFound(3))
+        );
+
+        for id in [
+            RewardContractId::CodeId(2),
+            RewardContractId::CodeId(1),
+            RewardContractId::Address(contracts[1].clone()),
+            RewardContractId::Address(contracts[0].clone()),
+        ] {
+            register(&mut deps, &owner, id).unwrap();
+        }
+
+        assert_eq!(
+            register(&mut deps, &owner, RewardContractId::CodeId(1)),
+            Err(Error::RewardContractAlreadyRegistered(
+                RewardContractId::CodeId(1)
+            ))
+        );
+        assert_eq!(
+            register(
+                &mut deps,
+                &owner,
+                RewardContractId::Address(contracts[0].clone())
+            ),
+            Err(Error::RewardContractAlreadyRegistered(
+                RewardContractId::Address(contracts[0].clone())
+            ))
+        );
+
+        let page = |deps: Deps, start_after, limit| {
+            query_reward_registry(deps, start_after, limit)
+                .unwrap()
+                .into_iter()
+                .map(|registered| registered.id)
+                .collect::<Vec<_>>()
+        };
+
+        // code ids are listed before contracts
+        assert_eq!(
+            page(deps.as_ref(), None, None),
+            [
+                RewardContractId::CodeId(1),
+                RewardContractId::CodeId(2),
+                RewardContractId::Address(contracts[0].clone()),
+                RewardContractId::Address(contracts[1].clone()),
+            ]
+        );
+        assert_eq!(
+            page(deps.as_ref(), None, Some(1)),
+            [RewardContractId::CodeId(1)]
+        );
+        assert_eq!(
+            page(deps.as_ref(), Some(RewardContractId::CodeId(1)), Some(2)),
+            [
+                RewardContractId::CodeId(2),
+                RewardContractId::Address(contracts[0].clone()),
+            ]
+        );
+        assert_eq!(
+            page(
+                deps.as_ref(),
+                Some(RewardContractId::Address(contracts[0].clone())),
+                None
+            ),
+            [RewardContractId::Address(contracts[1].clone())]
+        );
+
+        // contracts registered by address are allowed regardless of their code id
+        ensure_reward_contract_registered(deps.as_ref(), &contracts[0]).unwrap();
+
+        deregister_reward_contract(
+            &mut deps.as_mut(),
+            &message_info(&owner, &[]),
+            DeregisterRewardContractMsg {
+                id: RewardContractId::CodeId(1),
+            },
+            &config,
+        )
+        .unwrap();
+        assert_eq!(
+            page(deps.as_ref(), None, Some(1)),
+            [RewardContractId::CodeId(2)]
+        );
+        register(&mut deps, &owner, RewardContractId::CodeId(1)).unwrap();
+    }
+
+    #[test]
+    fn fee() {
+        let mut deps = mock_dependencies();
+
+        let repo = Repo::from(("unionlabs", "union"));
+        let owner = deps.api.addr_make("owner");
+        let treasury = deps.api.addr_make("treasury");
+        let relayer = deps.api.addr_make("relayer");
+
+        let flat_fee = Coin::new(100_u128, "untrn");
+
+        let config = Config {
+            owner: owner.clone(),
+            verifier_address: deps.api.addr_make("verifier"),
+            commitment_delay_min_height: 0,
+            commitment_delay_max_height: 1,
+            allowed_forges: vec![],
+            allowed_provider_hashes: vec![],
+            guardian: None,
+            fee: Some(FeeConfig {
+                treasury: treasury.clone(),
+                flat_fee: Some(flat_fee.clone()),
+                bps: 250,
+            }),
+            reward_registry_enabled: false,
+            account_requirements: None,
+        };
+
+        assert_eq!(
+            validate_fee(deps.as_ref(), &message_info(&relayer, &[]), &repo, &config,),
+            Err(Error::InvalidFeeFunds(flat_fee.clone()))
+        );
+
+        let (held_fee, protocol_fee) = validate_fee(
+            deps.as_ref(),
+            &message_info(&relayer, std::slice::from_ref(&flat_fee)),
+            &repo,
+            &config,
+        )
+        .unwrap();
+        assert_eq!(
+            held_fee,
+            Some(FlatFee {
+                fee: flat_fee.clone(),
+                treasury: treasury.clone(),
+            })
+        );
+
+        let response = collect_fee(&mut deps.as_mut(), &repo, 1, held_fee, Response::new());
+
+        assert_eq!(response.messages.len(), 1);
+        assert_eq!(
+            protocol_fee,
+            Some(ProtocolFee {
+                bps: 250,
+                treasury: treasury.clone()
+            })
+        );
+        assert_eq!(
+            ACCUMULATED_FEES
+                .load(deps.as_ref().storage, "untrn".to_owned())
+                .unwrap(),
+            flat_fee.amount
+        );
+
+        set_fee_exemption(
+            &mut deps.as_mut(),
+            &message_info(&owner, &[]),
+            SetFeeExemptionMsg {
+                repo: repo.clone(),
+                exempt: true,
+            },
+            &config,
+        )
+        .unwrap();
+
+        assert_eq!(
+            validate_fee(deps.as_ref(), &message_info(&relayer, &[]), &repo, &config,),
+            Ok((None, None))
+        );
+    }
+
+    #[test]
+    fn simulate() {
+        let mut deps = mock_dependencies();
+
+        let repo = Repo::from(("unionlabs", "union"));
+        let recipient = deps.api.addr_make("recipient");
+        let reward_contract = deps.api.addr_make("reward");
+        let owner = deps.api.addr_make("owner");
+        let verifier = deps.api.addr_make("verifier");
+
+        CONFIG
+            .save(
+                deps.as_mut().storage,
+                &Config {
+                    owner,
+                    verifier_address: verifier,
+                    commitment_delay_min_height: 0,
+                    commitment_delay_max_height: 1,
+                    allowed_forges: vec![],
+                    allowed_provider_hashes: vec![],
+                    guardian: None,
+                    fee: None,
+                    reward_registry_enabled: false,
+                    account_requirements: None,
+                },
+            )
+            .unwrap();
+        USERS
+            .save(deps.as_mut().storage, (Forge::Github, 1), &recipient)
+            .unwrap();
+        REPOS
+            .save(
+                deps.as_mut().storage,
+                repo.clone(),
+                &RepoConfig {
+                    label_configs: vec![LabelConfig {
+                        label_id: 7,
+                        reward_contract: reward_contract.clone(),
+                        reward_config: "{}".to_owned(),
+                        tier: Some(Tier {
+                            min_ordinal: None,
+                            max_ordinal: Some(1),
+                        }),
+                        approval: None,
+                    }],
+                    rate_limits: None,
+                    account_requirements: None,
+                    self_dealing: None,
+                    challenge_period: None,
+                    relayer_tips: None,
+                },
+            )
+            .unwrap();
+
+        deps.querier.update_wasm(|_| {
+            cosmwasm_std::SystemResult::Ok(cosmwasm_std::ContractResult::Ok(
+                to_json_binary(&QueryRewardsResponse {
+                    claimed: false,
+                    rewards: vec![],
+                })
+                .unwrap(),
+            ))
+        });
+
+        let simulate = |deps: Deps, pr_id| {
+            simulate_reward_pr(
+                deps,
+                &cosmwasm_std::testing::mock_env(),
+                SimulateRewardPrMsg::PullRequest {
+                    repo: repo.clone(),
+                    pr_id,
+                    github_user_id: 1,
+                    label_ids: vec![7, 8],
+                },
+            )
+            .unwrap()
+        };
+
+        assert_eq!(
+            simulate(deps.as_ref(), 1),
+            SimulateRewardPrResponse {
+                error: None,
+                recipient_address: Some(recipient.clone()),
+                ordinal: Some(1),
+                flat_fee: None,
+                labels: vec![LabelRewards {
+                    label_id: 7,
+                    reward_contract,
+                    rewards: Some(QueryRewardsResponse {
+                        claimed: false,
+                        rewards: vec![],
+                    }),
+                    error: None,
+                }],
+                unverified: vec![],
+            }
+        );
+
+        // the label config only applies to the first pr of the user in the repo
+        USER_REPO_ORDINALS
+            .save(
+                deps.as_mut().storage,
+                ((Forge::Github, 1), repo.clone()),
+                &1,
+            )
+            .unwrap();
+
+        let response = simulate(deps.as_ref(), 2);
+        assert_eq!(response.ordinal, Some(2));
+        assert!(response.labels.is_empty());
+
+        REWARDED_PRS
+            .save(
+                deps.as_mut().storage,
+                (repo.clone(), 1),
+                &RewardedPr {
+                    user_id: 1,
+                    recipient_address: recipient,
+                    status: RewardStatus::Dispatched,
+                    revert_pr_id: None,
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            simulate(deps.as_ref(), 1).error,
+            Some(Error::PrAlreadyRewarded(1).to_string())
+        );
+    }
+
+    #[test]
+    fn simulate_unverified() {
+        let mut deps = mock_dependencies();
+
+        let repo = Repo::from(("unionlabs", "union"));
+        let recipient = deps.api.addr_make("recipient");
+        let owner = deps.api.addr_make("owner");
+        let verifier = deps.api.addr_make("verifier");
+
+        CONFIG
+            .save(
+                deps.as_mut().storage,
+                &Config {
+                    owner: owner.clone(),
+                    verifier_address: verifier,
+                    commitment_delay_min_height: 0,
+                    commitment_delay_max_height: 1,
+                    allowed_forges: vec![],
+                    allowed_provider_hashes: vec![],
+                    guardian: None,
+                    fee: None,
+                    reward_registry_enabled: false,
+                    account_requirements: None,
+                },
+            )
+            .unwrap();
+        USERS
+            .save(deps.as_mut().storage, (Forge::Github, 1), &recipient)
+            .unwrap();
+        REPO_OWNERS
+            .save(deps.as_mut().storage, repo.clone(), &owner)
+            .unwrap();
+        REPO_ADMIN_IDS
+            .save(deps.as_mut().storage, repo.clone(), &2)
+            .unwrap();
+        REPOS
+            .save(
+                deps.as_mut().storage,
+                repo.clone(),
+                &RepoConfig {
+                    label_configs: vec![],
+                    rate_limits: Some(RateLimits {
+                        min_merge_delay: Some(3600),
+                        ..Default::default()
+                    }),
+                    account_requirements: None,
+                    self_dealing: Some(SelfDealingPolicy {
+                        reject_self_merged: true,
+                        reject_linker: true,
+                        allowed_associations: Some(vec![AuthorAssociation::Contributor]),
+                    }),
+                    challenge_period: None,
+                    relayer_tips: None,
+                },
+            )
+            .unwrap();
+
+        let simulate = |deps: Deps, github_user_id| {
+            simulate_reward_pr(
+                deps,
+                &cosmwasm_std::testing::mock_env(),
+                SimulateRewardPrMsg::PullRequest {
+                    repo: repo.clone(),
+                    pr_id: 1,
+                    github_user_id,
+                    label_ids: vec![],
+                },
+            )
+            .unwrap()
+        };
+
+        // the checks that depend on data missing without a proof are skipped
+        assert_eq!(
+            simulate(deps.as_ref(), 1),
+            SimulateRewardPrResponse {
+                error: None,
+                recipient_address: Some(recipient.clone()),
+                ordinal: Some(1),
+                flat_fee: None,
+                labels: vec![],
+                unverified: vec![
+                    UnverifiedCheck::MinMergeDelay,
+                    UnverifiedCheck::RejectSelfMerged,
+                    UnverifiedCheck::AllowedAssociations,
+                ],
+            }
+        );
+
+        // the other checks still apply
+        USERS
+            .save(deps.as_mut().storage, (Forge::Github, 2), &recipient)
+            .unwrap();
+
+        assert_eq!(
+            simulate(deps.as_ref(), 2).error,
+            Some(Error::PrAuthorIsLinker(2).to_string())
+        );
+    }
+
+    #[test]
+    fn ordinals() {
+        let mut deps = mock_dependencies();
+
+        let repo = Repo::from(("unionlabs", "union"));
+        let user = (Forge::Github, 1);
+        let recipient = deps.api.addr_make("recipient");
+        let reward_contract = deps.api.addr_make("reward");
+        let owner = deps.api.addr_make("owner");
+        let verifier = deps.api.addr_make("verifier");
+
+        let tier = |min_ordinal, max_ordinal| Tier {
+            min_ordinal,
+            max_ordinal,
+        };
+        assert!(tier(None, None).contains(1));
+        assert!(tier(None, Some(1)).contains(1));
+        assert!(!tier(None, Some(1)).contains(2));
+        assert!(tier(Some(2), Some(3)).contains(3));
+        assert!(!tier(Some(2), Some(3)).contains(4));
+
+        CONFIG
+            .save(
+                deps.as_mut().storage,
+                &Config {
+                    owner,
+                    verifier_address: verifier,
+                    commitment_delay_min_height: 0,
+                    commitment_delay_max_height: 1,
+                    allowed_forges: vec![],
+                    allowed_provider_hashes: vec![],
+                    guardian: None,
+                    fee: None,
+                    reward_registry_enabled: false,
+                    account_requirements: None,
+                },
+            )
+            .unwrap();
+        USERS
+            .save(deps.as_mut().storage, user.clone(), &recipient)
+            .unwrap();
+        REPOS
+            .save(
+                deps.as_mut().storage,
+                repo.clone(),
+                &RepoConfig {
+                    label_configs: [(7, tier(None, Some(1))), (8, tier(Some(3), None))]
+                        .into_iter()
+                        .map(|(label_id, tier)| LabelConfig {
+                            label_id,
+                            reward_contract: reward_contract.clone(),
+                            reward_config: "{}".to_owned(),
+                            tier: Some(tier),
+                            approval: None,
+                        })
+                        .collect(),
+                    ..empty_repo_config()
+                },
+            )
+            .unwrap();
+
+        deps.querier.update_wasm(|_| {
+            cosmwasm_std::SystemResult::Ok(cosmwasm_std::ContractResult::Ok(
+                to_json_binary(&QueryRewardsResponse {
+                    claimed: false,
+                    rewards: vec![],
+                })
+                .unwrap(),
+            ))
+        });
+
+        let simulate = |deps: Deps, pr_id| {
+            let response = simulate_reward_pr(
+                deps,
+                &cosmwasm_std::testing::mock_env(),
+                SimulateRewardPrMsg::PullRequest {
+                    repo: repo.clone(),
+                    pr_id,
+                    github_user_id: 1,
+                    label_ids: vec![7, 8],
+                },
+            )
+            .unwrap();
+
+            (
+                response.ordinal.unwrap(),
+                response
+                    .labels
+                    .iter()
+                    .map(|label| label.label_id)
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        assert_eq!(simulate(deps.as_ref(), 1), (1, vec![7]));
+
+        save_rewarded_pr(
+            &mut deps.as_mut(),
+            &repo,
+            1,
+            user.clone(),
+            &recipient,
+            RewardStatus::Dispatched,
+        );
+        assert_eq!(simulate(deps.as_ref(), 2), (2, vec![]));
+
+        // reverting the first pr removes it from the stats, but does not free up its ordinal
+        remove_from_user_stats(&mut deps.as_mut(), &repo, 1, 1);
+        assert_eq!(
+            USER_REPO_STATS
+                .load(deps.as_ref().storage, (user.clone(), repo.clone()))
+                .unwrap(),
+            0
+        );
+        assert_eq!(simulate(deps.as_ref(), 2), (2, vec![]));
+
+        save_rewarded_pr(
+            &mut deps.as_mut(),
+            &repo,
+            2,
+            user.clone(),
+            &recipient,
+            RewardStatus::Dispatched,
+        );
+        assert_eq!(simulate(deps.as_ref(), 3), (3, vec![8]));
+        assert_eq!(
+            USER_REPO_STATS
+                .load(deps.as_ref().storage, (user, repo))
+                .unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn revert_pr() {
+        let mut deps = mock_dependencies();
+
+        let repo = Repo::from(("unionlabs", "union"));
+        let user = (Forge::Github, 1);
+        let owner = deps.api.addr_make("owner");
+        let other = deps.api.addr_make("other");
+        let recipient = deps.api.addr_make("recipient");
+        let reward_contract = deps.api.addr_make("reward");
+
+        let config = Config {
+            owner: owner.clone(),
+            verifier_address: deps.api.addr_make("verifier"),
+            commitment_delay_min_height: 0,
+            commitment_delay_max_height: 1,
+            allowed_forges: vec![],
+            allowed_provider_hashes: vec![],
+            guardian: None,
+            fee: None,
+            reward_registry_enabled: false,
+            account_requirements: None,
+        };
+
+        REPO_OWNERS
+            .save(deps.as_mut().storage, repo.clone(), &owner)
+            .unwrap();
+
+        for (pr_id, status) in [(1, RewardStatus::Dispatched), (2, RewardStatus::Pending)] {
+            save_rewarded_pr(
+                &mut deps.as_mut(),
+                &repo,
+                pr_id,
+                user.clone(),
+                &recipient,
+                status,
+            );
+        }
+        DISPATCHED_REWARDS
+            .save(
+                deps.as_mut().storage,
+                (repo.clone(), 1),
+                &vec![QueuedReward {
+                    label_id: 7,
+                    reward_contract: reward_contract.clone(),
+                    msg: RewardMsg {
+                        repo: repo.clone(),
+                        pr_id: 1,
+                        user_id: 1,
+                        recipient_address: recipient,
+                        reward_config: "{}".to_owned(),
+                        protocol_fee: None,
+                        ordinal: 1,
+                    },
+                }],
+            )
+            .unwrap();
+
+        let revert_proof = |body: &str| {
+            json_proof(
+                ProofKind::PullRequest,
+                &Forge::Github.pull_request_url("unionlabs", "union", 3),
+                &PrBody {
+                    url: Forge::Github.pull_request_url("unionlabs", "union", 3),
+                    number: 3,
+                    user: User { id: 2 },
+                    merged: true,
+                    labels: vec![],
+                    created_at: None,
+                    merged_at: None,
+                    merged_by: None,
+                    author_association: None,
+                    body: Some(body.to_owned()),
+                },
+            )
+        };
+
+        let revert = |mut deps: DepsMut, sender: &Addr, pr_id, revert_proof| {
+            // the proofs of failed attempts are not stored, since failed messages are reverted
+            EXISTING_PROOFS.clear(deps.storage);
+
+            super::revert_pr(
+                &mut deps,
+                &message_info(sender, &[]),
+                RevertPrMsg {
+                    repo: repo.clone(),
+                    pr_id,
+                    revert_proof,
+                },
+                &config,
+            )
+        };
+
+        assert_eq!(
+            revert(deps.as_mut(), &other, 1, None),
+            Err(Error::MissingRepoRole(
+                repo.clone(),
+                RepoRole::RewardManager
+            ))
+        );
+        assert_eq!(
+            revert(deps.as_mut(), &owner, 4, None),
+            Err(Error::PrNotRewarded(4))
+        );
+        // pending rewards are vetoed instead
+        assert_eq!(
+            revert(deps.as_mut(), &owner, 2, None),
+            Err(Error::InvalidRewardStatus {
+                pr_id: 2,
+                status: RewardStatus::Pending,
+            })
+        );
+        assert_eq!(
+            revert(deps.as_mut(), &owner, 1, Some(revert_proof("Reverts #12"))),
+            Err(Error::InvalidRevertPr(3))
+        );
+
+        let response = revert(deps.as_mut(), &owner, 1, Some(revert_proof("Reverts #1"))).unwrap();
+        // the verification of the revert proof and the revoke of the dispatched reward
+        assert_eq!(response.messages.len(), 2);
+        assert_eq!(response.messages[1].id, REVOKE_REPLY_ID);
+
+        let rewarded_pr = REWARDED_PRS
+            .load(deps.as_ref().storage, (repo.clone(), 1))
+            .unwrap();
+        assert_eq!(rewarded_pr.status, RewardStatus::Reverted);
+        assert_eq!(rewarded_pr.revert_pr_id, Some(3));
+        assert!(!DISPATCHED_REWARDS.has(deps.as_ref().storage, (repo.clone(), 1)));
+        assert_eq!(
+            USER_REPO_STATS
+                .load(deps.as_ref().storage, (user, repo.clone()))
+                .unwrap(),
+            1
+        );
+
+        assert_eq!(
+            revert(deps.as_mut(), &owner, 1, None),
+            Err(Error::InvalidRewardStatus {
+                pr_id: 1,
+                status: RewardStatus::Reverted,
+            })
+        );
+    }
+
+    #[test]
+    fn validate_reward_config() {
+        let mut deps = mock_dependencies();
+
+        let repo = Repo::from(("unionlabs", "union"));
+        let owner = deps.api.addr_make("owner");
+        let reward_contract = deps.api.addr_make("reward");
+
+        let mut config = Config {
+            owner: owner.clone(),
+            verifier_address: deps.api.addr_make("verifier"),
+            commitment_delay_min_height: 0,
+            commitment_delay_max_height: 1,
+            allowed_forges: vec![],
+            allowed_provider_hashes: vec![],
+            guardian: None,
+            fee: None,
+            reward_registry_enabled: false,
+            account_requirements: None,
+        };
+
+        let repo_config = RepoConfig {
+            label_configs: vec![LabelConfig {
+                label_id: 7,
+                reward_contract: reward_contract.clone(),
+                reward_config: "abc".to_owned(),
+                tier: None,
+                approval: None,
+            }],
+            rate_limits: None,
+            account_requirements: None,
+            self_dealing: None,
+            challenge_period: None,
+            relayer_tips: None,
+        };
+        let scope = RewardScope::Repo(repo);
+
+        deps.querier.update_wasm(|query| match query {
+            cosmwasm_std::WasmQuery::ContractInfo { .. } => {
+                cosmwasm_std::SystemResult::Ok(cosmwasm_std::ContractResult::Ok(
+                    to_json_binary(&cosmwasm_std::ContractInfoResponse::new(
+                        1,
+                        cosmwasm_std::testing::mock_env().contract.address,
+                        None,
+                        false,
+                        None,
+                    ))
+                    .unwrap(),
+                ))
+            }
+            _ => cosmwasm_std::SystemResult::Ok(cosmwasm_std::ContractResult::Err(
+                "invalid config".to_owned(),
+            )),
+        });
+
+        assert!(matches!(
+            validate_repo_config(deps.as_ref(), &config, &scope, &repo_config),
+            Err(Error::InvalidRewardConfig { label_id: 7, reward_contract: contract, .. })
+                if contract == reward_contract
+        ));
+
+        config.reward_registry_enabled = true;
+
+        assert_eq!(
+            validate_repo_config(deps.as_ref(), &config, &scope, &repo_config),
+            Err(Error::RewardContractNotRegistered(reward_contract.clone()))
+        );
+
+        register_reward_contract(
+            &mut deps.as_mut(),
+            &message_info(&owner, &[]),
+            RegisterRewardContractMsg {
+                id: RewardContractId::Address(reward_contract.clone()),
+                metadata: RewardContractMetadata {
+                    name: "token".to_owned(),
+                    description: "cw20 rewards".to_owned(),
+                },
+            },
+            &config,
+        )
+        .unwrap();
+
+        deps.querier.update_wasm(|_| {
+            cosmwasm_std::SystemResult::Ok(cosmwasm_std::ContractResult::Ok(
+                to_json_binary(&Empty {}).unwrap(),
+            ))
+        });
+
+        validate_repo_config(deps.as_ref(), &config, &scope, &repo_config).unwrap();
+    }
+
+    #[test]
+    fn rate_limits() {
+        let mut deps = mock_dependencies();
+        let mut env = cosmwasm_std::testing::mock_env();
+
+        let repo = Repo::from(("unionlabs", "union"));
+        let rate_limits = RateLimits {
+            per_user: Some(RateLimit {
+                max_prs: 1,
+                period: 86_400,
+            }),
+            per_repo: Some(RateLimit {
+                max_prs: 2,
+                period: 86_400,
+            }),
+            min_merge_delay: Some(3600),
+        };
+
+        let merged_at = env.block.time;
+        let pull_request = |author_id, open_for| PullRequest {
+            number: 1,
+            author_id,
+            merged: true,
+            label_ids: vec![],
+            created_at: Some(merged_at.minus_seconds(open_for)),
+            merged_at: Some(merged_at),
+            merged_by_id: None,
+            author_association: None,
+            body: None,
+        };
+
+        assert_eq!(
+            ensure_within_rate_limits(
+                deps.as_ref(),
+                &env,
+                &repo,
+                &pull_request(1, 60),
+                &rate_limits,
+                None,
+            ),
+            Err(Error::MergedTooSoon(3600))
+        );
+
+        ensure_within_rate_limits(
+            deps.as_ref(),
+            &env,
+            &repo,
+            &pull_request(1, 3600),
+            &rate_limits,
+            None,
+        )
+        .unwrap();
+
+        let record = |deps: &mut DepsMut, env: &Env, author_id| {
+            record_rate_limits(
+                deps,
+                env,
+                &EligiblePr {
+                    repo: repo.clone(),
+                    pull_request: pull_request(author_id, 3600),
+                    recipient_address: Addr::unchecked("recipient"),
+                    reward_recipients: vec![],
+                    repo_config: RepoConfig {
+                        label_configs: vec![],
+                        rate_limits: Some(rate_limits.clone()),
+                        account_requirements: None,
+                        self_dealing: None,
+                        challenge_period: None,
+                        relayer_tips: None,
+                    },
+                    ordinal: 1,
+                },
+            );
+        };
+
+        record(&mut deps.as_mut(), &env, 1);
+
+        let resets_at = rate_limits
+            .per_user
+            .as_ref()
+            .unwrap()
+            .resets_at(env.block.time);
+        assert_eq!(
+            ensure_within_rate_limits(
+                deps.as_ref(),
+                &env,
+                &repo,
+                &pull_request(1, 3600),
+                &rate_limits,
+                None,
+            ),
+            Err(Error::UserRateLimited {
+                repo: repo.clone(),
+                user_id: 1,
+                resets_at,
+            })
+        );
+
+        record(&mut deps.as_mut(), &env, 2);
+
+        assert_eq!(
+            ensure_within_rate_limits(
+                deps.as_ref(),
+                &env,
+                &repo,
+                &pull_request(3, 3600),
+                &rate_limits,
+                None,
+            ),
+            Err(Error::RepoRateLimited {
+                repo: repo.clone(),
+                resets_at,
+            })
+        );
+
+        env.block.time = resets_at;
+
+        ensure_within_rate_limits(
+            deps.as_ref(),
+            &env,
+            &repo,
+            &pull_request(1, 3600),
+            &rate_limits,
+            None,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn account_requirements() {
+        let env = cosmwasm_std::testing::mock_env();
+
+        let parameters = UserExtractedParameters {
+            id: "1".to_owned(),
+            created_at: Some("2015-01-01T00:00:00Z".to_owned()),
+            public_repos: Some("3".to_owned()),
+            followers: Some("0".to_owned()),
+        };
+
+        let profile = parse_user_profile(&parameters).unwrap().unwrap();
+        assert_eq!(
+            profile,
+            UserProfile {
+                created_at: cosmwasm_std::Timestamp::from_seconds(1_420_070_400),
+                public_repos: 3,
+                followers: 0,
+            }
+        );
+
+        // proofs of the plain user endpoint have no profile
+        assert_eq!(
+            parse_user_profile(&UserExtractedParameters {
+                id: "1".to_owned(),
+                created_at: None,
+                public_repos: None,
+                followers: None,
+            }),
+            Ok(None)
+        );
+
+        let requirements = AccountRequirements {
+            min_account_age: Some(90 * 86_400),
+            min_public_repos: Some(1),
+            min_followers: None,
+        };
+
+        assert_eq!(
+            ensure_account_requirements(&env, 1, Some(&profile), &requirements),
+            Ok(())
+        );
+        assert_eq!(
+            ensure_account_requirements(&env, 1, None, &requirements),
+            Err(Error::MissingUserProfile(1))
+        );
+        assert_eq!(
+            ensure_account_requirements(&env, 1, None, &AccountRequirements::default()),
+            Ok(())
+        );
+
+        let new_account = UserProfile {
+            created_at: env.block.time.minus_seconds(86_400),
+            ..profile.clone()
+        };
+        assert_eq!(
+            ensure_account_requirements(&env, 1, Some(&new_account), &requirements),
+            Err(Error::AccountTooNew {
+                user_id: 1,
+                min_account_age: 90 * 86_400
+            })
+        );
+
+        assert_eq!(
+            ensure_account_requirements(
+                &env,
+                1,
+                Some(&profile),
+                &AccountRequirements {
+                    min_followers: Some(1),
+                    ..requirements
+                }
+            ),
+            Err(Error::TooFewFollowers {
+                user_id: 1,
+                min_followers: 1
+            })
+        );
+    }
+
+    #[test]
+    fn self_dealing() {
+        let mut deps = mock_dependencies();
+
+        let owner = deps.api.addr_make("owner");
+        let contributor = deps.api.addr_make("contributor");
+
+        let repo = Repo::from(("unionlabs", "union"));
+        let repo_config = RepoConfig {
+            label_configs: vec![],
+            rate_limits: None,
+            account_requirements: None,
+            self_dealing: None,
+            challenge_period: None,
+            relayer_tips: None,
+        };
+
+        REPOS
+            .save(deps.as_mut().storage, repo.clone(), &repo_config)
+            .unwrap();
+        REPO_OWNERS
+            .save(deps.as_mut().storage, repo.clone(), &owner)
+            .unwrap();
+        REPO_ADMIN_IDS
+            .save(deps.as_mut().storage, repo.clone(), &1)
+            .unwrap();
+
+        let policy = SelfDealingPolicy {
+            reject_self_merged: true,
+            reject_linker: true,
+            allowed_associations: Some(vec![
+                AuthorAssociation::Contributor,
+                AuthorAssociation::FirstTimeContributor,
+            ]),
+        };
+
+        let pull_request = |author_id, merged_by_id, author_association| PullRequest {
+            number: 1,
+            author_id,
+            merged: true,
+            label_ids: vec![],
+            created_at: None,
+            merged_at: None,
+            merged_by_id,
+            author_association,
+            body: None,
+        };
+
+        assert_eq!(
+            ensure_no_self_dealing(
+                deps.as_ref(),
+                &repo,
+                &pull_request(2, Some(1), Some(AuthorAssociation::Contributor)),
+                &contributor,
+                &policy,
+                None,
+            ),
+            Ok(())
+        );
+
+        assert_eq!(
+            ensure_no_self_dealing(
+                deps.as_ref(),
+                &repo,
+                &pull_request(2, Some(2), Some(AuthorAssociation::Contributor)),
+                &contributor,
+                &policy,
+                None,
+            ),
+            Err(Error::SelfMergedPr)
+        );
+
+        assert_eq!(
+            ensure_no_self_dealing(
+                deps.as_ref(),
+                &repo,
+                &pull_request(2, None, Some(AuthorAssociation::Contributor)),
+                &contributor,
+                &policy,
+                None,
+            ),
+            Err(Error::MissingMergedBy)
+        );
+
+        // the admin that linked the repo
+        assert_eq!(
+            ensure_no_self_dealing(
+                deps.as_ref(),
+                &repo,
+                &pull_request(1, Some(3), Some(AuthorAssociation::Contributor)),
+                &contributor,
+                &policy,
+                None,
+            ),
+            Err(Error::PrAuthorIsLinker(1))
+        );
+
+        // an account linked to the address of the repo owner
+        assert_eq!(
+            ensure_no_self_dealing(
+                deps.as_ref(),
+                &repo,
+                &pull_request(3, Some(1), Some(AuthorAssociation::Contributor)),
+                &owner,
+                &policy,
+                None,
+            ),
+            Err(Error::PrAuthorIsLinker(3))
+        );
+
+        assert_eq!(
+            ensure_no_self_dealing(
+                deps.as_ref(),
+                &repo,
+                &pull_request(2, Some(1), Some(AuthorAssociation::Member)),
+                &contributor,
+                &policy,
+                None,
+            ),
+            Err(Error::AuthorAssociationNotAllowed(
+                AuthorAssociation::Member
+            ))
+        );
+
+        assert_eq!(
+            ensure_no_self_dealing(
+                deps.as_ref(),
+                &repo,
+                &pull_request(2, Some(1), None),
+                &contributor,
+                &policy,
+                None,
+            ),
+            Err(Error::MissingAuthorAssociation)
+        );
+
+        // a repo owned through its org, with a config override written by a config editor
+        let org_repo = Repo::from(("unionlabs", "other"));
+        let org = (Forge::Github, "unionlabs".to_owned());
+        REPOS
+            .save(deps.as_mut().storage, org_repo.clone(), &repo_config)
+            .unwrap();
+        ORG_OWNERS
+            .save(deps.as_mut().storage, org.clone(), &owner)
+            .unwrap();
+        ORG_ADMIN_IDS.save(deps.as_mut().storage, org, &4).unwrap();
+
+        let policy = SelfDealingPolicy {
+            reject_self_merged: false,
+            reject_linker: true,
+            allowed_associations: None,
+        };
+
+        assert_eq!(
+            ensure_no_self_dealing(
+                deps.as_ref(),
+                &org_repo,
+                &pull_request(4, Some(1), None),
+                &contributor,
+                &policy,
+                None,
+            ),
+            Err(Error::PrAuthorIsLinker(4))
+        );
+        assert_eq!(
+            ensure_no_self_dealing(
+                deps.as_ref(),
+                &org_repo,
+                &pull_request(3, Some(1), None),
+                &owner,
+                &policy,
+                None,
+            ),
+            Err(Error::PrAuthorIsLinker(3))
+        );
+        // the admin of the org is not the linker of repos that are linked individually
+        assert_eq!(
+            ensure_no_self_dealing(
+                deps.as_ref(),
+                &repo,
+                &pull_request(4, Some(1), None),
+                &contributor,
+                &policy,
+                None,
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn challenge_period() {
+        let mut deps = mock_dependencies();
+        let mut env = cosmwasm_std::testing::mock_env();
+
+        let owner = deps.api.addr_make("owner");
+        let guardian = deps.api.addr_make("guardian");
+        let recipient = deps.api.addr_make("recipient");
+        let reward_contract = deps.api.addr_make("reward_contract");
+        let relayer = deps.api.addr_make("relayer");
+        let treasury = deps.api.addr_make("treasury");
+
+        let fee = Coin::new(100_u128, "untrn");
+        let tip = Coin::new(10_u128, "untrn");
+
+        let repo = Repo::from(("unionlabs", "union"));
+        let rate_limit = RateLimit {
+            max_prs: 2,
+            period: 86_400,
+        };
+
+        REPOS
+            .save(
+                deps.as_mut().storage,
+                repo.clone(),
+                &RepoConfig {
+                    label_configs: vec![],
+                    rate_limits: Some(RateLimits {
+                        per_user: Some(rate_limit.clone()),
+                        per_repo: Some(rate_limit.clone()),
+                        min_merge_delay: None,
+                    }),
+                    account_requirements: None,
+                    self_dealing: None,
+                    challenge_period: Some(ChallengePeriod {
+                        blocks: 10,
+                        guardian: Some(guardian.clone()),
+                    }),
+                    relayer_tips: Some(RelayerTips {
+                        reward_pr: Some(tip.clone()),
+                        link_account: None,
+                        max_per_block: 10,
+                    }),
+                },
+            )
+            .unwrap();
+        TIP_POOLS
+            .save(
+                deps.as_mut().storage,
+                (repo.clone(), "untrn".to_owned()),
+                &cosmwasm_std::Uint128::new(100),
+            )
+            .unwrap();
+        REPO_OWNERS
+            .save(deps.as_mut().storage, repo.clone(), &owner)
+            .unwrap();
+
+        let challenge_ends_at_height = env.block.height + 10;
+
+        let park = |deps: &mut DepsMut, pr_id| {
+            save_rewarded_pr(
+                deps,
+                &repo,
+                pr_id,
+                (Forge::Github, 1),
+                &recipient,
+                RewardStatus::Pending,
+            );
+            PENDING_REWARDS
+                .save(
+                    deps.storage,
+                    (repo.clone(), pr_id),
+                    &PendingReward {
+                        pr_id,
+                        user_id: 1,
+                        recipient_address: recipient.clone(),
+                        challenge_ends_at_height,
+                        rewarded_at: env.block.time,
+                        relayer: relayer.clone(),
+                        held_fee: Some(FlatFee {
+                            fee: fee.clone(),
+                            treasury: treasury.clone(),
+                        }),
+                        rewards: vec![QueuedReward {
+                            label_id: 1,
+                            reward_contract: reward_contract.clone(),
+                            msg: RewardMsg {
+                                repo: repo.clone(),
+                                pr_id,
+                                user_id: 1,
+                                recipient_address: recipient.clone(),
+                                reward_config: "{}".to_owned(),
+                                protocol_fee: None,
+                                ordinal: 1,
+                            },
+                        }],
+                    },
+                )
+                .unwrap();
+        };
+
+        park(&mut deps.as_mut(), 1);
+        park(&mut deps.as_mut(), 2);
+
+        let usage = RateLimitUsage {
+            window: rate_limit.window(env.block.time),
+            count: 2,
+        };
+        USER_RATE_LIMITS
+            .save(
+                deps.as_mut().storage,
+                (repo.clone(), (Forge::Github, 1)),
+                &usage,
+            )
+            .unwrap();
+        REPO_RATE_LIMITS
+            .save(deps.as_mut().storage, repo.clone(), &usage)
+            .unwrap();
+
+        let veto = |pr_id| VetoRewardMsg {
+            repo: repo.clone(),
+            pr_id,
+        };
+        let finalize = |pr_id| FinalizeRewardMsg {
+            repo: repo.clone(),
+            pr_id,
+        };
+
+        assert_eq!(
+            veto_reward(
+                &mut deps.as_mut(),
+                &env,
+                &message_info(&recipient, &[]),
+                veto(1)
+            ),
+            Err(Error::MissingRepoRole(
+                repo.clone(),
+                RepoRole::RewardManager
+            ))
+        );
+        assert_eq!(
+            finalize_reward(&mut deps.as_mut(), &env, finalize(1)),
+            Err(Error::ChallengePeriodActive(challenge_ends_at_height))
+        );
+
+        let response = veto_reward(
+            &mut deps.as_mut(),
+            &env,
+            &message_info(&guardian, &[]),
+            veto(1),
+        )
+        .unwrap();
+
+        // the relayer of the vetoed reward gets the held fee back, and is not tipped
+        assert_eq!(
+            response.messages,
+            [SubMsg::new(BankMsg::Send {
+                to_address: relayer.to_string(),
+                amount: vec![fee.clone()],
+            })]
+        );
+
+        assert_eq!(
+            REWARDED_PRS
+                .load(deps.as_ref().storage, (repo.clone(), 1))
+                .unwrap()
+                .status,
+            RewardStatus::Vetoed
+        );
+        assert_eq!(
+            USER_REPO_STATS
+                .load(deps.as_ref().storage, ((Forge::Github, 1), repo.clone()))
+                .unwrap(),
+            1
+        );
+
+        // the vetoed pr no longer counts towards the rate limits
+        let refunded_usage = RateLimitUsage { count: 1, ..usage };
+        assert_eq!(
+            USER_RATE_LIMITS
+                .load(deps.as_ref().storage, (repo.clone(), (Forge::Github, 1)))
+                .unwrap(),
+            refunded_usage
+        );
+        assert_eq!(
+            REPO_RATE_LIMITS
+                .load(deps.as_ref().storage, repo.clone())
+                .unwrap(),
+            refunded_usage
+        );
+
+        env.block.height = challenge_ends_at_height;
+
+        assert_eq!(
+            veto_reward(
+                &mut deps.as_mut(),
+                &env,
+                &message_info(&owner, &[]),
+                veto(2)
+            ),
+            Err(Error::ChallengePeriodEnded(challenge_ends_at_height))
+        );
+        assert_eq!(
+            finalize_reward(&mut deps.as_mut(), &env, finalize(1)),
+            Err(Error::PendingRewardNotFound {
+                repo: repo.clone(),
+                pr_id: 1
+            })
+        );
+
+        // the held fee and the tip are paid once the reward is finalized
+        let response = finalize_reward(&mut deps.as_mut(), &env, finalize(2)).unwrap();
+        assert_eq!(response.messages.len(), 3);
+        assert_eq!(
+            response.messages[..2],
+            [
+                SubMsg::new(BankMsg::Send {
+                    to_address: treasury.to_string(),
+                    amount: vec![fee.clone()],
+                }),
+                SubMsg::new(BankMsg::Send {
+                    to_address: relayer.to_string(),
+                    amount: vec![tip],
+                }),
+            ]
+        );
+        assert_eq!(
+            ACCUMULATED_FEES
+                .load(deps.as_ref().storage, "untrn".to_owned())
+                .unwrap(),
+            fee.amount
+        );
+        assert_eq!(
+            REWARDED_PRS
+                .load(deps.as_ref().storage, (repo.clone(), 2))
+                .unwrap()
+                .status,
+            RewardStatus::Dispatched
+        );
+        assert!(!PENDING_REWARDS.has(deps.as_ref().storage, (repo, 2)));
+    }
+
+    #[test]
+    fn approvals() {
+        let mut deps = mock_dependencies();
+        let mut env = cosmwasm_std::testing::mock_env();
+
+        let approvers = ["alice", "bob", "carol"].map(|approver| deps.api.addr_make(approver));
+        let outsider = deps.api.addr_make("outsider");
+        let recipient = deps.api.addr_make("recipient");
+        let reward_contract = deps.api.addr_make("reward_contract");
+
+        let repo = Repo::from(("unionlabs", "union"));
+
+        let expires_at_height = env.block.height + 10;
+
+        let park = |deps: &mut DepsMut, pr_id| {
+            save_rewarded_pr(
+                deps,
+                &repo,
+                pr_id,
+                (Forge::Github, 1),
+                &recipient,
+                RewardStatus::Dispatched,
+            );
+            PENDING_APPROVALS
+                .save(
+                    deps.storage,
+                    (repo.clone(), pr_id),
+                    &vec![PendingApproval {
+                        reward: QueuedReward {
+                            label_id: 1,
+                            reward_contract: reward_contract.clone(),
+                            msg: RewardMsg {
+                                repo: repo.clone(),
+                                pr_id,
+                                user_id: 1,
+                                recipient_address: recipient.clone(),
+                                reward_config: "{}".to_owned(),
+                                protocol_fee: None,
+                                ordinal: 1,
+                            },
+                        },
+                        approvers: approvers.to_vec(),
+                        threshold: 2,
+                        approvals: vec![],
+                        expires_at_height,
+                    }],
+                )
+                .unwrap();
+        };
+
+        park(&mut deps.as_mut(), 1);
+        park(&mut deps.as_mut(), 2);
+
+        let approve = |pr_id, label_id| ApproveRewardMsg {
+            repo: repo.clone(),
+            pr_id,
+            label_id,
+        };
+
+        assert_eq!(
+            approve_reward(
+                &mut deps.as_mut(),
+                &env,
+                &message_info(&approvers[0], &[]),
+                approve(1, 2)
+            ),
+            Err(Error::PendingApprovalNotFound {
+                pr_id: 1,
+                label_id: 2
+            })
+        );
+        assert_eq!(
+            approve_reward(
+                &mut deps.as_mut(),
+                &env,
+                &message_info(&outsider, &[]),
+                approve(1, 1)
+            ),
+            Err(Error::NotApprover {
+                pr_id: 1,
+                label_id: 1
+            })
+        );
+
+        // approving twice doesn't count twice
+        for _ in 0..2 {
+            let response = approve_reward(
+                &mut deps.as_mut(),
+                &env,
+                &message_info(&approvers[0], &[]),
+                approve(1, 1),
+            )
+            .unwrap();
+            assert!(response.messages.is_empty());
+        }
+
+        let response = approve_reward(
+            &mut deps.as_mut(),
+            &env,
+            &message_info(&approvers[2], &[]),
+            approve(1, 1),
+        )
+        .unwrap();
+        assert_eq!(response.messages.len(), 1);
+        assert!(!PENDING_APPROVALS.has(deps.as_ref().storage, (repo.clone(), 1)));
+        assert_eq!(
+            DISPATCHED_REWARDS
+                .load(deps.as_ref().storage, (repo.clone(), 1))
+                .unwrap()
+                .len(),
+            1
+        );
+
+        env.block.height = expires_at_height;
+
+        assert_eq!(
+            approve_reward(
+                &mut deps.as_mut(),
+                &env,
+                &message_info(&approvers[1], &[]),
+                approve(2, 1)
+            ),
+            Err(Error::ApprovalExpired(expires_at_height))
+        );
+    }
+
+    #[test]
+    fn reward_recipients() {
+        let mut deps = mock_dependencies();
+
+        let linked = deps.api.addr_make("linked");
+        let cold_wallet = deps.api.addr_make("cold_wallet");
+        let nft_contract = deps.api.addr_make("nft_contract");
+        let token_contract = deps.api.addr_make("token_contract");
+
+        USERS
+            .save(deps.as_mut().storage, (Forge::Github, 1), &linked)
+            .unwrap();
+
+        let set = |reward_contract, recipient| SetRewardRecipientMsg {
+            forge: Forge::Github,
+            github_user_id: 1,
+            reward_contract,
+            recipient,
+        };
+
+        assert_eq!(
+            set_reward_recipient(
+                &mut deps.as_mut(),
+                &message_info(&cold_wallet, &[]),
+                set(
+                    RewardContractId::Address(nft_contract.clone()),
+                    Some(cold_wallet.clone())
+                ),
+            ),
+            Err(Error::NotLinkedAddress(1))
+        );
+
+        for (reward_contract, recipient) in [
+            (
+                RewardContractId::Address(nft_contract.clone()),
+                Some(linked.clone()),
+            ),
+            (
+                RewardContractId::Address(nft_contract.clone()),
+                Some(cold_wallet.clone()),
+            ),
+            (RewardContractId::CodeId(1), Some(cold_wallet.clone())),
+            (RewardContractId::CodeId(1), None),
+        ] {
+            set_reward_recipient(
+                &mut deps.as_mut(),
+                &message_info(&linked, &[]),
+                set(reward_contract, recipient),
+            )
+            .unwrap();
+        }
+
+        let reward_recipients = REWARD_RECIPIENTS
+            .load(deps.as_ref().storage, (Forge::Github, 1))
+            .unwrap();
+        assert_eq!(
+            reward_recipients,
+            vec![RewardRecipient {
+                reward_contract: RewardContractId::Address(nft_contract.clone()),
+                recipient: cold_wallet.clone(),
+            }]
+        );
+
+        let pr = EligiblePr {
+            repo: Repo::from(("unionlabs", "union")),
+            pull_request: PullRequest {
+                number: 1,
+                author_id: 1,
+                merged: true,
+                label_ids: vec![],
+                created_at: None,
+                merged_at: None,
+                merged_by_id: None,
+                author_association: None,
+                body: None,
+            },
+            recipient_address: linked.clone(),
+            reward_recipients,
+            repo_config: RepoConfig {
+                label_configs: vec![],
+                rate_limits: None,
+                account_requirements: None,
+                self_dealing: None,
+                challenge_period: None,
+                relayer_tips: None,
+            },
+            ordinal: 1,
+        };
+
+        let querier = deps.as_ref().querier;
+        assert_eq!(pr.recipient(&querier, &nft_contract), cold_wallet);
+        assert_eq!(pr.recipient(&querier, &token_contract), linked);
+    }
+
+    #[test]
+    fn relayer_tips() {
+        let mut deps = mock_dependencies();
+        let mut env = cosmwasm_std::testing::mock_env();
+
+        let funder = deps.api.addr_make("funder");
+        let relayer = deps.api.addr_make("relayer");
+        let link_relayer = deps.api.addr_make("link_relayer");
+
+        let repo = Repo::from(("unionlabs", "union"));
+        let repo_config = RepoConfig {
+            label_configs: vec![],
+            rate_limits: None,
+            account_requirements: None,
+            self_dealing: None,
+            challenge_period: None,
+            relayer_tips: Some(RelayerTips {
+                reward_pr: Some(Coin::new(10_u128, "ucosm")),
+                link_account: Some(Coin::new(5_u128, "ucosm")),
+                max_per_block: 2,
+            }),
+        };
+
+        REPOS
+            .save(deps.as_mut().storage, repo.clone(), &repo_config)
+            .unwrap();
+
+        assert_eq!(
+            fund_tip_pool(
+                &mut deps.as_mut(),
+                &message_info(&funder, &[]),
+                FundTipPoolMsg { repo: repo.clone() }
+            ),
+            Err(Error::NoTipPoolFunds)
+        );
+        fund_tip_pool(
+            &mut deps.as_mut(),
+            &message_info(&funder, &[Coin::new(25_u128, "ucosm")]),
+            FundTipPoolMsg { repo: repo.clone() },
+        )
+        .unwrap();
+
+        LINK_RELAYERS
+            .save(deps.as_mut().storage, (Forge::Github, 1), &link_relayer)
+            .unwrap();
+
+        let tip = |deps: &mut DepsMut, env: &Env, pr_id, author_id| {
+            tip_relayers(
+                deps,
+                env,
+                &relayer,
+                &repo,
+                pr_id,
+                author_id,
+                Response::new(),
+            )
+            .messages
+            .len()
+        };
+        let remaining = |deps: Deps| {
+            TIP_POOLS
+                .load(deps.storage, (repo.clone(), "ucosm".to_owned()))
+                .unwrap()
+                .u128()
+        };
+
+        assert_eq!(tip(&mut deps.as_mut(), &env, 1, 1), 2);
+        assert_eq!(remaining(deps.as_ref()), 10);
+        assert!(!LINK_RELAYERS.has(deps.as_ref().storage, (Forge::Github, 1)));
+
+        // the per block limit has been reached
+        assert_eq!(tip(&mut deps.as_mut(), &env, 2, 2), 0);
+
+        env.block.height += 1;
+
+        assert_eq!(tip(&mut deps.as_mut(), &env, 2, 2), 1);
+        // the tip pool has run out
+        assert_eq!(tip(&mut deps.as_mut(), &env, 3, 2), 0);
+        assert_eq!(remaining(deps.as_ref()), 0);
+    }
+
+    #[test]
+    fn tip_pool_withdrawal() {
+        let mut deps = mock_dependencies();
+
+        let owner = deps.api.addr_make("owner");
+        let treasurer = deps.api.addr_make("treasurer");
+
+        let repo = Repo::from(("unionlabs", "union"));
+
+        REPOS
+            .save(deps.as_mut().storage, repo.clone(), &empty_repo_config())
+            .unwrap();
+        REPO_OWNERS
+            .save(deps.as_mut().storage, repo.clone(), &owner)
+            .unwrap();
+        fund_tip_pool(
+            &mut deps.as_mut(),
+            &message_info(&owner, &[Coin::new(25_u128, "ucosm")]),
+            FundTipPoolMsg { repo: repo.clone() },
+        )
+        .unwrap();
+
+        let withdraw = |deps: &mut DepsMut, sender: &Addr, amount: u128| {
+            withdraw_tip_pool(
+                deps,
+                &message_info(sender, &[]),
+                WithdrawTipPoolMsg {
+                    repo: repo.clone(),
+                    amount: Coin::new(amount, "ucosm"),
+                },
+            )
+        };
+
+        assert_eq!(
+            withdraw(&mut deps.as_mut(), &treasurer, 10),
+            Err(Error::MissingRepoRole(repo.clone(), RepoRole::Treasurer))
+        );
+
+        grant_repo_role(
+            &mut deps.as_mut(),
+            &message_info(&owner, &[]),
+            GrantRepoRoleMsg {
+                repo: repo.clone(),
+                address: treasurer.clone(),
+                role: RepoRole::Treasurer,
+            },
+        )
+        .unwrap();
+
+        withdraw(&mut deps.as_mut(), &treasurer, 10).unwrap();
+        withdraw(&mut deps.as_mut(), &owner, 10).unwrap();
+        assert_eq!(
+            withdraw(&mut deps.as_mut(), &treasurer, 10),
+            Err(Error::InsufficientTipPool(Coin::new(5_u128, "ucosm")))
+        );
+    }
+}
@@ -1,21 +1,49 @@
-use cosmwasm_std::Binary;
+use cosmwasm_std::{Addr, Binary, Coin, Timestamp};
 
-use crate::state::Repo;
+use crate::{
+    forge::Forge,
+    models::github::AuthorAssociation,
+    state::{PauseSwitch, Repo, RepoRole, RewardContractId, RewardStatus},
+};
 
 #[derive(Debug, PartialEq, thiserror::Error)]
 pub enum Error {
+    #[error("sender is not the contract owner")]
+    NotOwner,
+    #[error("migrating from a version without forge support requires an owner")]
+    MissingMigrationOwner,
+    #[error("sender is neither the contract owner nor the guardian")]
+    NotOwnerOrGuardian,
+    #[error("the guardian cannot shorten the pause of {0:?}")]
+    PauseShortened(PauseSwitch),
+    #[error("{0:?} is paused")]
+    Paused(PauseSwitch),
+    #[error("repo {}/{} is paused", .0.org, .0.repo)]
+    RepoPaused(Repo),
+    #[error("forge {0} is not allowed")]
+    ForgeNotAllowed(Forge),
+    #[error("forge {0} does not support linking orgs")]
+    UnsupportedForge(Forge),
     #[error("user {0} not found")]
     UserNotFound(u64),
+    #[error("sender is not the linked address of user {0}")]
+    NotLinkedAddress(u64),
+    #[error("invalid user url")]
+    InvalidUserUrl,
     #[error("proof has already been submitted")]
     ProofAlreadySubmitted,
     #[error("invalid pull request url")]
     InvalidPrUrl,
     #[error("invalid collaborator url")]
     InvalidCollaboratorUrl,
+    #[error("invalid org membership url")]
+    InvalidOrgMembershipUrl,
     #[error("user commitment for {0} not found")]
     UserCommitmentNotFound(u64),
     #[error("repo commitment for {}/{} not found", .0.org, .0.repo)]
     RepoCommitmentNotFound(Repo),
+    #[error("org commitment for {0} not found")]
+    OrgCommitmentNotFound(String),
     #[error("commitment key {0} already exists")]
     CommitmentAlreadyExists(Binary),
     #[error("commitments must be 32 bytes, found {0}")]
@@ -24,10 +52,26 @@ pub enum Error {
     PrAlreadyRewarded(u64),
     #[error("unable to deserialize context: {0}")]
     InvalidContext(serde_json_wasm::de::Error),
+    #[error("unable to deserialize parameters: {0}")]
+    InvalidParameters(serde_json_wasm::de::Error),
+    #[error("proof request method must be GET, found {0}")]
+    InvalidProofMethod(String),
+    #[error("proof request body must be empty")]
+    InvalidProofBody,
+    #[error("proof response matches are not the expected matches for this endpoint")]
+    InvalidResponseMatches,
+    #[error("proof response redactions are not allowed")]
+    ResponseRedactionsNotAllowed,
+    #[error("provider hash {0:?} is not allowed")]
+    ProviderHashNotAllowed(Option<String>),
     #[error("unable to deserialize extracted parameters: {0}")]
     InvalidExtractedParameters(serde_json_wasm::de::Error),
     #[error("invalid repo")]
     InvalidRepo,
+    #[error("invalid org")]
+    InvalidOrg,
+    #[error("neither repo {}/{} nor its org have been linked", .0.org, .0.repo)]
+    RepoNotLinked(Repo),
     #[error("pr is not merged")]
     PrNotMerged,
     #[error("invalid user id")]
@@ -38,6 +82,104 @@ pub enum Error {
     InvalidCommitmentKey,
     #[error("user is not admin on the repo")]
     InsufficientPermissions,
+    #[error("the repo proof does not identify the admin, an admin user proof is required")]
+    MissingAdminUserProof,
+    #[error("the admin user proof was not generated by the same prover as the repo proof")]
+    AdminUserProofOwnerMismatch,
+    #[error(
+        "the admin user proof is a proof of user {found}, but the repo proof is of user {expected}"
+    )]
+    AdminIdMismatch { expected: u64, found: u64 },
+    #[error("user is not an active admin of the org")]
+    InsufficientOrgPermissions,
     #[error("invalid commitment")]
     InvalidCommitment,
+    #[error("sender is not the owner of repo {}/{}", .0.org, .0.repo)]
+    NotRepoOwner(Repo),
+    #[error("sender does not have the {1:?} role on repo {}/{}", .0.org, .0.repo)]
+    MissingRepoRole(Repo, RepoRole),
+    #[error("fee bps must be at most 10000, found {0}")]
+    InvalidFeeBps(u16),
+    #[error("exactly {0} must be attached as the protocol fee")]
+    InvalidFeeFunds(Coin),
+    #[error("relayer tips must be non-zero and paid at least once per block")]
+    InvalidRelayerTips,
+    #[error("funds must be attached to fund the tip pool")]
+    NoTipPoolFunds,
+    #[error("the tip pool of the repo only has {0}")]
+    InsufficientTipPool(Coin),
+    #[error("reward contract {reward_contract} rejected the config of label {label_id}: {error}")]
+    InvalidRewardConfig {
+        label_id: u64,
+        reward_contract: Addr,
+        error: String,
+    },
+    #[error("invalid tier for label {0}, min_ordinal must be at most max_ordinal")]
+    InvalidTier(u64),
+    #[error("reward contract {0} is not registered")]
+    RewardContractNotRegistered(Addr),
+    #[error("{0:?} is already registered")]
+    RewardContractAlreadyRegistered(RewardContractId),
+    #[error("code id {0} does not exist")]
+    CodeNotFound(u64),
+    #[error("contract {0} does not exist")]
+    ContractNotFound(Addr),
+    #[error("rate limit periods must be non-zero")]
+    InvalidRateLimit,
+    #[error("user {user_id} has reached the reward limit of repo {}/{} until {resets_at}", .repo.org, .repo.repo)]
+    UserRateLimited {
+        repo: Repo,
+        user_id: u64,
+        resets_at: Timestamp,
+    },
+    #[error("repo {}/{} has reached its reward limit until {resets_at}", .repo.org, .repo.repo)]
+    RepoRateLimited { repo: Repo, resets_at: Timestamp },
+    #[error("pr must be open for at least {0} seconds before it is merged")]
+    MergedTooSoon(u64),
+    #[error("the creation and merge times of the pr are unknown")]
+    MissingPrTimestamps,
+    #[error("invalid account details in user proof")]
+    InvalidUserProfile,
+    #[error("the account details of user {0} are unknown, the account must be linked with a user profile proof")]
+    MissingUserProfile(u64),
+    #[error("account of user {user_id} must be at least {min_account_age} seconds old")]
+    AccountTooNew { user_id: u64, min_account_age: u64 },
+    #[error("account of user {user_id} must have at least {min_public_repos} public repos")]
+    TooFewPublicRepos { user_id: u64, min_public_repos: u64 },
+    #[error("account of user {user_id} must have at least {min_followers} followers")]
+    TooFewFollowers { user_id: u64, min_followers: u64 },
+    #[error("pr was merged by its author")]
+    SelfMergedPr,
+    #[error("the user that merged the pr is unknown")]
+    MissingMergedBy,
+    #[error("author {0} of the pr is the admin or owner that linked the repo")]
+    PrAuthorIsLinker(u64),
+    #[error("author association {0:?} is not allowed")]
+    AuthorAssociationNotAllowed(AuthorAssociation),
+    #[error("the association of the author with the repo is unknown")]
+    MissingAuthorAssociation,
+    #[error("challenge period must be at least one block")]
+    InvalidChallengePeriod,
+    #[error("pr {pr_id} of repo {}/{} has no pending reward", .repo.org, .repo.repo)]
+    PendingRewardNotFound { repo: Repo, pr_id: u64 },
+    #[error("the challenge period of the reward ends at height {0}")]
+    ChallengePeriodActive(u64),
+    #[error("the challenge period of the reward ended at height {0}")]
+    ChallengePeriodEnded(u64),
+    #[error("pr {0} has not been rewarded")]
+    PrNotRewarded(u64),
+    #[error("the rewards of pr {pr_id} are {status:?}")]
+    InvalidRewardStatus { pr_id: u64, status: RewardStatus },
+    #[error("pr {0} is not a merged pr of the same repo referencing the reverted pr")]
+    InvalidRevertPr(u64),
+    #[error("invalid approval requirement for label {0}")]
+    InvalidApprovalRequirement(u64),
+    #[error("pr {pr_id} has no reward for label {label_id} waiting for approval")]
+    PendingApprovalNotFound { pr_id: u64, label_id: u64 },
+    #[error("sender is not an approver of the reward of pr {pr_id} for label {label_id}")]
+    NotApprover { pr_id: u64, label_id: u64 },
+    #[error("the reward expired at height {0}")]
+    ApprovalExpired(u64),
+    #[error("unknown reply id {0}")]
+    UnknownReplyId(u64),
 }
@@ -0,0 +1,420 @@
+//! The events emitted by lazydev, following the schema of [`lazydev_reward_interface::event`].
+
+use std::{fmt::Display, str::FromStr};
+
+use cosmwasm_std::{Addr, Binary, Coin};
+pub use lazydev_reward_interface::event::{
+    AttributeValue, DecodeError, EventField, RewardEvent, RewardFeeEvent, TypedEvent, REWARD,
+    REWARD_FEE, VERSION,
+};
+use lazydev_reward_interface::{display_attribute_value, json_attribute_value, typed_events};
+
+use crate::{
+    forge::Forge,
+    state::{
+        Config, Pause, Repo, RepoConfig, RepoPause, RepoRole, RewardContractId,
+        RewardContractMetadata,
+    },
+};
+
+pub const COMMIT: &str = "commit";
+pub const LINK_ACCOUNT: &str = "link_account";
+pub const LINK_REPO: &str = "link_repo";
+pub const LINK_ORG: &str = "link_org";
+pub const UPDATE_CONFIG: &str = "update_config";
+pub const UPDATE_REPO_CONFIG: &str = "update_repo_config";
+pub const GRANT_REPO_ROLE: &str = "grant_repo_role";
+pub const REVOKE_REPO_ROLE: &str = "revoke_repo_role";
+pub const TRANSFER_REPO_OWNERSHIP: &str = "transfer_repo_ownership";
+pub const UPDATE_PAUSE: &str = "update_pause";
+pub const UPDATE_REPO_PAUSE: &str = "update_repo_pause";
+pub const PR_REWARD: &str = "pr_reward";
+pub const REWARD_DISPATCHED: &str = "reward_dispatched";
+pub const FEE_COLLECTED: &str = "fee_collected";
+pub const FEE_REFUNDED: &str = "fee_refunded";
+pub const UPDATE_FEE_EXEMPTION: &str = "update_fee_exemption";
+pub const UPDATE_REWARD_REGISTRY: &str = "update_reward_registry";
+pub const REWARD_PENDING: &str = "reward_pending";
+pub const REWARD_VETOED: &str = "reward_vetoed";
+pub const REWARD_FINALIZED: &str = "reward_finalized";
+pub const PR_REVERTED: &str = "pr_reverted";
+pub const REVOKE_FAILED: &str = "revoke_failed";
+pub const REWARD_AWAITING_APPROVAL: &str = "reward_awaiting_approval";
+pub const REWARD_APPROVED: &str = "reward_approved";
+pub const UPDATE_REWARD_RECIPIENT: &str = "update_reward_recipient";
+pub const FUND_TIP_POOL: &str = "fund_tip_pool";
+pub const WITHDRAW_TIP_POOL: &str = "withdraw_tip_pool";
+pub const RELAYER_TIP: &str = "relayer_tip";
+
+pub mod attribute {
+    pub use lazydev_reward_interface::event::attribute::*;
+
+    pub const KIND: &str = "kind";
+    pub const KEY: &str = "key";
+    pub const VALUE: &str = "value";
+    pub const COMMITMENT_KEY: &str = "commitment_key";
+    pub const OWNER: &str = "owner";
+    pub const CONFIG: &str = "config";
+    pub const SENDER: &str = "sender";
+    pub const ADDRESS: &str = "address";
+    pub const ROLE: &str = "role";
+    pub const PREVIOUS_OWNER: &str = "previous_owner";
+    pub const NEW_OWNER: &str = "new_owner";
+    pub const LABEL: &str = "label";
+    pub const REWARD_CONTRACT: &str = "reward_contract";
+    pub const ERROR: &str = "error";
+    pub const PAUSE: &str = "pause";
+    pub const EXEMPT: &str = "exempt";
+    pub const METADATA: &str = "metadata";
+    pub const CHALLENGE_ENDS_AT_HEIGHT: &str = "challenge_ends_at_height";
+    pub const REVERT_PR: &str = "revert_pr";
+    pub const EXPIRES_AT_HEIGHT: &str = "expires_at_height";
+    pub const APPROVALS: &str = "approvals";
+    pub const AMOUNT: &str = "amount";
+    pub const RELAYER: &str = "relayer";
+    pub const TIP: &str = "tip";
+}
+
+display_attribute_value!(CommitKind, TipKind);
+json_attribute_value!(
+    Config,
+    RepoConfig,
+    RepoRole,
+    Pause,
+    RepoPause,
+    RewardContractId,
+    RewardContractMetadata
+);
+
+typed_events! {
+    /// A commitment was stored, see [`crate::contract`].
+    pub struct CommitEvent = COMMIT {
+        pub kind: CommitKind = attribute::KIND,
+        /// The json encoded key of the commitment.
+        pub key: String = attribute::KEY,
+        /// The json encoded committed value.
+        pub value: String = attribute::VALUE,
+        pub commitment_key: Binary = attribute::COMMITMENT_KEY,
+    }
+
+    /// A forge account was linked to an address.
+    pub struct LinkAccountEvent = LINK_ACCOUNT {
+        pub forge: Forge = attribute::FORGE,
+        pub user_id: u64 = attribute::USER,
+        pub recipient: Addr = attribute::RECIPIENT,
+    }
+
+    /// A linked user set or removed the recipient of the rewards of a reward contract.
+    pub struct UpdateRewardRecipientEvent = UPDATE_REWARD_RECIPIENT {
+        pub forge: Forge = attribute::FORGE,
+        pub user_id: u64 = attribute::USER,
+        pub reward_contract: RewardContractId = attribute::REWARD_CONTRACT,
+        /// The new recipient, or [`None`] if it was removed and rewards are sent to the linked
+        /// address.
+        pub recipient: Option<Addr> = attribute::RECIPIENT,
+    }
+
+    /// A repo was linked.
+    pub struct LinkRepoEvent = LINK_REPO {
+        pub repo: Repo = attribute::REPO,
+        pub owner: Addr = attribute::OWNER,
+    }
+
+    /// An org was linked.
+    pub struct LinkOrgEvent = LINK_ORG {
+        pub forge: Forge = attribute::FORGE,
+        pub org: String = attribute::ORG,
+    }
+
+    /// The contract config was updated.
+    pub struct UpdateConfigEvent = UPDATE_CONFIG {
+        pub config: Config = attribute::CONFIG,
+    }
+
+    /// The config of a linked repo was updated.
+    pub struct UpdateRepoConfigEvent = UPDATE_REPO_CONFIG {
+        pub repo: Repo = attribute::REPO,
+        pub sender: Addr = attribute::SENDER,
+        pub config: RepoConfig = attribute::CONFIG,
+    }
+
+    /// A role on a repo was granted to an address.
+    pub struct GrantRepoRoleEvent = GRANT_REPO_ROLE {
+        pub repo: Repo = attribute::REPO,
+        pub address: Addr = attribute::ADDRESS,
+        pub role: RepoRole = attribute::ROLE,
+    }
+
+    /// A role on a repo was revoked from an address.
+    pub struct RevokeRepoRoleEvent = REVOKE_REPO_ROLE {
+        pub repo: Repo = attribute::REPO,
+        pub address: Addr = attribute::ADDRESS,
+        pub role: RepoRole = attribute::ROLE,
+    }
+
+    /// The ownership of a repo was transferred.
+    pub struct TransferRepoOwnershipEvent = TRANSFER_REPO_OWNERSHIP {
+        pub repo: Repo = attribute::REPO,
+        pub previous_owner: Addr = attribute::PREVIOUS_OWNER,
+        pub new_owner: Addr = attribute::NEW_OWNER,
+    }
+
+    /// The global pause state was updated.
+    pub struct UpdatePauseEvent = UPDATE_PAUSE {
+        pub sender: Addr = attribute::SENDER,
+        /// The new pause state.
+        pub pause: Pause = attribute::PAUSE,
+    }
+
+    /// A repo was paused or unpaused.
+    pub struct UpdateRepoPauseEvent = UPDATE_REPO_PAUSE {
+        pub repo: Repo = attribute::REPO,
+        pub sender: Addr = attribute::SENDER,
+        /// The new pause of the repo, or [`None`] if it was unpaused.
+        pub pause: Option<RepoPause> = attribute::PAUSE,
+    }
+
+    /// A merged PR was claimed. This is emitted once per PR, along with one
+    /// [`RewardDispatchedEvent`] per matching label config.
+    pub struct PrRewardEvent = PR_REWARD {
+        pub repo: Repo = attribute::REPO,
+        pub pr_id: u64 = attribute::PR,
+        pub user_id: u64 = attribute::USER,
+        pub recipient: Addr = attribute::RECIPIENT,
+    }
+
+    /// The rewards of a PR were recorded as pending instead of being dispatched, since its repo has
+    /// a challenge period. This is emitted instead of the [`RewardDispatchedEvent`]s, which are
+    /// emitted once the reward is finalized.
+    pub struct RewardPendingEvent = REWARD_PENDING {
+        pub repo: Repo = attribute::REPO,
+        pub pr_id: u64 = attribute::PR,
+        pub challenge_ends_at_height: u64 = attribute::CHALLENGE_ENDS_AT_HEIGHT,
+    }
+
+    /// A pending reward was vetoed, and will never be dispatched.
+    pub struct RewardVetoedEvent = REWARD_VETOED {
+        pub repo: Repo = attribute::REPO,
+        pub pr_id: u64 = attribute::PR,
+        pub sender: Addr = attribute::SENDER,
+    }
+
+    /// A pending reward was finalized after its challenge period, along with one
+    /// [`RewardDispatchedEvent`] per reward.
+    pub struct RewardFinalizedEvent = REWARD_FINALIZED {
+        pub repo: Repo = attribute::REPO,
+        pub pr_id: u64 = attribute::PR,
+    }
+
+    /// A rewarded PR was marked as reverted. A [`RewardExecuteMsg::Revoke`] was sent to the reward
+    /// contract of every reward of the PR.
+    ///
+    /// [`RewardExecuteMsg::Revoke`]: crate::msg::RewardExecuteMsg::Revoke
+    pub struct PrRevertedEvent = PR_REVERTED {
+        pub repo: Repo = attribute::REPO,
+        pub pr_id: u64 = attribute::PR,
+        pub sender: Addr = attribute::SENDER,
+        /// The PR that reverted `pr_id`, if it was proven.
+        pub revert_pr_id: Option<u64> = attribute::REVERT_PR,
+    }
+
+    /// The reward of a label config with an approval requirement was recorded as waiting for its
+    /// approvers instead of being dispatched.
+    pub struct RewardAwaitingApprovalEvent = REWARD_AWAITING_APPROVAL {
+        pub repo: Repo = attribute::REPO,
+        pub pr_id: u64 = attribute::PR,
+        pub label_id: u64 = attribute::LABEL,
+        pub expires_at_height: u64 = attribute::EXPIRES_AT_HEIGHT,
+    }
+
+    /// An approver approved a reward. Once the threshold is met, this is followed by a
+    /// [`RewardDispatchedEvent`], unless the PR is still in its challenge period.
+    pub struct RewardApprovedEvent = REWARD_APPROVED {
+        pub repo: Repo = attribute::REPO,
+        pub pr_id: u64 = attribute::PR,
+        pub label_id: u64 = attribute::LABEL,
+        pub sender: Addr = attribute::SENDER,
+        /// The number of approvals of the reward, including this one.
+        pub approvals: u64 = attribute::APPROVALS,
+    }
+
+    /// A reward contract failed to revoke the reward of a reverted PR.
+    pub struct RevokeFailedEvent = REVOKE_FAILED {
+        pub repo: Repo = attribute::REPO,
+        pub pr_id: u64 = attribute::PR,
+        pub label_id: u64 = attribute::LABEL,
+        pub reward_contract: Addr = attribute::REWARD_CONTRACT,
+        pub error: String = attribute::ERROR,
+    }
+
+    /// A reward message was sent to a reward contract. Rewards are atomic with the PR being marked
+    /// as rewarded, so a reward contract failing reverts the whole message, and this event is only
+    /// emitted for rewards that succeeded.
+    pub struct RewardDispatchedEvent = REWARD_DISPATCHED {
+        pub repo: Repo = attribute::REPO,
+        pub pr_id: u64 = attribute::PR,
+        pub label_id: u64 = attribute::LABEL,
+        pub reward_contract: Addr = attribute::REWARD_CONTRACT,
+    }
+
+    /// The flat protocol fee attached to a [`RewardPr`][crate::msg::ExecuteMsg::RewardPr] was sent
+    /// to the treasury.
+    pub struct FeeCollectedEvent = FEE_COLLECTED {
+        pub repo: Repo = attribute::REPO,
+        pub pr_id: u64 = attribute::PR,
+        pub fee: Coin = attribute::FEE,
+        pub treasury: Addr = attribute::TREASURY,
+    }
+
+    /// The flat protocol fee of a vetoed reward was refunded to the relayer that paid it.
+    pub struct FeeRefundedEvent = FEE_REFUNDED {
+        pub repo: Repo = attribute::REPO,
+        pub pr_id: u64 = attribute::PR,
+        pub fee: Coin = attribute::FEE,
+        pub relayer: Addr = attribute::RELAYER,
+    }
+
+    /// A repo was exempted from the protocol fee, or its exemption was removed.
+    pub struct UpdateFeeExemptionEvent = UPDATE_FEE_EXEMPTION {
+        pub repo: Repo = attribute::REPO,
+        pub exempt: bool = attribute::EXEMPT,
+    }
+
+    /// Funds were added to the tip pool of a repo. This is emitted once per denom.
+    pub struct FundTipPoolEvent = FUND_TIP_POOL {
+        pub repo: Repo = attribute::REPO,
+        pub sender: Addr = attribute::SENDER,
+        pub amount: Coin = attribute::AMOUNT,
+    }
+
+    /// Funds were withdrawn from the tip pool of a repo by its owner.
+    pub struct WithdrawTipPoolEvent = WITHDRAW_TIP_POOL {
+        pub repo: Repo = attribute::REPO,
+        pub recipient: Addr = attribute::RECIPIENT,
+        pub amount: Coin = attribute::AMOUNT,
+    }
+
+    /// A relayer was tipped from the tip pool of a repo for the reward of a PR.
+    pub struct RelayerTipEvent = RELAYER_TIP {
+        pub repo: Repo = attribute::REPO,
+        pub pr_id: u64 = attribute::PR,
+        pub kind: TipKind = attribute::KIND,
+        pub relayer: Addr = attribute::RELAYER,
+        pub tip: Coin = attribute::TIP,
+    }
+
+    /// A code id or contract was added to or removed from the reward registry.
+    pub struct UpdateRewardRegistryEvent = UPDATE_REWARD_REGISTRY {
+        pub reward_contract: RewardContractId = attribute::REWARD_CONTRACT,
+        /// The metadata of the entry, or [`None`] if it was removed.
+        pub metadata: Option<RewardContractMetadata> = attribute::METADATA,
+    }
+}
+
+/// The kind of commitment in a [`CommitEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitKind {
+    Account,
+    Repo,
+    Org,
+}
+
+impl Display for CommitKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            CommitKind::Account => "account",
+            CommitKind::Repo => "repo",
+            CommitKind::Org => "org",
+        })
+    }
+}
+
+impl FromStr for CommitKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "account" => Ok(CommitKind::Account),
+            "repo" => Ok(CommitKind::Repo),
+            "org" => Ok(CommitKind::Org),
+            _ => Err(()),
+        }
+    }
+}
+
+/// The message that a relayer was tipped for in a [`RelayerTipEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TipKind {
+    RewardPr,
+    LinkAccount,
+}
+
+impl Display for TipKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            TipKind::RewardPr => "reward_pr",
+            TipKind::LinkAccount => "link_account",
+        })
+    }
+}
+
+impl FromStr for TipKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "reward_pr" => Ok(TipKind::RewardPr),
+            "link_account" => Ok(TipKind::LinkAccount),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{Event, Uint128};
+
+    use super::*;
+    use crate::models::reward::PrReward;
+
+    #[test]
+    fn roundtrip() {
+        let event = RewardEvent {
+            reward: PrReward::Token {
+                denom: "lazy".to_owned(),
+                amount: Uint128::new(100),
+            },
+            repo: Repo {
+                forge: Forge::Gitlab {
+                    host: "gitlab.com".to_owned(),
+                },
+                org: "gitlab-org".to_owned(),
+                repo: "gitlab".to_owned(),
+            },
+            pr_id: 1,
+            user_id: 2,
+            recipient: Addr::unchecked("recipient"),
+        };
+
+        let mut raw = Event::from(event.clone());
+
+        assert_eq!(RewardEvent::try_from(&raw), Ok(event.clone()));
+
+        raw.ty = format!("wasm-{}", raw.ty);
+        assert_eq!(RewardEvent::try_from(&raw), Ok(event));
+
+        assert_eq!(
+            PrRewardEvent::try_from(&raw),
+            Err(DecodeError::InvalidType {
+                expected: PR_REWARD,
+                found: "wasm-reward".to_owned()
+            })
+        );
+
+        raw = raw.add_attribute(attribute::USER, "3");
+        assert_eq!(
+            RewardEvent::try_from(&raw),
+            Err(DecodeError::DuplicateAttribute(attribute::USER))
+        );
+    }
+}
@@ -0,0 +1,428 @@
+use cosmwasm_std::Timestamp;
+
+use crate::{
+    error::Error,
+    models::{
+        github::{AuthorAssociation, PrBody, UserRepoBody},
+        gitlab::{MergeRequestBody, ProjectBody, MAINTAINER_ACCESS_LEVEL},
+        reclaim::JsonExtractedParameters,
+    },
+    state::Repo,
+    utils::{
+        parse_gitlab_merge_request_url, parse_gitlab_project_url, parse_org_membership_url,
+        parse_repos_pull_request_url, parse_repos_url, parse_rfc3339,
+    },
+};
+
+pub use lazydev_reward_interface::forge::Forge;
+
+/// A pull request (or merge request), normalized across all supported forges.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullRequest {
+    /// The pull request number, or the `iid` on GitLab.
+    pub number: u64,
+    /// The forge user id of the author of the pull request.
+    pub author_id: u64,
+    pub merged: bool,
+    pub label_ids: Vec<u64>,
+    /// When the pull request was created, if known.
+    pub created_at: Option<Timestamp>,
+    /// When the pull request was merged, if known.
+    pub merged_at: Option<Timestamp>,
+    /// The forge user id of the user that merged the pull request, if known.
+    pub merged_by_id: Option<u64>,
+    /// The relationship of the author to the repo, if known. This is only returned by github.
+    pub author_association: Option<AuthorAssociation>,
+    /// The description of the pull request.
+    pub body: Option<String>,
+}
+
+impl PullRequest {
+    /// Whether the description of this pull request references the pull request `pr_id` of the
+    /// same repo, as `#{pr_id}` (or `!{pr_id}` on GitLab).
+    #[must_use]
+    pub fn references(&self, pr_id: u64) -> bool {
+        let Some(body) = &self.body else {
+            return false;
+        };
+
+        ["#", "!"].into_iter().any(|prefix| {
+            let reference = format!("{prefix}{pr_id}");
+
+            body.match_indices(&reference).any(|(index, _)| {
+                !body[index + reference.len()..].starts_with(|c: char| c.is_ascii_digit())
+            })
+        })
+    }
+}
+
+/// The permissions of the authenticated user of a repo proof, see
+/// [`ForgeExt::deserialize_repo_admin`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoAdmin {
+    pub is_admin: bool,
+    /// The forge user id of the authenticated user, if it can be determined from the response.
+    /// This is only the case for github repos owned by a user, since only the owner can be an
+    /// admin of those. Otherwise the admin is identified by a separate user proof.
+    pub user_id: Option<u64>,
+}
+
+/// Forge specific urls and proof bodies, implemented on [`Forge`].
+pub trait ForgeExt {
+    /// The base url of the REST API of this forge, without a trailing slash.
+    #[must_use]
+    fn api_base_url(&self) -> String;
+
+    /// The url of the authenticated user endpoint, used to prove ownership of an account.
+    #[must_use]
+    fn user_url(&self) -> String;
+
+    /// The url of the repo endpoint, used to prove admin permissions on a repo.
+    #[must_use]
+    fn repo_url(&self, org: &str, repo: &str) -> String;
+
+    /// The url of the pull request endpoint, used to prove a merged pull request.
+    ///
+    /// For GitLab, this is the merge request list endpoint filtered to the single merge request,
+    /// since the single merge request endpoint does not return label ids.
+    #[must_use]
+    fn pull_request_url(&self, org: &str, repo: &str, number: u64) -> String;
+
+    /// Whether orgs on this forge can be linked with an org membership proof.
+    #[must_use]
+    fn supports_org_linking(&self) -> bool;
+
+    /// Parse a pull request url of this forge into the repo and pull request number.
+    ///
+    /// Only the canonical url as returned by [`Self::pull_request_url`] is accepted.
+    #[must_use]
+    fn parse_pull_request_url(&self, url: &str) -> Option<(Repo, u64)>;
+
+    /// Parse a repo url of this forge into the repo.
+    ///
+    /// Only the canonical url as returned by [`Self::repo_url`] is accepted.
+    #[must_use]
+    fn parse_repo_url(&self, url: &str) -> Option<Repo>;
+
+    /// Parse an org membership url of the authenticated user of this forge into the org, or
+    /// [`None`] if this forge does not [support org linking][Self::supports_org_linking].
+    #[must_use]
+    fn parse_org_membership_url<'a>(&self, url: &'a str) -> Option<&'a str>;
+
+    /// Deserialize the extracted json of a pull request proof of this forge.
+    fn deserialize_pull_request(
+        &self,
+        parameters: &JsonExtractedParameters,
+    ) -> Result<PullRequest, Error>;
+
+    /// Deserialize the extracted json of a repo proof of this forge, returning whether the
+    /// authenticated user has admin permissions on the repo.
+    fn deserialize_repo_admin(
+        &self,
+        parameters: &JsonExtractedParameters,
+    ) -> Result<RepoAdmin, Error>;
+}
+
+impl ForgeExt for Forge {
+    fn api_base_url(&self) -> String {
+        match self {
+            Forge::Github => "https://api.github.com".to_owned(),
+            Forge::GithubEnterprise { host } => format!("https://{host}/api/v3"),
+            Forge::Gitlab { host } => format!("https://{host}/api/v4"),
+            Forge::Gitea { host } => format!("https://{host}/api/v1"),
+        }
+    }
+
+    fn user_url(&self) -> String {
+        format!("{}/user", self.api_base_url())
+    }
+
+    fn repo_url(&self, org: &str, repo: &str) -> String {
+        match self {
+            Forge::Gitlab { .. } => format!(
+                "{}/projects/{}",
+                self.api_base_url(),
+                gitlab_project_path(org, repo)
+            ),
+            _ => format!("{}/repos/{org}/{repo}", self.api_base_url()),
+        }
+    }
+
+    fn pull_request_url(&self, org: &str, repo: &str, number: u64) -> String {
+        match self {
+            Forge::Gitlab { .. } => format!(
+                "{}/projects/{}/merge_requests?iids[]={number}&with_labels_details=true",
+                self.api_base_url(),
+                gitlab_project_path(org, repo)
+            ),
+            _ => format!("{}/repos/{org}/{repo}/pulls/{number}", self.api_base_url()),
+        }
+    }
+
+    fn supports_org_linking(&self) -> bool {
+        matches!(self, Forge::Github | Forge::GithubEnterprise { .. })
+    }
+
+    fn parse_pull_request_url(&self, url: &str) -> Option<(Repo, u64)> {
+        let base = self.api_base_url();
+
+        let (org, repo, number) = if let Forge::Gitlab { .. } = self {
+            let (project, iid) = parse_gitlab_merge_request_url(&base, url)?;
+            let (org, repo) = split_gitlab_project_path(project)?;
+            (org, repo.to_owned(), iid)
+        } else {
+            let (org, repo, number) = parse_repos_pull_request_url(&base, url)?;
+            (org.to_owned(), repo.to_owned(), number)
+        };
+
+        (self.pull_request_url(&org, &repo, number) == url).then(|| {
+            (
+                Repo {
+                    forge: self.clone(),
+                    org,
+                    repo,
+                },
+                number,
+            )
+        })
+    }
+
+    fn parse_repo_url(&self, url: &str) -> Option<Repo> {
+        let base = self.api_base_url();
+
+        let (org, repo) = if let Forge::Gitlab { .. } = self {
+            let (org, repo) = split_gitlab_project_path(parse_gitlab_project_url(&base, url)?)?;
+            (org, repo.to_owned())
+        } else {
+            let (org, repo) = parse_repos_url(&base, url)?;
+            (org.to_owned(), repo.to_owned())
+        };
+
+        (self.repo_url(&org, &repo) == url).then(|| Repo {
+            forge: self.clone(),
+            org,
+            repo,
+        })
+    }
+
+    fn parse_org_membership_url<'a>(&self, url: &'a str) -> Option<&'a str> {
+        if !self.supports_org_linking() {
+            return None;
+        }
+
+        parse_org_membership_url(&self.api_base_url(), url)
+    }
+
+    fn deserialize_pull_request(
+        &self,
+        parameters: &JsonExtractedParameters,
+    ) -> Result<PullRequest, Error> {
+        Ok(match self {
+            Forge::Gitlab { .. } => parameters.deserialize_json::<MergeRequestBody>()?.into(),
+            // gitea's pull request response is compatible with github's
+            _ => parameters.deserialize_json::<PrBody>()?.into(),
+        })
+    }
+
+    fn deserialize_repo_admin(
+        &self,
+        parameters: &JsonExtractedParameters,
+    ) -> Result<RepoAdmin, Error> {
+        Ok(if let Forge::Gitlab { .. } = self {
+            let body = parameters.deserialize_json::<ProjectBody>()?;
+
+            let is_admin = body
+                .permissions
+                .project_access
+                .into_iter()
+                .chain(body.permissions.group_access)
+                .any(|access| access.access_level >= MAINTAINER_ACCESS_LEVEL);
+
+            RepoAdmin {
+                is_admin,
+                // the response does not identify the authenticated user, and members of
+                // personal projects can be maintainers as well
+                user_id: None,
+            }
+        } else {
+            // gitea's repo response is compatible with github's
+            let body = parameters.deserialize_json::<UserRepoBody>()?;
+
+            RepoAdmin {
+                is_admin: body.permissions.admin,
+                // gitea does not return the type of the owner, and collaborators of user repos
+                // can be admins on gitea
+                user_id: body
+                    .owner
+                    .filter(|owner| owner.ty.as_deref() == Some("User"))
+                    .map(|owner| owner.id),
+            }
+        })
+    }
+}
+
+fn gitlab_project_path(org: &str, repo: &str) -> String {
+    format!("{}%2F{repo}", org.replace('/', "%2F"))
+}
+
+/// Split an url-encoded gitlab project path into the (decoded) namespace and project name.
+/// Namespaces may be nested groups.
+fn split_gitlab_project_path(project: &str) -> Option<(String, &str)> {
+    let (namespace, repo) = project.rsplit_once("%2F")?;
+
+    (!namespace.is_empty() && !repo.is_empty() && !repo.contains('/'))
+        .then(|| (namespace.replace("%2F", "/"), repo))
+}
+
+impl From<PrBody> for PullRequest {
+    fn from(body: PrBody) -> Self {
+        Self {
+            number: body.number,
+            author_id: body.user.id,
+            merged: body.merged,
+            label_ids: body.labels.into_iter().map(|label| label.id).collect(),
+            created_at: body.created_at.as_deref().and_then(parse_rfc3339),
+            merged_at: body.merged_at.as_deref().and_then(parse_rfc3339),
+            merged_by_id: body.merged_by.map(|user| user.id),
+            author_association: body.author_association,
+            body: body.body,
+        }
+    }
+}
+
+impl From<MergeRequestBody> for PullRequest {
+    fn from(body: MergeRequestBody) -> Self {
+        Self {
+            number: body.iid,
+            author_id: body.author.id,
+            merged: body.merged_at.is_some(),
+            label_ids: body.labels.into_iter().map(|label| label.id).collect(),
+            created_at: body.created_at.as_deref().and_then(parse_rfc3339),
+            merged_at: body.merged_at.as_deref().and_then(parse_rfc3339),
+            merged_by_id: body.merged_by.map(|user| user.id),
+            author_association: None,
+            body: body.description,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_pull_request_url() {
+        assert_eq!(
+            Forge::Github
+                .parse_pull_request_url("https://api.github.com/repos/benluelo/test/pulls/1")
+                .unwrap(),
+            (Repo::from(("benluelo", "test")), 1)
+        );
+
+        let forge = Forge::Gitlab {
+            host: "gitlab.com".to_owned(),
+        };
+
+        assert_eq!(
+            forge
+                .parse_pull_request_url(
+                    "https://gitlab.com/api/v4/projects/group%2Fsubgroup%2Fproject/merge_requests?iids[]=7&with_labels_details=true"
+                )
+                .unwrap(),
+            (
+                Repo {
+                    forge: forge.clone(),
+                    org: "group/subgroup".to_owned(),
+                    repo: "project".to_owned(),
+                },
+                7
+            )
+        );
+
+        let forge = Forge::Gitea {
+            host: "gitea.com".to_owned(),
+        };
+
+        assert_eq!(
+            forge
+                .parse_pull_request_url("https://gitea.com/api/v1/repos/gitea/tea/pulls/42")
+                .unwrap()
+                .1,
+            42
+        );
+
+        // non-canonical urls are rejected
+        assert_eq!(
+            Forge::Github
+                .parse_pull_request_url("https://api.github.com/repos/benluelo/test/pulls/01"),
+            None
+        );
+        assert_eq!(
+            forge.parse_pull_request_url("https://api.github.com/repos/benluelo/test/pulls/1"),
+            None
+        );
+    }
+
+    #[test]
+    fn parse_repo_url() {
+        let forge = Forge::GithubEnterprise {
+            host: "github.example.com".to_owned(),
+        };
+
+        assert_eq!(
+            forge
+                .parse_repo_url("https://github.example.com/api/v3/repos/atahanyild/NEVO")
+                .unwrap(),
+            Repo {
+                forge,
+                org: "atahanyild".to_owned(),
+                repo: "NEVO".to_owned(),
+            }
+        );
+
+        assert_eq!(
+            Forge::Github.parse_repo_url("https://api.github.com/repos/atahanyild/NEVO/pulls"),
+            None
+        );
+    }
+
+    #[test]
+    fn display_from_str() {
+        for forge in [
+            Forge::Github,
+            Forge::GithubEnterprise {
+                host: "github.example.com".to_owned(),
+            },
+            Forge::Gitlab {
+                host: "gitlab.example.com:8443".to_owned(),
+            },
+            Forge::Gitea {
+                host: "gitea.com".to_owned(),
+            },
+        ] {
+            assert_eq!(forge.to_string().parse::<Forge>().unwrap(), forge);
+        }
+    }
+
+    #[test]
+    fn pull_request_references() {
+        let pull_request = |body: Option<&str>| PullRequest {
+            number: 2,
+            author_id: 1,
+            merged: true,
+            label_ids: vec![],
+            created_at: None,
+            merged_at: None,
+            merged_by_id: None,
+            author_association: None,
+            body: body.map(ToOwned::to_owned),
+        };
+
+        assert!(pull_request(Some("Reverts #12")).references(12));
+        assert!(pull_request(Some("This reverts !12.")).references(12));
+        assert!(pull_request(Some("See #123, reverts #12")).references(12));
+        assert!(!pull_request(Some("Reverts #123")).references(12));
+        assert!(!pull_request(Some("Reverts 12")).references(12));
+        assert!(!pull_request(None).references(12));
+    }
+}
@@ -1,3 +1,4 @@
 pub mod github;
+pub mod gitlab;
 pub mod reclaim;
 pub mod reward;
@@ -3,6 +3,8 @@ use cosmwasm_schema::cw_serde;
 /// Simplified model of the response from [`/repos/{owner}/{repo}/pulls/{pull_number}`](get-pr)
 /// endpoint, containing only the fields we require. All additional fields will be ignored by serde.
 ///
+/// This is also used for GitHub Enterprise Server and Gitea, which return a compatible response.
+///
 /// [get-pr]: https://docs.github.com/en/rest/pulls/pulls?apiVersion=2022-11-28#get-a-pull-request
 #[cw_serde]
 pub struct PrBody {
@@ -11,6 +13,51 @@ pub struct PrBody {
     pub user: User,
     pub merged: bool,
     pub labels: Vec<Label>,
+    /// The RFC 3339 timestamp of the creation of the pull request.
+    #[serde(default)]
+    pub created_at: Option<String>,
+    /// The RFC 3339 timestamp of the merge of the pull request, if it has been merged.
+    #[serde(default)]
+    pub merged_at: Option<String>,
+    /// The user that merged the pull request, if it has been merged.
+    #[serde(default)]
+    pub merged_by: Option<User>,
+    /// The relationship of the author to the repo. This is not returned by gitea.
+    #[serde(default)]
+    pub author_association: Option<AuthorAssociation>,
+    /// The description of the pull request.
+    #[serde(default)]
+    pub body: Option<String>,
+}
+
+/// See <https://docs.github.com/en/graphql/reference/enums#commentauthorassociation>.
+#[cw_serde]
+#[derive(Eq)]
+pub enum AuthorAssociation {
+    /// The author has been invited to collaborate on the repository.
+    #[serde(rename = "COLLABORATOR")]
+    Collaborator,
+    /// The author has previously committed to the repository.
+    #[serde(rename = "CONTRIBUTOR")]
+    Contributor,
+    /// The author has not previously committed to GitHub.
+    #[serde(rename = "FIRST_TIMER")]
+    FirstTimer,
+    /// The author has not previously committed to the repository.
+    #[serde(rename = "FIRST_TIME_CONTRIBUTOR")]
+    FirstTimeContributor,
+    /// The author is a placeholder for an unclaimed user.
+    #[serde(rename = "MANNEQUIN")]
+    Mannequin,
+    /// The author is a member of the organization that owns the repository.
+    #[serde(rename = "MEMBER")]
+    Member,
+    /// The author has no association with the repository.
+    #[serde(rename = "NONE")]
+    None,
+    /// The author is the owner of the repository.
+    #[serde(rename = "OWNER")]
+    Owner,
 }
 
 #[cw_serde]
@@ -23,12 +70,53 @@ pub struct User {
     pub id: u64,
 }
 
+/// Simplified model of the response from the [`/repos/{owner}/{repo}`](get-repo) endpoint, called
+/// with an authenticated user.
+///
+/// This is also used for GitHub Enterprise Server and Gitea, which return a compatible response.
+///
+/// [get-repo]: https://docs.github.com/en/rest/repos/repos?apiVersion=2022-11-28#get-a-repository
 #[cw_serde]
 pub struct UserRepoBody {
     pub permissions: Permissions,
+    #[serde(default)]
+    pub owner: Option<Owner>,
+}
+
+#[cw_serde]
+pub struct Owner {
+    pub id: u64,
+    /// Either `User` or `Organization`. This is not returned by gitea.
+    #[serde(rename = "type", default)]
+    pub ty: Option<String>,
 }
 
 #[cw_serde]
 pub struct Permissions {
     pub admin: bool,
 }
+
+/// Simplified model of the response from the [`/user/memberships/orgs/{org}`](get-membership)
+/// endpoint, containing only the fields we require.
+///
+/// [get-membership]: https://docs.github.com/en/rest/orgs/members?apiVersion=2022-11-28#get-an-organization-membership-for-the-authenticated-user
+#[cw_serde]
+pub struct OrgMembershipBody {
+    pub state: MembershipState,
+    pub role: MembershipRole,
+    #[serde(default)]
+    pub user: Option<User>,
+}
+
+#[cw_serde]
+pub enum MembershipState {
+    Active,
+    Pending,
+}
+
+#[cw_serde]
+pub enum MembershipRole {
+    Admin,
+    Member,
+    BillingManager,
+}
@@ -0,0 +1,58 @@
+use cosmwasm_schema::cw_serde;
+
+/// The `access_level` of the maintainer role, the lowest role that is able to manage a project.
+///
+/// See <https://docs.gitlab.com/api/access_requests/#valid-access-levels>.
+pub const MAINTAINER_ACCESS_LEVEL: u64 = 40;
+
+/// Simplified model of a single item of the response from the
+/// [`/projects/{id}/merge_requests`](list-mrs) endpoint, queried with `with_labels_details=true`,
+/// containing only the fields we require. All additional fields will be ignored by serde.
+///
+/// [list-mrs]: https://docs.gitlab.com/api/merge_requests/#list-project-merge-requests
+#[cw_serde]
+pub struct MergeRequestBody {
+    pub iid: u64,
+    pub author: User,
+    /// This is only set once the merge request has been merged.
+    pub merged_at: Option<String>,
+    pub labels: Vec<Label>,
+    /// The RFC 3339 timestamp of the creation of the merge request.
+    #[serde(default)]
+    pub created_at: Option<String>,
+    /// The user that merged the merge request, if it has been merged.
+    #[serde(default)]
+    pub merged_by: Option<User>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[cw_serde]
+pub struct Label {
+    pub id: u64,
+}
+
+#[cw_serde]
+pub struct User {
+    pub id: u64,
+}
+
+/// Simplified model of the response from the [`/projects/{id}`](get-project) endpoint, called with
+/// an authenticated user.
+///
+/// [get-project]: https://docs.gitlab.com/api/projects/#get-a-single-project
+#[cw_serde]
+pub struct ProjectBody {
+    pub permissions: Permissions,
+}
+
+#[cw_serde]
+pub struct Permissions {
+    pub project_access: Option<Access>,
+    pub group_access: Option<Access>,
+}
+
+#[cw_serde]
+pub struct Access {
+    pub access_level: u64,
+}
@@ -1,7 +1,62 @@
 use cosmwasm_schema::cw_serde;
-use serde::de::DeserializeOwned;
+use serde::{de::DeserializeOwned, Deserialize};
 
-use crate::error::Error;
+use crate::{error::Error, state::Config};
+
+/// The regex used to extract the full json response body, for all proofs other than
+/// [`ProofKind::User`].
+pub const JSON_RESPONSE_MATCH: &str = r"(?<json>\{.+\})";
+
+/// The regex used to extract the user id from the response of the authenticated user endpoint.
+pub const USER_ID_RESPONSE_MATCH: &str = r#""id"\s*:\s*(?<id>\d+)"#;
+
+/// The regex used to extract the creation time of the account from the response of the
+/// authenticated user endpoint.
+pub const USER_CREATED_AT_RESPONSE_MATCH: &str = r#""created_at"\s*:\s*"(?<created_at>[^"]+)""#;
+
+/// The regex used to extract the amount of public repos from the response of the authenticated
+/// user endpoint.
+pub const USER_PUBLIC_REPOS_RESPONSE_MATCH: &str = r#""public_repos"\s*:\s*(?<public_repos>\d+)"#;
+
+/// The regex used to extract the amount of followers from the response of the authenticated user
+/// endpoint.
+pub const USER_FOLLOWERS_RESPONSE_MATCH: &str = r#""followers"\s*:\s*(?<followers>\d+)"#;
+
+/// The type of endpoint that a proof is expected to be of. Each kind has exactly one set of
+/// [`ResponseMatch`]es that is accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofKind {
+    /// The authenticated user endpoint, see [`UserExtractedParameters`].
+    User,
+    /// The authenticated user endpoint, additionally extracting the details of the account that
+    /// [`AccountRequirements`][crate::state::AccountRequirements] are checked against.
+    UserProfile,
+    /// The pull request endpoint.
+    PullRequest,
+    /// The repo endpoint, called with an authenticated user.
+    RepoAdmin,
+    /// The org membership endpoint of the authenticated user.
+    OrgAdmin,
+}
+
+impl ProofKind {
+    /// The exact set of response matches that a proof of this kind must have been generated with.
+    #[must_use]
+    pub fn expected_response_matches(self) -> Vec<ResponseMatch> {
+        match self {
+            ProofKind::User => vec![ResponseMatch::regex(USER_ID_RESPONSE_MATCH)],
+            ProofKind::UserProfile => vec![
+                ResponseMatch::regex(USER_ID_RESPONSE_MATCH),
+                ResponseMatch::regex(USER_CREATED_AT_RESPONSE_MATCH),
+                ResponseMatch::regex(USER_PUBLIC_REPOS_RESPONSE_MATCH),
+                ResponseMatch::regex(USER_FOLLOWERS_RESPONSE_MATCH),
+            ],
+            ProofKind::PullRequest | ProofKind::RepoAdmin | ProofKind::OrgAdmin => {
+                vec![ResponseMatch::regex(JSON_RESPONSE_MATCH)]
+            }
+        }
+    }
+}
 
 #[cw_serde]
 pub struct Proof {
@@ -19,7 +74,76 @@ impl Proof {
 
     /// Parse the parameters field of this proof from json.
     pub fn deserialize_parameters(&self) -> Result<Parameters, Error> {
-        serde_json_wasm::from_str(&self.claim_info.parameters).map_err(Error::InvalidContext)
+        serde_json_wasm::from_str(&self.claim_info.parameters).map_err(Error::InvalidParameters)
+    }
+
+    /// Parse the parameters field of this proof and ensure that the proof was generated exactly as
+    /// expected for the provided kind of endpoint:
+    ///
+    /// - the request must be a `GET` with an empty body,
+    /// - the response matches must be exactly [`ProofKind::expected_response_matches`],
+    /// - there must be no response redactions, since they could hide fields of the response,
+    /// - the provider hash of the context must be allowlisted in
+    ///   [`Config::allowed_provider_hashes`], if the allowlist is not empty.
+    ///
+    /// The url is *not* checked here, since it depends on the forge and the kind of endpoint.
+    pub fn validate_parameters(
+        &self,
+        kind: ProofKind,
+        config: &Config,
+    ) -> Result<Parameters, Error> {
+        let parameters = self.deserialize_parameters()?;
+
+        if parameters.method != "GET" {
+            return Err(Error::InvalidProofMethod(parameters.method));
+        }
+
+        if !parameters.body.is_empty() {
+            return Err(Error::InvalidProofBody);
+        }
+
+        if parameters.response_matches != kind.expected_response_matches() {
+            return Err(Error::InvalidResponseMatches);
+        }
+
+        if !parameters.response_redactions.is_empty() {
+            return Err(Error::ResponseRedactionsNotAllowed);
+        }
+
+        if !config.allowed_provider_hashes.is_empty() {
+            #[derive(Deserialize)]
+            struct ProviderHashContext {
+                #[serde(rename = "providerHash")]
+                provider_hash: Option<String>,
+            }
+
+            let provider_hash =
+                serde_json_wasm::from_str::<ProviderHashContext>(&self.claim_info.context)
+                    .map_err(Error::InvalidContext)?
+                    .provider_hash;
+
+            if !provider_hash
+                .as_ref()
+                .is_some_and(|provider_hash| config.allowed_provider_hashes.contains(provider_hash))
+            {
+                return Err(Error::ProviderHashNotAllowed(provider_hash));
+            }
+        }
+
+        Ok(parameters)
+    }
+
+    /// The kind of this proof, if it is a proof of the authenticated user endpoint. Both
+    /// [`ProofKind::User`] and [`ProofKind::UserProfile`] proofs are of the same endpoint, so the
+    /// kind is selected by the response matches that the proof was generated with.
+    pub fn user_proof_kind(&self) -> Result<ProofKind, Error> {
+        let parameters = self.deserialize_parameters()?;
+
+        if parameters.response_matches == ProofKind::UserProfile.expected_response_matches() {
+            Ok(ProofKind::UserProfile)
+        } else {
+            Ok(ProofKind::User)
+        }
     }
 }
 
@@ -27,9 +151,40 @@ impl Proof {
 pub struct Parameters {
     pub body: String,
     pub method: String,
+    #[serde(rename = "responseMatches")]
+    pub response_matches: Vec<ResponseMatch>,
+    #[serde(rename = "responseRedactions")]
+    pub response_redactions: Vec<ResponseRedaction>,
     pub url: String,
 }
 
+#[cw_serde]
+pub struct ResponseMatch {
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub value: String,
+}
+
+impl ResponseMatch {
+    #[must_use]
+    pub fn regex(value: &str) -> Self {
+        Self {
+            ty: "regex".to_owned(),
+            value: value.to_owned(),
+        }
+    }
+}
+
+#[cw_serde]
+pub struct ResponseRedaction {
+    #[serde(rename = "xPath", default)]
+    pub x_path: Option<String>,
+    #[serde(rename = "jsonPath", default)]
+    pub json_path: Option<String>,
+    #[serde(default)]
+    pub regex: Option<String>,
+}
+
 #[cw_serde]
 pub struct ClaimInfo {
     pub provider: String,
@@ -61,6 +216,15 @@ pub struct CompleteClaimData {
 #[cw_serde]
 pub struct UserExtractedParameters {
     pub id: String,
+    /// Only extracted by [`ProofKind::UserProfile`] proofs.
+    #[serde(default)]
+    pub created_at: Option<String>,
+    /// Only extracted by [`ProofKind::UserProfile`] proofs.
+    #[serde(default)]
+    pub public_repos: Option<String>,
+    /// Only extracted by [`ProofKind::UserProfile`] proofs.
+    #[serde(default)]
+    pub followers: Option<String>,
 }
 
 #[cw_serde]
@@ -77,6 +241,8 @@ impl JsonExtractedParameters {
 
 #[cfg(test)]
 mod tests {
+    use cosmwasm_std::Addr;
+
     use super::*;
     use crate::models::github::UserRepoBody;
     // use crate::models::github::UserRepoBody;
@@ -112,5 +278,163 @@ mod tests {
             serde_json_wasm::from_str::<UserRepoBody>(&col.extracted_parameters.json).unwrap();
 
         dbg!(col);
+
+        let mut config = Config {
+            owner: Addr::unchecked("owner"),
+            verifier_address: Addr::unchecked("verifier"),
+            commitment_delay_min_height: 0,
+            commitment_delay_max_height: 1,
+            allowed_forges: vec![],
+            allowed_provider_hashes: vec![],
+            guardian: None,
+            fee: None,
+            reward_registry_enabled: false,
+            account_requirements: None,
+        };
+
+        let parameters = proof
+            .validate_parameters(ProofKind::RepoAdmin, &config)
+            .unwrap();
+        assert_eq!(
+            parameters.url,
+            "https://api.github.com/repos/ahmedzk100/test"
+        );
+
+        assert_eq!(
+            proof.validate_parameters(ProofKind::User, &config),
+            Err(Error::InvalidResponseMatches)
+        );
+
+        config.allowed_provider_hashes = vec!["0x00".to_owned()];
+        assert_eq!(
+            proof.validate_parameters(ProofKind::RepoAdmin, &config),
+            Err(Error::ProviderHashNotAllowed(Some(
+                "0xdcb73385494c6804decf6e8bc9da1beb42ec28d59127ddfdf011781e39cd5541".to_owned()
+            )))
+        );
+
+        config.allowed_provider_hashes =
+            vec!["0xdcb73385494c6804decf6e8bc9da1beb42ec28d59127ddfdf011781e39cd5541".to_owned()];
+        proof
+            .validate_parameters(ProofKind::RepoAdmin, &config)
+            .unwrap();
+    }
+
+    #[test]
+    fn validate_parameters() {
+        let user_proof = |f: &dyn Fn(&mut Parameters), context: &str| {
+            let mut parameters = Parameters {
+                body: String::new(),
+                method: "GET".to_owned(),
+                response_matches: ProofKind::User.expected_response_matches(),
+                response_redactions: vec![],
+                url: "https://api.github.com/user".to_owned(),
+            };
+            f(&mut parameters);
+
+            Proof {
+                claim_info: ClaimInfo {
+                    provider: "http".to_owned(),
+                    parameters: serde_json_wasm::to_string(&parameters).unwrap(),
+                    context: context.to_owned(),
+                },
+                signed_claim: SignedClaim {
+                    claim: CompleteClaimData {
+                        identifier: "0x00".to_owned(),
+                        owner: "0x00".to_owned(),
+                        epoch: 1,
+                        timestamp_s: 0,
+                    },
+                    signatures: vec![],
+                },
+            }
+        };
+        let context = r#"{"extractedParameters":{"id":"1"},"providerHash":"0x01"}"#;
+
+        let mut config = Config {
+            owner: Addr::unchecked("owner"),
+            verifier_address: Addr::unchecked("verifier"),
+            commitment_delay_min_height: 0,
+            commitment_delay_max_height: 1,
+            allowed_forges: vec![],
+            allowed_provider_hashes: vec![],
+            guardian: None,
+            fee: None,
+            reward_registry_enabled: false,
+            account_requirements: None,
+        };
+
+        let proof = user_proof(&|_| {}, context);
+        assert_eq!(proof.user_proof_kind(), Ok(ProofKind::User));
+        proof.validate_parameters(ProofKind::User, &config).unwrap();
+
+        let proof = user_proof(
+            &|parameters| {
+                parameters.response_matches = ProofKind::UserProfile.expected_response_matches();
+            },
+            context,
+        );
+        assert_eq!(proof.user_proof_kind(), Ok(ProofKind::UserProfile));
+        proof
+            .validate_parameters(ProofKind::UserProfile, &config)
+            .unwrap();
+
+        // a profile proof missing a response match is a user proof with the wrong matches
+        let proof = user_proof(
+            &|parameters| {
+                parameters
+                    .response_matches
+                    .push(ResponseMatch::regex(USER_FOLLOWERS_RESPONSE_MATCH));
+            },
+            context,
+        );
+        assert_eq!(proof.user_proof_kind(), Ok(ProofKind::User));
+        assert_eq!(
+            proof.validate_parameters(ProofKind::User, &config),
+            Err(Error::InvalidResponseMatches)
+        );
+
+        assert_eq!(
+            user_proof(&|parameters| parameters.method = "POST".to_owned(), context)
+                .validate_parameters(ProofKind::User, &config),
+            Err(Error::InvalidProofMethod("POST".to_owned()))
+        );
+
+        assert_eq!(
+            user_proof(&|parameters| parameters.body = "{}".to_owned(), context)
+                .validate_parameters(ProofKind::User, &config),
+            Err(Error::InvalidProofBody)
+        );
+
+        assert_eq!(
+            user_proof(
+                &|parameters| {
+                    parameters.response_redactions = vec![ResponseRedaction {
+                        x_path: None,
+                        json_path: Some("$.login".to_owned()),
+                        regex: None,
+                    }];
+                },
+                context
+            )
+            .validate_parameters(ProofKind::User, &config),
+            Err(Error::ResponseRedactionsNotAllowed)
+        );
+
+        config.allowed_provider_hashes = vec!["0x02".to_owned()];
+        assert_eq!(
+            user_proof(&|_| {}, context).validate_parameters(ProofKind::User, &config),
+            Err(Error::ProviderHashNotAllowed(Some("0x01".to_owned())))
+        );
+        assert_eq!(
+            user_proof(&|_| {}, r#"{"extractedParameters":{"id":"1"}}"#)
+                .validate_parameters(ProofKind::User, &config),
+            Err(Error::ProviderHashNotAllowed(None))
+        );
+
+        config.allowed_provider_hashes.push("0x01".to_owned());
+        user_proof(&|_| {}, context)
+            .validate_parameters(ProofKind::User, &config)
+            .unwrap();
     }
 }
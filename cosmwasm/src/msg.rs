@@ -1,9 +1,19 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Binary};
+use cosmwasm_std::{Addr, Binary, Coin, Timestamp};
+pub use lazydev_reward_interface::msg::{
+    ProtocolFee, QueryRewardsResponse, RevokeMsg, RewardExecuteMsg, RewardMsg, RewardQueryMsg,
+    RewardScope, ValidateRewardConfigMsg,
+};
+use serde::{Deserialize, Deserializer};
 
 use crate::{
-    models::{reclaim::Proof, reward::PrReward},
-    state::{Commitment, Config, Repo, RepoConfig},
+    forge::Forge,
+    models::reclaim::Proof,
+    state::{
+        AccountRequirements, Commitment, Config, FeeConfig, Pause, PendingApproval, PendingReward,
+        Repo, RepoConfig, RepoPause, RepoRole, RewardContractId, RewardContractMetadata,
+        RewardRecipient, UserProfile,
+    },
 };
 
 #[cw_serde]
@@ -16,22 +26,265 @@ pub struct InstantiateMsg {
     pub verifier_address: Addr,
     pub commitment_delay_min_height: u64,
     pub commitment_delay_max_height: u64,
+    /// Forges other than github that repos and users are allowed to be linked from.
+    #[serde(default)]
+    pub allowed_forges: Vec<Forge>,
+    /// Reclaim provider hashes that proofs are allowed to be generated with. If empty, the provider
+    /// hash is not checked.
+    #[serde(default)]
+    pub allowed_provider_hashes: Vec<String>,
+    /// An address that is able to pause the contract in addition to the owner.
+    #[serde(default)]
+    pub guardian: Option<Addr>,
+    /// The protocol fee charged on rewards.
+    #[serde(default)]
+    pub fee: Option<FeeConfig>,
+    /// Whether label configs may only use registered reward contracts.
+    #[serde(default)]
+    pub reward_registry_enabled: bool,
+    /// Requirements that accounts must meet to be linked.
+    #[serde(default)]
+    pub account_requirements: Option<AccountRequirements>,
 }
 
 #[cw_serde]
 #[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
+    UpdateConfig(UpdateConfigMsg),
+
     CommitAccount(CommitAccountMsg),
     LinkAccount(LinkAccountMsg),
+    SetRewardRecipient(SetRewardRecipientMsg),
 
     RewardPr(RewardPrMsg),
+    VetoReward(VetoRewardMsg),
+    FinalizeReward(FinalizeRewardMsg),
+    RevertPr(RevertPrMsg),
+    ApproveReward(ApproveRewardMsg),
 
     CommitRepo(CommitRepoMsg),
     LinkRepo(LinkRepoMsg),
+
+    CommitOrg(CommitOrgMsg),
+    LinkOrg(LinkOrgMsg),
+
+    UpdateRepoConfig(UpdateRepoConfigMsg),
+    GrantRepoRole(GrantRepoRoleMsg),
+    RevokeRepoRole(RevokeRepoRoleMsg),
+    TransferRepoOwnership(TransferRepoOwnershipMsg),
+
+    Pause(PauseMsg),
+    Unpause(UnpauseMsg),
+    PauseRepo(PauseRepoMsg),
+    UnpauseRepo(UnpauseRepoMsg),
+
+    SetFeeExemption(SetFeeExemptionMsg),
+
+    FundTipPool(FundTipPoolMsg),
+    WithdrawTipPool(WithdrawTipPoolMsg),
+
+    RegisterRewardContract(RegisterRewardContractMsg),
+    DeregisterRewardContract(DeregisterRewardContractMsg),
     // /// Only for use in testing. Will be removed in production.
     // AdminResetAll,
 }
 
+/// Update the contract config. Only callable by the contract owner.
+///
+/// Fields that are omitted are left unchanged. Optional config fields are removed by setting them
+/// to `null`.
+#[cw_serde]
+#[derive(Default)]
+pub struct UpdateConfigMsg {
+    pub owner: Option<Addr>,
+    /// Replaces the list of allowed forges.
+    pub allowed_forges: Option<Vec<Forge>>,
+    /// Replaces the list of allowed reclaim provider hashes.
+    pub allowed_provider_hashes: Option<Vec<String>>,
+    /// Replaces or removes the guardian.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_update"
+    )]
+    pub guardian: Option<Option<Addr>>,
+    /// Replaces or removes the protocol fee config.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_update"
+    )]
+    pub fee: Option<Option<FeeConfig>>,
+    /// Enables or disables the reward registry.
+    pub reward_registry_enabled: Option<bool>,
+    /// Replaces or removes the account requirements that are checked when linking accounts.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_update"
+    )]
+    pub account_requirements: Option<Option<AccountRequirements>>,
+}
+
+/// Deserializes a present field into [`Some`], such that `null` removes the value instead of
+/// leaving it unchanged.
+#[allow(clippy::option_option)] // omitted fields are `None`, see `UpdateConfigMsg`
+fn deserialize_update<'de, T: Deserialize<'de>, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Option<T>>, D::Error> {
+    Option::deserialize(deserializer).map(Some)
+}
+
+/// Add a code id or contract to the reward registry. Only callable by the contract owner.
+///
+/// Entries that are already registered are rejected, they must be deregistered first to update
+/// their metadata.
+#[cw_serde]
+pub struct RegisterRewardContractMsg {
+    pub id: RewardContractId,
+    pub metadata: RewardContractMetadata,
+}
+
+/// Remove a code id or contract from the reward registry. Only callable by the contract owner.
+///
+/// Repos that are already configured with the contract are unaffected until their config is
+/// updated.
+#[cw_serde]
+pub struct DeregisterRewardContractMsg {
+    pub id: RewardContractId,
+}
+
+/// Exempt a repo from the protocol fee, or remove an exemption. Only callable by the contract
+/// owner.
+#[cw_serde]
+pub struct SetFeeExemptionMsg {
+    pub repo: Repo,
+    pub exempt: bool,
+}
+
+/// Add the attached funds to the tip pool of a repo, see
+/// [`RelayerTips`][crate::state::RelayerTips]. Callable by anyone.
+#[cw_serde]
+pub struct FundTipPoolMsg {
+    pub repo: Repo,
+}
+
+/// Withdraw funds from the tip pool of a repo to the sender. Only callable by the owner of the repo or
+/// an address with the [`RepoRole::Treasurer`] role.
+#[cw_serde]
+pub struct WithdrawTipPoolMsg {
+    pub repo: Repo,
+    pub amount: Coin,
+}
+
+/// Pause parts of the contract. Only callable by the contract owner or the guardian.
+///
+/// Switches that are `true` are paused until the expiry height, switches that are `false` are left
+/// unchanged. The guardian can only extend the pause of a switch that is already paused, lifting a
+/// pause earlier is reserved to the owner.
+#[cw_serde]
+#[derive(Copy, Default)]
+pub struct PauseMsg {
+    #[serde(default)]
+    pub link_accounts: bool,
+    #[serde(default)]
+    pub link_repos: bool,
+    #[serde(default)]
+    pub rewards: bool,
+    /// The height at which the pause is lifted automatically.
+    pub expires_at_height: Option<u64>,
+}
+
+/// Unpause parts of the contract. Only callable by the contract owner.
+///
+/// Switches that are `true` are unpaused, switches that are `false` are left unchanged.
+#[cw_serde]
+#[derive(Copy, Default)]
+pub struct UnpauseMsg {
+    #[serde(default)]
+    pub link_accounts: bool,
+    #[serde(default)]
+    pub link_repos: bool,
+    #[serde(default)]
+    pub rewards: bool,
+}
+
+/// Send the rewards of a linked user from `reward_contract` to `recipient` instead of the linked
+/// address, or remove the recipient if it is [`None`]. The sender must be the linked address of
+/// the user.
+///
+/// The recipients of a user are removed when the account is linked again.
+#[cw_serde]
+pub struct SetRewardRecipientMsg {
+    #[serde(default)]
+    pub forge: Forge,
+    pub github_user_id: u64,
+    pub reward_contract: RewardContractId,
+    pub recipient: Option<Addr>,
+}
+
+/// Veto the pending reward of a PR during the challenge period of its repo. The rewards of the PR
+/// are never dispatched, and the PR can not be rewarded again.
+///
+/// Requires the [`RepoRole::RewardManager`] role on the repo, or the sender must be the guardian of
+/// the [`ChallengePeriod`][crate::state::ChallengePeriod] of the repo.
+#[cw_serde]
+pub struct VetoRewardMsg {
+    pub repo: Repo,
+    pub pr_id: u64,
+}
+
+/// Dispatch the pending reward of a PR once the challenge period of its repo has ended. Callable by
+/// anyone.
+#[cw_serde]
+pub struct FinalizeRewardMsg {
+    pub repo: Repo,
+    pub pr_id: u64,
+}
+
+/// Mark a rewarded PR as reverted. A [`RewardExecuteMsg::Revoke`] is sent to the reward contract of
+/// every reward that was dispatched for the PR. If a reward contract fails, only that revoke is
+/// reverted and a `revoke_failed` event is emitted.
+///
+/// Requires the [`RepoRole::RewardManager`] role on the repo.
+#[cw_serde]
+pub struct RevertPrMsg {
+    pub repo: Repo,
+    pub pr_id: u64,
+    /// A zktls proof of the merged PR that reverted `pr_id`, in the same format as
+    /// [`RewardPrMsg::proof`]. The description of the PR must reference `pr_id`, for example
+    /// `Reverts org/repo#123`.
+    pub revert_proof: Option<Proof>,
+}
+
+/// Approve the reward of a PR for a label config with an
+/// [`ApprovalRequirement`][crate::state::ApprovalRequirement]. The reward is dispatched once enough
+/// approvers have approved it, or added to the pending reward of the PR if it is still in the
+/// challenge period of its repo.
+///
+/// The sender must be one of the approvers of the label config, and the reward must not have
+/// expired.
+#[cw_serde]
+pub struct ApproveRewardMsg {
+    pub repo: Repo,
+    pub pr_id: u64,
+    pub label_id: u64,
+}
+
+/// Pause rewards for a single repo. Requires the [`RepoRole::Pauser`] role on the repo.
+#[cw_serde]
+pub struct PauseRepoMsg {
+    pub repo: Repo,
+    /// The height at which the pause is lifted automatically.
+    pub expires_at_height: Option<u64>,
+}
+
+/// Unpause rewards for a single repo. Requires the [`RepoRole::Pauser`] role on the repo.
+#[cw_serde]
+pub struct UnpauseRepoMsg {
+    pub repo: Repo,
+}
+
 /// Commit to linking an address with a proof in a future message.
 ///
 /// In order to prevent a user proof being frontrun and assigned to a different recipient address,
@@ -50,6 +303,9 @@ pub struct CommitAccountMsg {
     /// sha256(secret)
     pub commitment_key: Binary,
     // KEY
+    /// The forge of the user account. Defaults to github.
+    #[serde(default)]
+    pub forge: Forge,
     /// The id of the user to be associated with the recipient address.
     pub github_user_id: u64,
     // VALUE
@@ -63,19 +319,30 @@ pub struct CommitAccountMsg {
 /// information).
 #[cw_serde]
 pub struct LinkAccountMsg {
+    /// The forge of the user account. Defaults to github.
+    #[serde(default)]
+    pub forge: Forge,
     /// The address to be linked to the github user id.
     pub recipient_address: Addr,
     /// The zktls proof of the user account. This also contains the user id.
     ///
-    /// This proof is of the <https://api.github.com/user> endpoint, called with an authenticated user. If the call to this endpoint is successfull, we can be sure that the submitter of this proof is indeed the account owner.
+    /// This proof is of the <https://api.github.com/user> endpoint (or the equivalent
+    /// [`Forge::user_url`] of the forge), called with an authenticated user. If the call to this endpoint is successfull, we can be sure that the submitter of this proof is indeed the account owner.
     pub proof: Proof,
     /// The secret that was generated client side during the [`CommitGithubUserId`] step.
     pub secret: Binary,
 }
 
 /// Reward a PR with a zktls proof of the pull request.
+///
+/// A [`RewardExecuteMsg::Reward`] is sent to the reward contract of every label config matching
+/// the labels of the PR. If any reward contract fails, the whole message is reverted and the PR can
+/// be rewarded again.
 #[cw_serde]
 pub struct RewardPrMsg {
+    /// The forge of the PR. Defaults to github.
+    #[serde(default)]
+    pub forge: Forge,
     /// The zktls proof of the PR from github.
     ///
     /// This proof is of the <https://api.github.com/repos/ORG/REPO/pulls/ID> endpoint. The zktls proof verifies the authenticity of the pull request state and contents, ensuring that a user cannot fabricate a PR that will give them unearned rewards.
@@ -87,7 +354,8 @@ pub struct RewardPrMsg {
 /// Linking a repo uses the same commit/reveal schema as [`CommitAccountMsg`].
 #[cw_serde]
 pub struct CommitRepoMsg {
-    /// sha256(secret)
+    /// sha256(secret || sender), where sender is the address that will send the [`LinkRepoMsg`]
+    /// and own the repo.
     pub commitment_key: Binary,
     // KEY
     /// The repo that will be linked.
@@ -98,6 +366,9 @@ pub struct CommitRepoMsg {
 }
 
 /// Link a repo as an admin. This follows the same commit/reveal scheme as [`LinkAccount`].
+///
+/// The sender of this message becomes the owner of the repo. Any roles delegated by a previous
+/// owner are cleared.
 #[cw_serde]
 pub struct LinkRepoMsg {
     pub repo: Repo,
@@ -111,6 +382,93 @@ pub struct LinkRepoMsg {
     ///
     /// [endpoint]: https://docs.github.com/en/rest/collaborators/collaborators?apiVersion=2022-11-28#get-repository-permissions-for-a-user
     pub repo_admin_permissions_proof: Proof,
+    /// A zktls proof of the authenticated user endpoint, generated by the same prover as
+    /// `repo_admin_permissions_proof`, identifying the admin that links the repo.
+    ///
+    /// This is required if the repo proof does not identify the admin, which is the case for repos
+    /// owned by an org and for repos on gitea and gitlab. The admin is recorded for
+    /// [`SelfDealingPolicy::reject_linker`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub admin_user_proof: Option<Proof>,
+}
+
+/// Commit to linking an organization as an org admin.
+///
+/// Linking an org uses the same commit/reveal schema as [`CommitAccountMsg`].
+#[cw_serde]
+pub struct CommitOrgMsg {
+    /// sha256(secret || sender), where sender is the address that will send the [`LinkOrgMsg`] and
+    /// own the org.
+    pub commitment_key: Binary,
+    // KEY
+    /// The forge of the org. Defaults to github. Only github and github enterprise support linking
+    /// orgs.
+    #[serde(default)]
+    pub forge: Forge,
+    /// The org that will be linked.
+    pub org: String,
+    // VALUE
+    /// The default config for every repo in the org.
+    pub config: RepoConfig,
+}
+
+/// Link an org as an org admin. This follows the same commit/reveal scheme as [`LinkAccount`].
+///
+/// The config is stored as the default config for all repos in the org. Repos that are linked
+/// individually with [`LinkRepoMsg`] override this default.
+///
+/// The sender of this message becomes the owner of every repo in the org that has no owner of its
+/// own. Any roles delegated in those repos by a previous owner of the org are cleared.
+#[cw_serde]
+pub struct LinkOrgMsg {
+    #[serde(default)]
+    pub forge: Forge,
+    pub org: String,
+    pub config: RepoConfig,
+    /// The secret that was generated client side during the [`CommitOrgMsg`] step.
+    pub secret: Binary,
+    /// The zktls proof of the user's membership in the github org.
+    ///
+    /// This is a proof of the (`/user/memberships/orgs/{org}`)[endpoint] endpoint, which returns
+    /// the membership of the authenticated user, and the membership must be active with the `admin`
+    /// role.
+    ///
+    /// [endpoint]: https://docs.github.com/en/rest/orgs/members?apiVersion=2022-11-28#get-an-organization-membership-for-the-authenticated-user
+    pub org_admin_membership_proof: Proof,
+}
+
+/// Replace the config of a linked repo.
+///
+/// The sender must be the repo owner or hold the [`RepoRole::ConfigEditor`] role.
+#[cw_serde]
+pub struct UpdateRepoConfigMsg {
+    pub repo: Repo,
+    pub config: RepoConfig,
+}
+
+/// Grant a role on a linked repo to an address. Only callable by the repo owner.
+#[cw_serde]
+pub struct GrantRepoRoleMsg {
+    pub repo: Repo,
+    pub address: Addr,
+    pub role: RepoRole,
+}
+
+/// Revoke a previously granted role on a linked repo. Only callable by the repo owner.
+#[cw_serde]
+pub struct RevokeRepoRoleMsg {
+    pub repo: Repo,
+    pub address: Addr,
+    pub role: RepoRole,
+}
+
+/// Transfer ownership of a linked repo to a new address. Only callable by the repo owner.
+///
+/// Any roles delegated by the previous owner are cleared, the same as when the repo is linked.
+#[cw_serde]
+pub struct TransferRepoOwnershipMsg {
+    pub repo: Repo,
+    pub new_owner: Addr,
 }
 
 #[cw_serde]
@@ -121,94 +479,309 @@ pub enum QueryMsg {
     /// Returns the linked account of a github user, or [`None`] if the user has not yet been
     /// linked.
     #[returns(Option<Addr>)]
-    LinkedAddress { github_user_id: u64 },
+    LinkedAddress {
+        #[serde(default)]
+        forge: Forge,
+        github_user_id: u64,
+    },
     #[returns(Option<Commitment<Addr>>)]
-    UserCommitment { github_user_id: u64 },
+    UserCommitment {
+        #[serde(default)]
+        forge: Forge,
+        github_user_id: u64,
+    },
     #[returns(Option<Commitment<RepoConfig>>)]
     RepoCommitment { repo: Repo },
     /// Returns all of the configured repos.
     #[returns(Vec<Repo>)]
     Repos {},
-    /// Returns all of the configured repos.
+    /// Returns the config of a repo that has been linked individually. This does not include the
+    /// default config of the repo's org, see [`QueryMsg::OrgConfig`].
     #[returns(Option<RepoConfig>)]
     RepoConfig { repo: Repo },
-    /// Returns whether a PR is eligible for rewards.
+    /// Returns the owner and all delegated roles of a repo.
+    #[returns(RepoRolesResponse)]
+    RepoRoles { repo: Repo },
+    #[returns(Option<Commitment<RepoConfig>>)]
+    OrgCommitment {
+        #[serde(default)]
+        forge: Forge,
+        org: String,
+    },
+    /// Returns all of the configured orgs.
+    #[returns(Vec<LinkedOrg>)]
+    Orgs {},
+    /// Returns the default repo config of an org.
+    #[returns(Option<RepoConfig>)]
+    OrgConfig {
+        #[serde(default)]
+        forge: Forge,
+        org: String,
+    },
+    /// Returns the global pause state that is in effect at the current height.
+    #[returns(Pause)]
+    PauseStatus {},
+    /// Returns the pause of a repo, or [`None`] if the repo is not paused at the current height.
+    #[returns(Option<RepoPause>)]
+    RepoPauseStatus { repo: Repo },
+    /// Runs every validation that [`ExecuteMsg::RewardPr`] would run without writing any state,
+    /// and queries [`RewardQueryMsg::Rewards`] on the reward contract of every matching label
+    /// config.
+    ///
+    /// NOTE: The signatures of the proof are not verified.
+    #[returns(SimulateRewardPrResponse)]
+    SimulateRewardPr(SimulateRewardPrMsg),
+    /// Returns the code ids and contracts in the reward registry, ordered by code id and then by
+    /// address, with all code ids listed before the contracts. `limit` defaults to 30 and is
+    /// capped at 100.
+    #[returns(Vec<RegisteredRewardContract>)]
+    RewardRegistry {
+        start_after: Option<RewardContractId>,
+        limit: Option<u32>,
+    },
+    /// Returns all of the repos that are exempt from the protocol fee.
+    #[returns(Vec<Repo>)]
+    FeeExemptRepos {},
+    /// Returns the total amount of flat fees collected, per denom. This does not include fees
+    /// deducted by reward contracts.
+    #[returns(Vec<Coin>)]
+    AccumulatedFees {},
+    /// Returns the funds remaining in the tip pool of a repo, per denom.
+    #[returns(Vec<Coin>)]
+    TipPool { repo: Repo },
+    /// Returns all of the users that have been linked to an address.
+    #[returns(Vec<LinkedUser>)]
+    UserByAddress { address: Addr },
+    /// Returns the amount of PRs that a user has been rewarded for, in total and per repo.
+    ///
+    /// The per repo stats are paginated, ordered by repo.
+    #[returns(UserStatsResponse)]
+    UserStats {
+        #[serde(default)]
+        forge: Forge,
+        github_user_id: u64,
+        start_after: Option<Repo>,
+        limit: Option<u32>,
+    },
+    /// Returns the PRs that a user has been rewarded for, ordered by repo and then PR id.
+    #[returns(Vec<RewardedPrRef>)]
+    UserRewardedPrs {
+        #[serde(default)]
+        forge: Forge,
+        github_user_id: u64,
+        start_after: Option<RewardedPrRef>,
+        limit: Option<u32>,
+    },
+    /// Returns whether a PR is eligible for rewards. The user is looked up on the forge of the
+    /// repo.
     #[returns(PrEligibility)]
     QueryPrEligibility {
         repo: Repo,
         pr_id: u64,
         github_user_id: u64,
     },
+    /// Returns how many more PRs of a user can be rewarded in a repo before the
+    /// [`RateLimits`][crate::state::RateLimits] of the repo are reached. The user is looked up on
+    /// the forge of the repo.
+    #[returns(RewardAllowanceResponse)]
+    RewardAllowance { repo: Repo, github_user_id: u64 },
+    /// Returns the account details of a linked user, or [`None`] if the user was not linked with a
+    /// [`ProofKind::UserProfile`][crate::models::reclaim::ProofKind::UserProfile] proof.
+    #[returns(Option<UserProfile>)]
+    UserProfile {
+        #[serde(default)]
+        forge: Forge,
+        github_user_id: u64,
+    },
+    /// Returns the recipients that a linked user has set for the rewards of specific reward
+    /// contracts.
+    #[returns(Vec<RewardRecipient>)]
+    RewardRecipients {
+        #[serde(default)]
+        forge: Forge,
+        github_user_id: u64,
+    },
+    /// Returns the rewards of a repo that are waiting for its challenge period to end, ordered by
+    /// PR id.
+    #[returns(Vec<PendingReward>)]
+    PendingRewards {
+        repo: Repo,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the rewards of a repo that are waiting for approval, ordered by PR id. `limit`
+    /// applies to the number of PRs, not rewards.
+    #[returns(Vec<PendingApproval>)]
+    PendingApprovals {
+        repo: Repo,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
-pub enum PrEligibility {
-    /// The PR has already been claimed.
-    Claimed,
-    /// The PR is eligble for rewards.
-    Eligible,
-    /// The PR is not eligible for rewards, either because the user has not yet linked their
-    /// account or the repo is not configured for rewards.
-    Ineligible,
+pub struct LinkedOrg {
+    /// The forge of this org. This is omitted for github orgs.
+    #[serde(default, skip_serializing_if = "Forge::is_github")]
+    pub forge: Forge,
+    pub org: String,
+}
+
+#[cw_serde]
+pub enum SimulateRewardPrMsg {
+    /// Simulate a [`ExecuteMsg::RewardPr`] with the provided proof.
+    Proof(RewardPrMsg),
+    /// Simulate rewarding a merged pull request without a proof.
+    PullRequest {
+        repo: Repo,
+        pr_id: u64,
+        /// The id of the author of the pull request, on the forge of `repo`.
+        github_user_id: u64,
+        label_ids: Vec<u64>,
+    },
 }
 
-/// The migration message for lazydev.
-///
-/// This is empty since no migrations are currently supported or required.
 #[cw_serde]
-pub struct MigrateMsg {}
+pub struct SimulateRewardPrResponse {
+    /// The error that the reward would fail with, if any. If this is set, no rewards would be paid
+    /// out.
+    pub error: Option<String>,
+    /// The address that the rewards would be sent to.
+    pub recipient_address: Option<Addr>,
+    /// The ordinal of the PR among the rewarded PRs of its author in the repo, see
+    /// [`Tier`][crate::state::Tier].
+    pub ordinal: Option<u64>,
+    /// The flat protocol fee that must be attached to the [`ExecuteMsg::RewardPr`] message.
+    pub flat_fee: Option<Coin>,
+    /// The rewards of every label config matching the labels of the pull request.
+    pub labels: Vec<LabelRewards>,
+    /// The policy checks of the repo that were skipped, since the data they depend on is only
+    /// available in a proof. These are always empty when simulating with a proof, and may still
+    /// fail when the PR is rewarded.
+    pub unverified: Vec<UnverifiedCheck>,
+}
 
-/// ExecuteMsg interface for the verifier contract.
+/// A policy check of a repo that [`SimulateRewardPrMsg::PullRequest`] can not perform.
 #[cw_serde]
-pub enum VerifierMsg {
-    VerifyProof(VerifyProofMsg),
+pub enum UnverifiedCheck {
+    /// [`RateLimits::min_merge_delay`][crate::state::RateLimits::min_merge_delay], which depends
+    /// on the creation and merge timestamps of the PR.
+    MinMergeDelay,
+    /// [`SelfDealingPolicy::reject_self_merged`][crate::state::SelfDealingPolicy::reject_self_merged],
+    /// which depends on the user that merged the PR.
+    RejectSelfMerged,
+    /// [`SelfDealingPolicy::allowed_associations`][crate::state::SelfDealingPolicy::allowed_associations],
+    /// which depends on the association of the author of the PR with the repo.
+    AllowedAssociations,
 }
 
 #[cw_serde]
-pub struct VerifyProofMsg {
-    pub proof: Proof,
+pub struct LabelRewards {
+    pub label_id: u64,
+    pub reward_contract: Addr,
+    /// The response of the reward contract, or [`None`] if the query failed.
+    pub rewards: Option<QueryRewardsResponse>,
+    /// The error returned by the reward contract, if the query failed.
+    pub error: Option<String>,
+}
+
+#[cw_serde]
+pub struct RegisteredRewardContract {
+    pub id: RewardContractId,
+    pub metadata: RewardContractMetadata,
+}
+
+#[cw_serde]
+pub struct LinkedUser {
+    /// The forge of this user. This is omitted for github users.
+    #[serde(default, skip_serializing_if = "Forge::is_github")]
+    pub forge: Forge,
+    pub github_user_id: u64,
 }
 
-/// ExecuteMsg interface for reward contracts.
 #[cw_serde]
-pub enum RewardExecuteMsg {
-    Reward(RewardMsg),
+pub struct RewardAllowanceResponse {
+    /// The allowance of the user in the repo, or [`None`] if the repo has no per user limit.
+    pub user: Option<Allowance>,
+    /// The allowance of the repo, or [`None`] if the repo has no per repo limit.
+    pub repo: Option<Allowance>,
 }
 
-/// The reward callback message for the reward contracts. This is expected to be
-/// in an `ExecuteMsg` under the key `"reward"`.
 #[cw_serde]
-pub struct RewardMsg {
-    /// The repo that the rewards are for.
+pub struct Allowance {
+    /// The amount of PRs that can still be rewarded in the current period.
+    pub remaining: u64,
+    /// The end of the current period.
+    pub resets_at: Timestamp,
+}
+
+#[cw_serde]
+pub struct UserStatsResponse {
+    /// The total amount of PRs that the user has been rewarded for, across all repos.
+    pub rewarded_prs: u64,
+    pub repos: Vec<RepoStats>,
+}
+
+#[cw_serde]
+pub struct RepoStats {
+    pub repo: Repo,
+    /// The amount of PRs that the user has been rewarded for in this repo.
+    pub rewarded_prs: u64,
+}
+
+#[cw_serde]
+pub struct RewardedPrRef {
     pub repo: Repo,
-    /// The github pr id that the rewards are for.
     pub pr_id: u64,
-    /// The github user id of the user receiving the reward.
-    pub user_id: u64,
-    /// The recipient address to send the rewards to.
-    pub recipient_address: Addr,
-    /// Additional reward config.
-    pub reward_config: String,
 }
 
-/// QueryMsg interface for reward contracts.
-///
-/// NOTE: This assumes that the user is eligible for these rewards. Since this query is not
-/// permissioned, it is up to the caller to check for eligibility before making any assumptions.
 #[cw_serde]
-#[derive(QueryResponses)]
-pub enum RewardQueryMsg {
-    /// Query the rewards that this contract will send to recipient_address.
-    #[returns(QueryRewardsResponse)]
-    Rewards(RewardMsg),
+pub struct RepoRolesResponse {
+    /// The owner of the repo, falling back to the owner of its org, or [`None`] if neither the repo
+    /// nor its org has been linked.
+    pub owner: Option<Addr>,
+    /// The forge user id of the admin that linked the repo, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub admin_id: Option<u64>,
+    pub roles: Vec<RepoRoleGrant>,
+}
+
+#[cw_serde]
+pub struct RepoRoleGrant {
+    pub address: Addr,
+    pub roles: Vec<RepoRole>,
+}
+
+#[cw_serde]
+pub enum PrEligibility {
+    /// The PR has already been claimed.
+    Claimed,
+    /// The PR is eligble for rewards.
+    Eligible,
+    /// The PR is not eligible for rewards, either because the user has not yet linked their
+    /// account or neither the repo nor its org is configured for rewards.
+    Ineligible,
 }
 
+/// The migration message for lazydev.
 #[cw_serde]
-pub struct QueryRewardsResponse {
-    /// Whether or not these rewards have already been claimed. If `true`, then `rewards` is the
-    /// *actual* reward that the user received when they originally claimed the rewards.
-    pub claimed: bool,
-    pub rewards: Vec<PrReward>,
+#[derive(Default)]
+pub struct MigrateMsg {
+    /// The owner of the contract, required when migrating from a version without forge support,
+    /// since the config of those versions has no owner.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<Addr>,
+}
+
+/// `ExecuteMsg` interface for the verifier contract.
+#[cw_serde]
+pub enum VerifierMsg {
+    VerifyProof(VerifyProofMsg),
+}
+
+#[cw_serde]
+pub struct VerifyProofMsg {
+    pub proof: Proof,
 }
 
 #[cfg(test)]
@@ -1,40 +1,309 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Binary};
-use cw_storage_plus::{Item, Key, KeyDeserialize, Map, PrimaryKey};
+use cosmwasm_std::{Addr, Binary, Coin, Timestamp, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+pub use lazydev_reward_interface::state::Repo;
+
+use crate::{
+    forge::Forge,
+    models::github::AuthorAssociation,
+    msg::{ProtocolFee, RewardMsg},
+};
 
 #[cw_serde]
 pub struct Config {
+    /// The owner of the contract, able to update this config.
+    pub owner: Addr,
     pub verifier_address: Addr,
     pub commitment_delay_min_height: u64,
     pub commitment_delay_max_height: u64,
+    /// Forges other than [`Forge::Github`] that repos and users are allowed to be linked from.
+    pub allowed_forges: Vec<Forge>,
+    /// Reclaim provider hashes that proofs are allowed to be generated with. If empty, any provider
+    /// hash is accepted.
+    pub allowed_provider_hashes: Vec<String>,
+    /// An address that is able to pause the contract in addition to the owner, see [`PAUSE`].
+    pub guardian: Option<Addr>,
+    /// The protocol fee charged on rewards, if any.
+    pub fee: Option<FeeConfig>,
+    /// Whether label configs may only use reward contracts in the reward registry, see
+    /// [`REWARD_CODE_IDS`] and [`REWARD_CONTRACTS`].
+    #[serde(default)]
+    pub reward_registry_enabled: bool,
+    /// Requirements that accounts must meet to be linked, see [`AccountRequirements`].
+    #[serde(default)]
+    pub account_requirements: Option<AccountRequirements>,
+}
+
+#[cw_serde]
+pub struct FeeConfig {
+    /// The address that all fees are sent to.
+    pub treasury: Addr,
+    /// A flat fee in native funds that must be attached to every
+    /// [`RewardPr`][crate::msg::ExecuteMsg::RewardPr].
+    pub flat_fee: Option<Coin>,
+    /// A fee in basis points that reward contracts are asked to deduct from each reward, see
+    /// [`ProtocolFee`].
+    pub bps: u16,
+}
+
+/// A flat protocol fee attached to a `RewardPr`, see [`FeeConfig::flat_fee`].
+#[cw_serde]
+pub struct FlatFee {
+    pub fee: Coin,
+    pub treasury: Addr,
+}
+
+impl FeeConfig {
+    /// The fee that reward contracts are asked to deduct from each reward, if any.
+    #[must_use]
+    pub fn protocol_fee(&self) -> Option<ProtocolFee> {
+        (self.bps > 0).then(|| ProtocolFee {
+            bps: self.bps,
+            treasury: self.treasury.clone(),
+        })
+    }
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
 
-pub const USERS: Map<u64, Addr> = Map::new("users");
+/// The global pause state. If this is not set, nothing is paused.
+pub const PAUSE: Item<Pause> = Item::new("pause");
+
+/// Repos that are exempt from the protocol fee.
+pub const FEE_EXEMPT_REPOS: Map<Repo, ()> = Map::new("fee_exempt_repos");
+
+/// The total amount of flat fees collected, keyed by denom.
+pub const ACCUMULATED_FEES: Map<String, Uint128> = Map::new("accumulated_fees");
+
+/// Funds that repos have deposited to tip relayers, keyed by `(repo, denom)`, see [`RelayerTips`].
+pub const TIP_POOLS: Map<(Repo, String), Uint128> = Map::new("tip_pools");
+
+/// The number of relayer tips that each repo paid in the latest block that it paid tips in, see
+/// [`RelayerTips::max_per_block`].
+pub const TIP_USAGE: Map<Repo, TipUsage> = Map::new("tip_usage");
+
+/// The addresses that linked accounts on behalf of their users, keyed by `(forge, user_id)`. These
+/// are tipped once a PR of the user is rewarded, see [`RelayerTips::link_account`].
+pub const LINK_RELAYERS: Map<UserKey, Addr> = Map::new("link_relayers");
+
+/// Code ids of reward contracts registered by the owner. Any instance of these codes may be used as
+/// a reward contract while [`Config::reward_registry_enabled`] is set.
+pub const REWARD_CODE_IDS: Map<u64, RewardContractMetadata> = Map::new("reward_code_ids");
+
+/// Individual reward contracts registered by the owner, regardless of their code id.
+pub const REWARD_CONTRACTS: Map<Addr, RewardContractMetadata> = Map::new("reward_contracts");
+
+/// Individually paused repos. Rewards for PRs in these repos are rejected.
+pub const REPO_PAUSES: Map<Repo, RepoPause> = Map::new("repo_pauses");
+
+/// Linked accounts, keyed by `(forge, user_id)`.
+///
+/// Indexed by the linked address, see [`UserIndexes`].
+pub const USERS: IndexedMap<(Forge, u64), Addr, UserIndexes> = IndexedMap::new(
+    "users",
+    UserIndexes {
+        address: MultiIndex::new(|_, address| address.clone(), "users", "users__address"),
+    },
+);
+
+pub struct UserIndexes<'a> {
+    /// Reverse lookup of linked address to `(forge, user_id)`. An address may be linked to more than
+    /// one user.
+    pub address: MultiIndex<'a, Addr, Addr, (Forge, u64)>,
+}
+
+impl IndexList<Addr> for UserIndexes<'_> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Addr>> + '_> {
+        let v: Vec<&dyn Index<Addr>> = vec![&self.address];
+        Box::new(v.into_iter())
+    }
+}
 
 /// Set of already submitted proofs, used to prevent replay attacks.
 ///
 /// sha256(json(proof))
 pub const EXISTING_PROOFS: Map<Vec<u8>, ()> = Map::new("existing_proofs");
 
-/// User commitments, keyed by the github user id of the user attempting to link their account.
+/// User commitments, keyed by the forge and user id of the user attempting to link their account.
 ///
 /// The commitment value is the intended recipient address.
-pub const USER_COMMITMENTS: Map<u64, Commitment<Addr>> = Map::new("user_commitments");
+pub const USER_COMMITMENTS: Map<(Forge, u64), Commitment<Addr>> = Map::new("user_commitments");
 
 /// Repo commitments, keyed by the repo being configured.
 ///
 /// The commitment value is the intended repo config.
 pub const REPO_COMMITMENTS: Map<Repo, Commitment<RepoConfig>> = Map::new("repo_commitments");
 
-/// `(org, repo)`
-pub const REPOS: Map<(String, String), RepoConfig> = Map::new("repos");
+pub const REPOS: Map<Repo, RepoConfig> = Map::new("repos");
+
+/// The owner of each individually linked repo.
+///
+/// The owner is the address that submitted the repo admin proof, and implicitly holds every
+/// [`RepoRole`] for the repo.
+pub const REPO_OWNERS: Map<Repo, Addr> = Map::new("repo_owners");
+
+/// The forge user id of the admin that linked each individually linked repo, either identified by
+/// the repo admin proof (see [`RepoAdmin`][crate::forge::RepoAdmin]) or by the admin user proof of
+/// [`LinkRepoMsg`][crate::msg::LinkRepoMsg].
+pub const REPO_ADMIN_IDS: Map<Repo, u64> = Map::new("repo_admin_ids");
+
+/// Roles delegated by repo owners, keyed by `(repo, address)`.
+pub const REPO_ROLES: Map<(Repo, Addr), Vec<RepoRole>> = Map::new("repo_roles");
+
+/// Org commitments, keyed by the forge and org being configured.
+///
+/// The commitment value is the intended default repo config for the org.
+pub const ORG_COMMITMENTS: Map<(Forge, String), Commitment<RepoConfig>> =
+    Map::new("org_commitments");
+
+/// Default repo configs of linked orgs, keyed by `(forge, org)`.
+///
+/// This config applies to every repo in the org that does not have its own entry in [`REPOS`].
+pub const ORGS: Map<(Forge, String), RepoConfig> = Map::new("orgs");
+
+/// The owner of each linked org.
+///
+/// The owner is the address that submitted the org membership proof, and owns every repo of the
+/// org that has no owner in [`REPO_OWNERS`].
+pub const ORG_OWNERS: Map<(Forge, String), Addr> = Map::new("org_owners");
+
+/// The forge user id of the admin that linked each org, if the org membership proof identified
+/// them.
+pub const ORG_ADMIN_IDS: Map<(Forge, String), u64> = Map::new("org_admin_ids");
 
 /// Pull requests that have already been rewarded.
 ///
-/// `(org, repo, pr_id)`
-pub const REWARDED_PRS: Map<(String, String, u64), ()> = Map::new("rewarded_prs");
+/// `(repo, pr_id)`
+pub const REWARDED_PRS: Map<(Repo, u64), RewardedPr> = Map::new("rewarded_prs");
+
+/// Secondary index of [`REWARDED_PRS`] by user.
+///
+/// `((forge, user_id), (repo, pr_id))`
+pub const USER_REWARDED_PRS: Map<(UserKey, PrKey), ()> = Map::new("user_rewarded_prs");
+
+/// `(forge, user_id)`
+pub type UserKey = (Forge, u64);
+
+/// `(repo, pr_id)`
+pub type PrKey = (Repo, u64);
+
+/// The total amount of PRs that each user has been rewarded for, keyed by `(forge, user_id)`.
+pub const USER_STATS: Map<(Forge, u64), u64> = Map::new("user_stats");
+
+/// The account details of linked users, if they were linked with a
+/// [`ProofKind::UserProfile`][crate::models::reclaim::ProofKind::UserProfile] proof.
+pub const USER_PROFILES: Map<UserKey, UserProfile> = Map::new("user_profiles");
+
+/// Recipients that linked users have set for the rewards of specific reward contracts, overriding
+/// the linked address in [`USERS`].
+pub const REWARD_RECIPIENTS: Map<UserKey, Vec<RewardRecipient>> = Map::new("reward_recipients");
+
+/// The usage of the per user [`RateLimits`] of each repo.
+///
+/// `(repo, (forge, user_id))`
+pub const USER_RATE_LIMITS: Map<(Repo, UserKey), RateLimitUsage> = Map::new("user_rate_limits");
+
+/// The usage of the per repo [`RateLimits`] of each repo.
+pub const REPO_RATE_LIMITS: Map<Repo, RateLimitUsage> = Map::new("repo_rate_limits");
+
+/// The amount of PRs that each user has been rewarded for in each repo.
+///
+/// `((forge, user_id), repo)`
+pub const USER_REPO_STATS: Map<((Forge, u64), Repo), u64> = Map::new("user_repo_stats");
+
+/// The amount of PRs that each user has ever been rewarded for in each repo, used for the ordinal of
+/// the next PR of the user. Unlike [`USER_REPO_STATS`], this is not decremented when a reward is
+/// vetoed or a PR is reverted, such that ordinals (and the [`Tier`]s they unlock) are never reused.
+///
+/// `((forge, user_id), repo)`
+pub const USER_REPO_ORDINALS: Map<((Forge, u64), Repo), u64> = Map::new("user_repo_ordinals");
+
+/// Rewards that are waiting for the [`ChallengePeriod`] of their repo to end before they are
+/// dispatched, keyed by `(repo, pr_id)`.
+pub const PENDING_REWARDS: Map<(Repo, u64), PendingReward> = Map::new("pending_rewards");
+
+/// The rewards that were sent to reward contracts for each PR, keyed by `(repo, pr_id)`. These are
+/// revoked if the PR is reverted.
+pub const DISPATCHED_REWARDS: Map<(Repo, u64), Vec<QueuedReward>> = Map::new("dispatched_rewards");
+
+/// Rewards of label configs with an [`ApprovalRequirement`] that are waiting for their approvers,
+/// keyed by `(repo, pr_id)`.
+pub const PENDING_APPROVALS: Map<(Repo, u64), Vec<PendingApproval>> = Map::new("pending_approvals");
+
+#[cw_serde]
+pub struct RewardedPr {
+    /// The id of the author of the PR, on the forge of the repo.
+    pub user_id: u64,
+    /// The address that the rewards were sent to.
+    pub recipient_address: Addr,
+    #[serde(default)]
+    pub status: RewardStatus,
+    /// The id of the PR that reverted this PR, if the PR was reverted with a proof of the revert.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert_pr_id: Option<u64>,
+}
+
+#[cw_serde]
+#[derive(Default, Copy, Eq)]
+pub enum RewardStatus {
+    /// The rewards have been sent to the reward contracts.
+    #[default]
+    Dispatched,
+    /// The rewards are waiting for the challenge period of the repo to end, see
+    /// [`PENDING_REWARDS`].
+    Pending,
+    /// The rewards were vetoed during the challenge period, and will never be dispatched.
+    Vetoed,
+    /// The PR was reverted after its rewards were dispatched, and the rewards were revoked.
+    Reverted,
+    /// The PR was rewarded by a version without forge support, which did not record who the PR was
+    /// rewarded to, see [`legacy::REWARDED_PRS`]. The `user_id` of these PRs is 0 and the
+    /// `recipient_address` is the address of this contract, and they can't be reverted.
+    Legacy,
+}
+
+#[cw_serde]
+pub struct PendingReward {
+    pub pr_id: u64,
+    /// The id of the author of the PR, on the forge of the repo.
+    pub user_id: u64,
+    pub recipient_address: Addr,
+    /// The height from which the reward can be finalized. Until then, it can be vetoed.
+    pub challenge_ends_at_height: u64,
+    /// The time the PR was rewarded at, which is used to refund its [`RateLimitUsage`] if the
+    /// reward is vetoed.
+    pub rewarded_at: Timestamp,
+    /// The sender of the `RewardPr`, who is tipped once the reward is finalized, see
+    /// [`RelayerTips`].
+    pub relayer: Addr,
+    /// The flat protocol fee attached to the `RewardPr`, which is held by the contract until the
+    /// reward is finalized, or refunded to the relayer if the reward is vetoed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub held_fee: Option<FlatFee>,
+    /// The messages that are sent to the reward contracts once the reward is finalized.
+    pub rewards: Vec<QueuedReward>,
+}
+
+/// A reward that is dispatched once `threshold` of `approvers` have approved it, see
+/// [`ApprovalRequirement`].
+#[cw_serde]
+pub struct PendingApproval {
+    pub reward: QueuedReward,
+    pub approvers: Vec<Addr>,
+    pub threshold: u32,
+    /// The approvers that have approved the reward so far.
+    pub approvals: Vec<Addr>,
+    /// The height from which the reward can no longer be approved.
+    pub expires_at_height: u64,
+}
+
+/// A [`RewardMsg`] for the reward contract of a label config.
+#[cw_serde]
+pub struct QueuedReward {
+    pub label_id: u64,
+    pub reward_contract: Addr,
+    pub msg: RewardMsg,
+}
 
 #[cw_serde]
 pub struct Commitment<T> {
@@ -47,60 +316,186 @@ pub struct Commitment<T> {
 }
 
 #[cw_serde]
-pub struct Repo {
-    pub org: String,
-    pub repo: String,
+pub struct RepoConfig {
+    /// All of the reward configurations for this repo. A single label may have multiple configured
+    /// rewards.
+    pub label_configs: Vec<LabelConfig>,
+    /// Limits on how many PRs are rewarded, see [`RateLimits`]. If [`None`], PRs are not limited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limits: Option<RateLimits>,
+    /// Requirements that the account of a PR author must meet for the PR to be rewarded, see
+    /// [`AccountRequirements`]. If [`None`], any linked account is rewarded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_requirements: Option<AccountRequirements>,
+    /// Checks against admins rewarding their own PRs, see [`SelfDealingPolicy`]. If [`None`], no
+    /// checks are done.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub self_dealing: Option<SelfDealingPolicy>,
+    /// A period during which rewards can be vetoed before they are dispatched, see
+    /// [`ChallengePeriod`]. If [`None`], rewards are dispatched immediately.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub challenge_period: Option<ChallengePeriod>,
+    /// Tips for relayers that submit messages on behalf of contributors, see [`RelayerTips`]. If
+    /// [`None`], relayers are not tipped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relayer_tips: Option<RelayerTips>,
 }
 
-impl<'a> PrimaryKey<'a> for Repo {
-    type Prefix = <(String, String) as PrimaryKey<'a>>::Prefix;
+/// Tips paid from the [`TIP_POOLS`] of a repo to relayers, so that contributors without gas tokens
+/// don't have to submit their own messages. Tips are skipped, without failing the message, once
+/// `max_per_block` tips have been paid in a block or the tip pool runs out.
+#[cw_serde]
+pub struct RelayerTips {
+    /// The tip for the sender of a [`RewardPr`][crate::msg::ExecuteMsg::RewardPr]. Since a PR can
+    /// only be rewarded once, it is only tipped once.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reward_pr: Option<Coin>,
+    /// The tip for the sender of a [`LinkAccount`][crate::msg::ExecuteMsg::LinkAccount] on behalf
+    /// of another address, paid when a PR of the linked user is rewarded. Linking itself is not
+    /// tipped, since accounts could be linked just to collect tips.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_account: Option<Coin>,
+    pub max_per_block: u32,
+}
 
-    type SubPrefix = <(String, String) as PrimaryKey<'a>>::SubPrefix;
+#[cw_serde]
+pub struct TipUsage {
+    pub height: u64,
+    pub count: u32,
+}
+
+/// Rewards of PRs in a repo with a challenge period are recorded in [`PENDING_REWARDS`] instead of
+/// being dispatched. Until `blocks` blocks have passed they can be vetoed by the repo owner, any
+/// [`RepoRole::RewardManager`], or the `guardian`; afterwards anyone can finalize them.
+#[cw_serde]
+pub struct ChallengePeriod {
+    pub blocks: u64,
+    /// An address that is able to veto rewards in addition to the repo owner and reward managers.
+    /// This is the only address able to veto rewards of repos configured by their org.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guardian: Option<Addr>,
+}
 
-    type Suffix = <(String, String) as PrimaryKey<'a>>::Suffix;
+/// Policies against admins labelling and merging their own PRs to drain the rewards of a repo.
+#[cw_serde]
+#[derive(Default)]
+pub struct SelfDealingPolicy {
+    /// Reject PRs that were merged by their author.
+    #[serde(default)]
+    pub reject_self_merged: bool,
+    /// Reject PRs whose author is the admin that linked the repo (or its org), or whose author is
+    /// linked to the address of the repo owner.
+    #[serde(default)]
+    pub reject_linker: bool,
+    /// Only reward PRs whose author has one of these associations with the repo, for example
+    /// `[CONTRIBUTOR, FIRST_TIME_CONTRIBUTOR]`. This is only supported on github. If [`None`], any
+    /// association is rewarded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_associations: Option<Vec<AuthorAssociation>>,
+}
 
-    type SuperSuffix = <(String, String) as PrimaryKey<'a>>::SuperSuffix;
+/// The details of a forge account at the time it was linked.
+#[cw_serde]
+pub struct UserProfile {
+    pub created_at: Timestamp,
+    pub public_repos: u64,
+    pub followers: u64,
+}
 
-    fn key(&self) -> Vec<Key> {
-        // copied from the tuple impl bc this trait is stupid
-        let mut keys = self.org.key();
-        keys.extend(self.repo.key());
-        keys
-    }
+/// Minimum requirements on the [`UserProfile`] of an account, against sybil accounts created only
+/// to farm rewards.
+#[cw_serde]
+#[derive(Default)]
+pub struct AccountRequirements {
+    /// The minimum age of the account in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_account_age: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_public_repos: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_followers: Option<u64>,
 }
 
-impl KeyDeserialize for Repo {
-    type Output = Repo;
+/// Limits against reward farming, for example by merging many trivial PRs in a short time.
+#[cw_serde]
+#[derive(Default)]
+pub struct RateLimits {
+    /// The maximum amount of PRs rewarded per user in the repo.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per_user: Option<RateLimit>,
+    /// The maximum amount of PRs rewarded in the repo, across all users.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per_repo: Option<RateLimit>,
+    /// The minimum time in seconds between the creation and the merge of a PR.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_merge_delay: Option<u64>,
+}
 
-    const KEY_ELEMS: u16 = <(String, String) as KeyDeserialize>::KEY_ELEMS;
+/// At most `max_prs` PRs are rewarded per `period` seconds. Periods are aligned to the unix epoch.
+#[cw_serde]
+pub struct RateLimit {
+    pub max_prs: u64,
+    pub period: u64,
+}
 
-    fn from_vec(value: Vec<u8>) -> cosmwasm_std::StdResult<Self::Output> {
-        let (org, repo) = <(String, String) as KeyDeserialize>::from_vec(value)?;
+impl RateLimit {
+    /// The period that `time` falls in.
+    #[must_use]
+    pub fn window(&self, time: Timestamp) -> u64 {
+        time.seconds() / self.period
+    }
 
-        Ok(Self { org, repo })
+    /// The amount of PRs that can still be rewarded at `time`, given the current `usage`.
+    #[must_use]
+    pub fn remaining(&self, usage: Option<&RateLimitUsage>, time: Timestamp) -> u64 {
+        let used = usage
+            .filter(|usage| usage.window == self.window(time))
+            .map_or(0, |usage| usage.count);
+
+        self.max_prs.saturating_sub(used)
     }
-}
 
-impl<T: Into<String>, U: Into<String>> From<(T, U)> for Repo {
-    fn from((org, repo): (T, U)) -> Self {
-        Self {
-            org: org.into(),
-            repo: repo.into(),
+    /// The time at which the period that `time` falls in ends.
+    #[must_use]
+    pub fn resets_at(&self, time: Timestamp) -> Timestamp {
+        Timestamp::from_seconds((self.window(time) + 1) * self.period)
+    }
+
+    /// `usage` after rewarding a PR at `time`.
+    #[must_use]
+    pub fn record(&self, usage: Option<RateLimitUsage>, time: Timestamp) -> RateLimitUsage {
+        let window = self.window(time);
+
+        match usage {
+            Some(usage) if usage.window == window => RateLimitUsage {
+                window,
+                count: usage.count + 1,
+            },
+            _ => RateLimitUsage { window, count: 1 },
         }
     }
-}
 
-impl From<Repo> for (String, String) {
-    fn from(repo: Repo) -> Self {
-        (repo.org, repo.repo)
+    /// `usage` after refunding a PR that was rewarded at `time`. PRs that were rewarded in an
+    /// earlier period than `usage` no longer count towards it, so nothing is refunded.
+    #[must_use]
+    pub fn refund(&self, usage: RateLimitUsage, time: Timestamp) -> RateLimitUsage {
+        if usage.window == self.window(time) {
+            RateLimitUsage {
+                window: usage.window,
+                count: usage.count.saturating_sub(1),
+            }
+        } else {
+            usage
+        }
     }
 }
 
+/// The amount of PRs rewarded in a [`RateLimit`] period.
 #[cw_serde]
-pub struct RepoConfig {
-    /// All of the reward configurations for this repo. A single label may have multiple configured
-    /// rewards.
-    pub label_configs: Vec<LabelConfig>,
+pub struct RateLimitUsage {
+    /// The period, see [`RateLimit::window`].
+    pub window: u64,
+    pub count: u64,
 }
 
 #[cw_serde]
@@ -114,4 +509,236 @@ pub struct LabelConfig {
     /// Additional configuration that will be passed to the reward contract. This is stringified
     /// json since [`serde_json_wasm`] doesn't support a dynamic `Value`-like object.
     pub reward_config: String,
+    /// Only reward PRs whose ordinal falls within this tier, see [`Tier`]. If [`None`], every PR with
+    /// the label is rewarded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tier: Option<Tier>,
+    /// Only dispatch the reward once it has been approved by the approvers, see
+    /// [`ApprovalRequirement`]. If [`None`], the reward is dispatched with the other rewards of the
+    /// PR.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval: Option<ApprovalRequirement>,
+}
+
+/// Rewards of a label config with an approval requirement are recorded in [`PENDING_APPROVALS`]
+/// when the PR is rewarded, and dispatched as soon as `threshold` of `approvers` have approved
+/// them. If that doesn't happen within `expires_after_blocks` blocks, the reward expires.
+#[cw_serde]
+pub struct ApprovalRequirement {
+    pub approvers: Vec<Addr>,
+    pub threshold: u32,
+    pub expires_after_blocks: u64,
+}
+
+/// A range of contribution ordinals, where the ordinal of a PR is its position among the PRs of its
+/// author that have been rewarded in the repo, starting at 1. PRs that have been vetoed or reverted
+/// still count towards the ordinal.
+///
+/// Tiers select which label configs apply to a PR, for example a first PR bonus (`max_ordinal: 1`)
+/// or a higher reward after 10 PRs (`min_ordinal: 11`).
+#[cw_serde]
+pub struct Tier {
+    /// The lowest ordinal in this tier, inclusive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_ordinal: Option<u64>,
+    /// The highest ordinal in this tier, inclusive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_ordinal: Option<u64>,
+}
+
+impl Tier {
+    #[must_use]
+    pub fn contains(&self, ordinal: u64) -> bool {
+        self.min_ordinal.is_none_or(|min| ordinal >= min)
+            && self.max_ordinal.is_none_or(|max| ordinal <= max)
+    }
+}
+
+/// An entry in the reward registry, see [`Config::reward_registry_enabled`].
+#[cw_serde]
+pub enum RewardContractId {
+    /// Every contract instantiated from this code id.
+    CodeId(u64),
+    /// A single contract.
+    Address(Addr),
+}
+
+/// The recipient of the rewards of a user from `reward_contract`, which is either a single reward
+/// contract or every instance of a code id, e.g. an nft reward contract. A recipient for a single
+/// contract takes precedence over one for its code id.
+#[cw_serde]
+pub struct RewardRecipient {
+    pub reward_contract: RewardContractId,
+    /// This is not validated, so that rewards can be routed to addresses of other chains by reward
+    /// contracts that support it.
+    pub recipient: Addr,
+}
+
+/// Human-readable information about a registered reward contract, for display in frontends.
+#[cw_serde]
+pub struct RewardContractMetadata {
+    pub name: String,
+    pub description: String,
+}
+
+/// The pause state of each [`PauseSwitch`]. Switches that are [`None`] are not paused.
+#[cw_serde]
+#[derive(Default)]
+pub struct Pause {
+    /// Whether linking accounts is paused.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_accounts: Option<SwitchPause>,
+    /// Whether linking repos and orgs is paused.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_repos: Option<SwitchPause>,
+    /// Whether rewarding PRs is paused.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rewards: Option<SwitchPause>,
+}
+
+impl Pause {
+    /// The pause of `switch`, if it is paused.
+    #[must_use]
+    pub fn switch(&self, switch: PauseSwitch) -> Option<SwitchPause> {
+        match switch {
+            PauseSwitch::LinkAccounts => self.link_accounts,
+            PauseSwitch::LinkRepos => self.link_repos,
+            PauseSwitch::Rewards => self.rewards,
+        }
+    }
+
+    pub fn switch_mut(&mut self, switch: PauseSwitch) -> &mut Option<SwitchPause> {
+        match switch {
+            PauseSwitch::LinkAccounts => &mut self.link_accounts,
+            PauseSwitch::LinkRepos => &mut self.link_repos,
+            PauseSwitch::Rewards => &mut self.rewards,
+        }
+    }
+
+    /// Whether `switch` is paused at `height`.
+    #[must_use]
+    pub fn is_paused(&self, switch: PauseSwitch, height: u64) -> bool {
+        self.switch(switch)
+            .is_some_and(|pause| pause.is_active(height))
+    }
+
+    /// Unpause every switch whose pause has expired at `height`.
+    pub fn clear_expired(&mut self, height: u64) {
+        for switch in PauseSwitch::ALL {
+            let pause = self.switch_mut(switch);
+
+            if pause.is_some_and(|pause| !pause.is_active(height)) {
+                *pause = None;
+            }
+        }
+    }
+}
+
+#[cw_serde]
+#[derive(Copy)]
+pub struct SwitchPause {
+    /// The height at which the pause is lifted automatically. If [`None`], the pause stays in effect
+    /// until it is lifted by the owner.
+    pub expires_at_height: Option<u64>,
+}
+
+impl SwitchPause {
+    /// Whether this pause is still in effect at `height`.
+    #[must_use]
+    pub fn is_active(&self, height: u64) -> bool {
+        self.expires_at_height.is_none_or(|expiry| height < expiry)
+    }
+
+    /// Whether this pause is lifted before `other`.
+    #[must_use]
+    pub fn ends_before(&self, other: &SwitchPause) -> bool {
+        match (self.expires_at_height, other.expires_at_height) {
+            (Some(expiry), Some(other_expiry)) => expiry < other_expiry,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+}
+
+/// The individually pausable parts of the contract.
+#[cw_serde]
+#[derive(Copy, Eq)]
+pub enum PauseSwitch {
+    LinkAccounts,
+    LinkRepos,
+    Rewards,
+}
+
+impl PauseSwitch {
+    pub const ALL: [PauseSwitch; 3] = [
+        PauseSwitch::LinkAccounts,
+        PauseSwitch::LinkRepos,
+        PauseSwitch::Rewards,
+    ];
+}
+
+#[cw_serde]
+pub struct RepoPause {
+    /// The height at which the pause is lifted automatically. If [`None`], the pause stays in effect
+    /// until it is lifted by a repo [`RepoRole::Pauser`].
+    pub expires_at_height: Option<u64>,
+}
+
+impl RepoPause {
+    /// Whether this pause is still in effect at `height`.
+    #[must_use]
+    pub fn is_active(&self, height: u64) -> bool {
+        self.expires_at_height.is_none_or(|expiry| height < expiry)
+    }
+}
+
+/// A permission on a linked repo that can be granted to an address by the repo owner.
+#[cw_serde]
+#[derive(Copy, Eq, Hash, PartialOrd, Ord)]
+pub enum RepoRole {
+    /// Can update the repo config.
+    ConfigEditor,
+    /// Can manage funds held on behalf of the repo.
+    Treasurer,
+    /// Can pause and unpause rewards for the repo.
+    Pauser,
+    /// Can veto pending rewards of the repo, see [`ChallengePeriod`], and revoke the rewards of
+    /// reverted PRs.
+    RewardManager,
+}
+
+/// The storage of versions without forge support, which is re-keyed under [`Forge::Github`] when
+/// migrating, see [`migrate`][crate::contract::migrate].
+pub mod legacy {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::Addr;
+    use cw_storage_plus::{Item, Map};
+
+    use super::{Commitment, RepoConfig};
+
+    #[cw_serde]
+    pub struct Config {
+        pub verifier_address: Addr,
+        pub commitment_delay_min_height: u64,
+        pub commitment_delay_max_height: u64,
+    }
+
+    pub const CONFIG: Item<Config> = Item::new("config");
+
+    /// Linked accounts, keyed by github user id.
+    pub const USERS: Map<u64, Addr> = Map::new("users");
+
+    /// User commitments, keyed by github user id.
+    pub const USER_COMMITMENTS: Map<u64, Commitment<Addr>> = Map::new("user_commitments");
+
+    /// Repo commitments, keyed by `(org, repo)`.
+    pub const REPO_COMMITMENTS: Map<(String, String), Commitment<RepoConfig>> =
+        Map::new("repo_commitments");
+
+    /// Repo configs, keyed by `(org, repo)`. The config of these versions only has label configs,
+    /// which deserialize as a [`RepoConfig`] without any of the optional policies.
+    pub const REPOS: Map<(String, String), RepoConfig> = Map::new("repos");
+
+    /// Rewarded PRs, keyed by `(org, repo, pr_id)`.
+    pub const REWARDED_PRS: Map<(String, String, u64), ()> = Map::new("rewarded_prs");
 }
@@ -1,53 +1,216 @@
+use cosmwasm_std::{Addr, Timestamp};
 use sha2::{Digest, Sha256};
 
+/// Parse a `{base}/repos/{org}/{repo}/pulls/{id}` url, as used by github and gitea.
 #[must_use]
-pub fn parse_github_api_pull_request_url(url: &str) -> Option<(&str, &str, u64)> {
-    let ("", tail) = url.split_once("https://api.github.com/repos/")? else {
-        return None;
-    };
+pub fn parse_repos_pull_request_url<'a>(
+    base: &str,
+    url: &'a str,
+) -> Option<(&'a str, &'a str, u64)> {
+    let tail = url.strip_prefix(base)?.strip_prefix("/repos/")?;
     let (org, tail) = tail.split_once('/')?;
     let (repo, id) = tail.split_once("/pulls/")?;
 
+    if repo.contains('/') {
+        return None;
+    }
+
     Some((org, repo, id.parse().ok()?))
 }
 
+/// Parse a `{base}/repos/{org}/{repo}` url, as used by github and gitea.
 #[must_use]
-pub fn parse_github_api_repos_url(url: &str) -> Option<(&str, &str)> {
-    let ("", tail) = url.split_once("https://api.github.com/repos/")? else {
-        return None;
-    };
+pub fn parse_repos_url<'a>(base: &str, url: &'a str) -> Option<(&'a str, &'a str)> {
+    let tail = url.strip_prefix(base)?.strip_prefix("/repos/")?;
     let (org, repo) = tail.split_once('/')?;
 
+    if repo.contains('/') {
+        return None;
+    }
+
     Some((org, repo))
 }
 
+/// Parse a `{base}/user/memberships/orgs/{org}` url, as used by github.
+#[must_use]
+pub fn parse_org_membership_url<'a>(base: &str, url: &'a str) -> Option<&'a str> {
+    let org = url
+        .strip_prefix(base)?
+        .strip_prefix("/user/memberships/orgs/")?;
+
+    if org.is_empty() || org.contains(['/', '?']) {
+        return None;
+    }
+
+    Some(org)
+}
+
+/// Parse a `{base}/projects/{project}/merge_requests?iids[]={iid}&with_labels_details=true` url, as
+/// used by gitlab. The project is returned url-encoded.
+#[must_use]
+pub fn parse_gitlab_merge_request_url<'a>(base: &str, url: &'a str) -> Option<(&'a str, u64)> {
+    let tail = url.strip_prefix(base)?.strip_prefix("/projects/")?;
+    let (project, tail) = tail.split_once("/merge_requests?iids[]=")?;
+    let iid = tail.strip_suffix("&with_labels_details=true")?;
+
+    Some((project, iid.parse().ok()?))
+}
+
+/// Parse a `{base}/projects/{project}` url, as used by gitlab. The project is returned url-encoded.
+#[must_use]
+pub fn parse_gitlab_project_url<'a>(base: &str, url: &'a str) -> Option<&'a str> {
+    let project = url.strip_prefix(base)?.strip_prefix("/projects/")?;
+
+    (!project.contains('/')).then_some(project)
+}
+
+/// Parse an RFC 3339 timestamp, such as `2011-01-26T19:01:12Z` or `2017-04-29T08:46:00.000+02:00`,
+/// as returned by the forge apis. Fractional seconds are truncated.
+#[must_use]
+pub fn parse_rfc3339(s: &str) -> Option<Timestamp> {
+    fn number(s: &str) -> Option<i64> {
+        s.bytes()
+            .all(|b| b.is_ascii_digit())
+            .then(|| s.parse().ok())?
+    }
+
+    let (date, time) = s.split_once(['T', 't', ' '])?;
+
+    let mut date = date.splitn(3, '-');
+    let (year, month, day) = (
+        number(date.next()?)?,
+        number(date.next()?)?,
+        number(date.next()?)?,
+    );
+
+    let (time, offset) = if let Some(time) = time.strip_suffix(['Z', 'z']) {
+        (time, 0)
+    } else {
+        let (time, offset, sign) = if let Some((time, offset)) = time.rsplit_once('+') {
+            (time, offset, 1)
+        } else {
+            let (time, offset) = time.rsplit_once('-')?;
+            (time, offset, -1)
+        };
+        let (hours, minutes) = offset.split_once(':')?;
+
+        (time, sign * (number(hours)? * 3600 + number(minutes)? * 60))
+    };
+
+    let time = time.split_once('.').map_or(time, |(time, _)| time);
+    let mut time = time.splitn(3, ':');
+    let (hour, minute, second) = (
+        number(time.next()?)?,
+        number(time.next()?)?,
+        number(time.next()?)?,
+    );
+
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 {
+        return None;
+    }
+
+    // days since the unix epoch in the proleptic gregorian calendar, see
+    // <https://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    let seconds = days * 86_400 + hour * 3600 + minute * 60 + second - offset;
+
+    Some(Timestamp::from_seconds(u64::try_from(seconds).ok()?))
+}
+
 /// Compute the sha256 hash of the provided bytes.
 #[must_use]
 pub fn sha256(bz: impl AsRef<[u8]>) -> Vec<u8> {
     Sha256::new().chain_update(bz).finalize().to_vec()
 }
 
+/// Compute the commitment key of a repo or org link, `sha256(secret || owner)`.
+///
+/// Binding the commitment to the address that will own the repo or org prevents a revealed secret
+/// and proof from being replayed by another sender.
+#[must_use]
+pub fn owner_commitment_key(secret: impl AsRef<[u8]>, owner: &Addr) -> Vec<u8> {
+    Sha256::new()
+        .chain_update(secret)
+        .chain_update(owner.as_bytes())
+        .finalize()
+        .to_vec()
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
 
+    const GITHUB: &str = "https://api.github.com";
+
     #[test]
-    fn test_parse_github_api_pull_request_url() {
+    fn test_parse_repos_pull_request_url() {
         let url = "https://api.github.com/repos/benluelo/test/pulls/1";
 
         assert_eq!(
-            parse_github_api_pull_request_url(url).unwrap(),
+            parse_repos_pull_request_url(GITHUB, url).unwrap(),
             ("benluelo", "test", 1)
         );
     }
 
     #[test]
-    fn test_parse_github_api_repos_url() {
+    fn test_parse_repos_url() {
         let url = "https://api.github.com/repos/atahanyild/NEVO";
 
         assert_eq!(
-            parse_github_api_repos_url(url).unwrap(),
+            parse_repos_url(GITHUB, url).unwrap(),
             ("atahanyild", "NEVO")
         );
     }
+
+    #[test]
+    fn test_parse_org_membership_url() {
+        let url = "https://api.github.com/user/memberships/orgs/unionlabs";
+
+        assert_eq!(parse_org_membership_url(GITHUB, url).unwrap(), "unionlabs");
+
+        // the membership of another user does not prove anything about the prover
+        assert_eq!(
+            parse_org_membership_url(
+                GITHUB,
+                "https://api.github.com/orgs/unionlabs/memberships/benluelo"
+            ),
+            None
+        );
+        assert_eq!(
+            parse_org_membership_url(
+                GITHUB,
+                "https://api.github.com/user/memberships/orgs/unionlabs/x"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_rfc3339() {
+        assert_eq!(
+            parse_rfc3339("2011-01-26T19:01:12Z"),
+            Some(Timestamp::from_seconds(1_296_068_472))
+        );
+        assert_eq!(
+            parse_rfc3339("2011-01-26T21:01:12.556+02:00"),
+            Some(Timestamp::from_seconds(1_296_068_472))
+        );
+        assert_eq!(parse_rfc3339("2011-01-26"), None);
+    }
+
+    #[test]
+    fn test_parse_gitlab_merge_request_url() {
+        let url = "https://gitlab.com/api/v4/projects/gitlab-org%2Fgitlab/merge_requests?iids[]=1&with_labels_details=true";
+
+        assert_eq!(
+            parse_gitlab_merge_request_url("https://gitlab.com/api/v4", url).unwrap(),
+            ("gitlab-org%2Fgitlab", 1)
+        );
+    }
 }
@@ -137,6 +137,11 @@
           lazydev = buildWasmBinary ".";
           lazydev-token-reward = buildWasmBinary "cosmwasm/reward/token";
           lazydev-nft-reward = buildWasmBinary "cosmwasm/reward/nft";
+          lazydev-splitter-reward = buildWasmBinary "cosmwasm/reward/splitter";
+          lazydev-fiat-reward = buildWasmBinary "cosmwasm/reward/fiat";
+          lazydev-mock-oracle = buildWasmBinary "cosmwasm/reward/mock-oracle";
+          lazydev-pool-reward = buildWasmBinary "cosmwasm/reward/pool";
+          lazydev-reputation-reward = buildWasmBinary "cosmwasm/reward/reputation";
 
           schema =
             let
@@ -164,6 +169,51 @@
                 doCheck = false;
                 meta.mainProgram = "schema";
               };
+
+              lazydev-splitter-reward-schema = craneLib.buildPackage {
+                src = craneLib.cleanCargoSource ./.;
+                cargoExtraArgs = "-p lazydev-splitter-reward";
+                strictDeps = true;
+                CARGO_PROFILE = "dev";
+                doCheck = false;
+                meta.mainProgram = "schema-splitter";
+              };
+
+              lazydev-fiat-reward-schema = craneLib.buildPackage {
+                src = craneLib.cleanCargoSource ./.;
+                cargoExtraArgs = "-p lazydev-fiat-reward";
+                strictDeps = true;
+                CARGO_PROFILE = "dev";
+                doCheck = false;
+                meta.mainProgram = "schema-fiat";
+              };
+
+              lazydev-mock-oracle-schema = craneLib.buildPackage {
+                src = craneLib.cleanCargoSource ./.;
+                cargoExtraArgs = "-p lazydev-mock-oracle";
+                strictDeps = true;
+                CARGO_PROFILE = "dev";
+                doCheck = false;
+                meta.mainProgram = "schema-mock-oracle";
+              };
+
+              lazydev-pool-reward-schema = craneLib.buildPackage {
+                src = craneLib.cleanCargoSource ./.;
+                cargoExtraArgs = "-p lazydev-pool-reward";
+                strictDeps = true;
+                CARGO_PROFILE = "dev";
+                doCheck = false;
+                meta.mainProgram = "schema-pool";
+              };
+
+              lazydev-reputation-reward-schema = craneLib.buildPackage {
+                src = craneLib.cleanCargoSource ./.;
+                cargoExtraArgs = "-p lazydev-reputation-reward";
+                strictDeps = true;
+                CARGO_PROFILE = "dev";
+                doCheck = false;
+                meta.mainProgram = "schema-reputation";
+              };
             in
             pkgs.stdenv.mkDerivation {
               name = "schema";
@@ -182,6 +232,21 @@
                 ${lazydev-nft-reward-schema}/bin/schema
                 mv ./schema $out/lazydev-nft-reward
 
+                ${lazydev-splitter-reward-schema}/bin/schema-splitter
+                mv ./schema $out/lazydev-splitter-reward
+
+                ${lazydev-fiat-reward-schema}/bin/schema-fiat
+                mv ./schema $out/lazydev-fiat-reward
+
+                ${lazydev-mock-oracle-schema}/bin/schema-mock-oracle
+                mv ./schema $out/lazydev-mock-oracle
+
+                ${lazydev-pool-reward-schema}/bin/schema-pool
+                mv ./schema $out/lazydev-pool-reward
+
+                ${lazydev-reputation-reward-schema}/bin/schema-reputation
+                mv ./schema $out/lazydev-reputation-reward
+
               '';
             };
 
@@ -195,6 +260,11 @@
               npx @cosmwasm/ts-codegen generate --plugin client --schema ${schema}/lazydev --out ./frontend/ts/lazydev --name lazydev --no-bundle
               npx @cosmwasm/ts-codegen generate --plugin client --schema ${schema}/lazydev-token-reward --out ./frontend/ts/lazydev-token-reward --name lazydev-token-reward --no-bundle
               npx @cosmwasm/ts-codegen generate --plugin client --schema ${schema}/lazydev-nft-reward --out ./frontend/ts/lazydev-nft-reward --name lazydev-nft-reward --no-bundle
+              npx @cosmwasm/ts-codegen generate --plugin client --schema ${schema}/lazydev-splitter-reward --out ./frontend/ts/lazydev-splitter-reward --name lazydev-splitter-reward --no-bundle
+              npx @cosmwasm/ts-codegen generate --plugin client --schema ${schema}/lazydev-fiat-reward --out ./frontend/ts/lazydev-fiat-reward --name lazydev-fiat-reward --no-bundle
+              npx @cosmwasm/ts-codegen generate --plugin client --schema ${schema}/lazydev-mock-oracle --out ./frontend/ts/lazydev-mock-oracle --name lazydev-mock-oracle --no-bundle
+              npx @cosmwasm/ts-codegen generate --plugin client --schema ${schema}/lazydev-pool-reward --out ./frontend/ts/lazydev-pool-reward --name lazydev-pool-reward --no-bundle
+              npx @cosmwasm/ts-codegen generate --plugin client --schema ${schema}/lazydev-reputation-reward --out ./frontend/ts/lazydev-reputation-reward --name lazydev-reputation-reward --no-bundle
 
             '';
           };
@@ -209,6 +279,11 @@
               lazydev
               lazydev-token-reward
               lazydev-nft-reward
+              lazydev-splitter-reward
+              lazydev-fiat-reward
+              lazydev-mock-oracle
+              lazydev-pool-reward
+              lazydev-reputation-reward
               schema
               ts-codegen
               ;
@@ -2,7 +2,7 @@ import toast from "react-hot-toast";
 import { CosmWasmClient, SigningCosmWasmClient } from "@cosmjs/cosmwasm-stargate";
 import { Decimal } from "@cosmjs/math";
 import { LazydevClient } from "@/ts/lazydev/Lazydev.client";
-import { generateSecret, generateCommitmentKey } from "@/utils/lazydev-helpers";
+import { generateSecret, generateOwnerCommitmentKey } from "@/utils/lazydev-helpers";
 import { toBase64 } from "@cosmjs/encoding";
 import { rpc_url, contract_address } from "@/utils/consts";
 import { ConfigItem, RepoDetails, Session } from "./types";
@@ -21,7 +21,7 @@ export const repoHelpers = {
 
     try {
       const secret = generateSecret();
-      const commitmentKey = generateCommitmentKey(secret);
+      const commitmentKey = generateOwnerCommitmentKey(secret, keplrWalletAddress);
       const repoConfig = {
         label_configs: configurations.map((config) => ({
           label_id: config.labelId,
@@ -1,6 +1,6 @@
 import { toast } from "react-hot-toast";
 import { Decimal } from "@cosmjs/math";
-import { toBase64 } from "@cosmjs/encoding";
+import { toBase64, toUtf8 } from "@cosmjs/encoding";
 import { sha256 } from "@cosmjs/crypto";
 import { SigningCosmWasmClient } from "@cosmjs/cosmwasm-stargate";
 import { Window as KeplrWindow } from "@keplr-wallet/types";
@@ -28,6 +28,13 @@ export function generateCommitmentKey(s: Uint8Array): string {
   return toBase64(sha256(s));
 }
 
+// Generate a base64-encoded commitment key for linking a repo or org.
+// The secret is followed by the address that will send the link, so the link can't be replayed.
+
+export function generateOwnerCommitmentKey(s: Uint8Array, owner: string): string {
+  return toBase64(sha256(new Uint8Array([...s, ...toUtf8(owner)])));
+}
+
 export async function handleConnectWallet({
   connectKeplrWallet,
   setIsLoading,
@@ -1,7 +1,7 @@
 import { SigningCosmWasmClient } from "@cosmjs/cosmwasm-stargate";
 import { Decimal } from "@cosmjs/math";
 import { LazydevClient } from "@/ts/lazydev/Lazydev.client";
-import { generateSecret, generateCommitmentKey } from "@/utils/lazydev-helpers";
+import { generateSecret, generateOwnerCommitmentKey } from "@/utils/lazydev-helpers";
 import { RepoConfig, Repo } from "@/ts/lazydev/Lazydev.types";
 import { rpc_url, contract_address } from "@/utils/consts";
 
@@ -47,7 +47,7 @@ export const commitRepository = async ({
   keplrWalletAddress,
 }: CommitRepoParams) => {
   const secret = generateSecret();
-  const commitmentKey = generateCommitmentKey(secret);
+  const commitmentKey = generateOwnerCommitmentKey(secret, keplrWalletAddress);
 
   try {
     const client = await getSigningClient(keplrWalletAddress);